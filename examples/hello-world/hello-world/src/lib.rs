@@ -17,3 +17,31 @@ pub mod hello_world_index_mod {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_utils::testing::{self, BlockBuilder, TransactionBuilder};
+
+    #[test]
+    fn test_saves_blocks_and_their_transactions() {
+        let block = BlockBuilder::new(7)
+            .transaction(TransactionBuilder::new().id([1; 32].into()))
+            .transaction(TransactionBuilder::new().id([2; 32].into()))
+            .build();
+        let block_id = block.id;
+
+        testing::run_handlers(handle_events_internal, vec![block]).unwrap();
+
+        let block = Block::find(Block::hash().eq(block_id)).unwrap();
+        assert_eq!(block.height, 7);
+
+        for tx_id in [[1; 32], [2; 32]] {
+            let hash = Bytes32::from(tx_id);
+            assert!(Transaction::find(Transaction::hash().eq(hash)).is_some());
+        }
+        assert!(
+            Transaction::find(Transaction::hash().eq(Bytes32::from([3; 32]))).is_none()
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use crate::{
     middleware::AuthenticationMiddleware,
     uses::{
-        get_nonce, graphql_playground, health_check, indexer_status, query_graph,
-        register_indexer_assets, remove_indexer, sql_query, verify_signature,
+        debug_indexer, get_nonce, graphql_playground, health_check, indexer_logs,
+        indexer_status, query_graph, register_indexer_assets, remove_indexer, sql_query,
+        verify_signature,
     },
 };
 
@@ -110,6 +111,8 @@ pub enum ApiError {
     },
     #[error("Other error: {0}")]
     OtherError(String),
+    #[error("Debug error: {0}")]
+    Debug(String),
 }
 
 impl Default for ApiError {
@@ -173,6 +176,9 @@ impl IntoResponse for ApiError {
                 // This is currently the only type of ParseError on the web server
                 (StatusCode::BAD_REQUEST, format!("Invalid asset type: {e}"))
             }
+            ApiError::Debug(e) => {
+                (StatusCode::BAD_REQUEST, format!("Could not debug indexer: {e}"))
+            }
             ApiError::ToolchainVersionMismatch{fuel_indexer_version, toolchain_version} => {
                 (StatusCode::METHOD_NOT_ALLOWED, format!("WASM module toolchain version `{toolchain_version}` does not match fuel-indexer version `{fuel_indexer_version}`"))
             }
@@ -216,6 +222,7 @@ impl WebApi {
             .route("/:namespace/:identifier", post(query_graph))
             .layer(Extension(schema_manager.clone()))
             .layer(Extension(pool.clone()))
+            .layer(Extension(config.clone()))
             .layer(RequestBodyLimitLayer::new(max_body_size));
 
         let mut sql_routes = Router::new();
@@ -262,6 +269,7 @@ impl WebApi {
             .layer(Extension(pool.clone()))
             .layer(Extension(config.clone()))
             .route("/:namespace/:identifier", delete(remove_indexer))
+            .route("/:namespace/:identifier/debug", post(debug_indexer))
             .layer(AuthenticationMiddleware::from(&config))
             .layer(Extension(tx))
             .layer(Extension(pool.clone()))
@@ -286,6 +294,15 @@ impl WebApi {
             .route("/metrics", get(crate::uses::get_metrics))
             .layer(MetricsMiddleware::default());
 
+        let logs_routes = Router::new()
+            .route("/:namespace/:identifier", get(indexer_logs))
+            .layer(AuthenticationMiddleware::from(&config))
+            .layer(Extension(pool.clone()))
+            .layer(Extension(config.clone()));
+
+        #[cfg(feature = "metrics")]
+        let logs_routes = logs_routes.layer(MetricsMiddleware::default());
+
         let auth_routes = Router::new()
             .route("/nonce", get(get_nonce))
             .layer(Extension(pool.clone()))
@@ -311,6 +328,7 @@ impl WebApi {
             .nest("/index", indexer_routes)
             .nest("/graph", graph_routes)
             .nest("/sql", sql_routes)
+            .nest("/logs", logs_routes)
             .nest("/auth", auth_routes);
 
         let app = Router::new()
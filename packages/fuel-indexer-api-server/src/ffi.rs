@@ -38,6 +38,22 @@ pub fn check_wasm_toolchain_version(data: Vec<u8>) -> anyhow::Result<String> {
         "ff_log_data".to_string(),
         Function::new_typed(&mut store, |_: i32, _: i32, _: i32| {}),
     );
+    exports.insert(
+        "ff_log_record".to_string(),
+        Function::new_typed(&mut store, |_: i32, _: i32| {}),
+    );
+    exports.insert(
+        "ff_set_block_height".to_string(),
+        Function::new_typed(&mut store, |_: i32| {}),
+    );
+    exports.insert(
+        "ff_watch_contract".to_string(),
+        Function::new_typed(&mut store, |_: i32, _: i32| {}),
+    );
+    exports.insert(
+        "ff_get_watched_contracts".to_string(),
+        Function::new_typed(&mut store, |_: i32| 0i32),
+    );
 
     let mut imports = imports! {};
     wasmer::Imports::register_namespace(&mut imports, "env", exports);
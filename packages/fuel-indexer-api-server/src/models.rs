@@ -80,3 +80,20 @@ pub struct SqlQuery {
     /// The literal raw SQL query.
     pub query: String,
 }
+
+/// Query parameters of a request to debug an indexer.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DebugQuery {
+    /// Height of the block to run the indexer against.
+    pub block: u32,
+}
+
+/// Query parameters of a request for the logs of an indexer.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct LogsQuery {
+    /// Only return log lines with an ID greater than this one.
+    pub since: Option<i64>,
+
+    /// Maximum number of log lines to return.
+    pub limit: Option<u32>,
+}
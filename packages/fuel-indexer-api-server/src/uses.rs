@@ -1,6 +1,6 @@
 use crate::{
     api::{ApiError, ApiResult, HttpError},
-    models::{Claims, SqlQuery, VerifySignatureRequest},
+    models::{Claims, DebugQuery, LogsQuery, SqlQuery, VerifySignatureRequest},
     sql::SqlQueryValidator,
 };
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
@@ -8,7 +8,7 @@ use async_graphql_axum::GraphQLRequest;
 use async_std::sync::{Arc, RwLock};
 use axum::{
     body::Body,
-    extract::{multipart::Multipart, Extension, Json, Path},
+    extract::{multipart::Multipart, Extension, Json, Path, Query},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
@@ -24,8 +24,8 @@ use fuel_indexer_lib::{
     defaults,
     graphql::GraphQLSchema,
     utils::{
-        FuelClientHealthResponse, ReloadRequest, ServiceRequest, ServiceStatus,
-        StopRequest,
+        DebugRequest, FuelClientHealthResponse, ReloadRequest, ServiceRequest,
+        ServiceStatus, StopRequest,
     },
 };
 use fuel_indexer_schema::db::manager::SchemaManager;
@@ -34,7 +34,7 @@ use hyper_rustls::HttpsConnectorBuilder;
 use jsonwebtoken::{encode, EncodingKey, Header};
 use serde_json::{json, Value};
 use std::{convert::From, str::FromStr, time::Instant};
-use tokio::sync::mpsc::Sender;
+use tokio::sync::{mpsc::Sender, oneshot};
 use tracing::error;
 
 #[cfg(feature = "metrics")]
@@ -49,6 +49,7 @@ pub(crate) async fn query_graph(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(pool): Extension<IndexerConnectionPool>,
     Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(config): Extension<IndexerConfig>,
     req: GraphQLRequest,
 ) -> ApiResult<axum::Json<Value>> {
     match manager
@@ -60,9 +61,15 @@ pub(crate) async fn query_graph(
         Ok(schema) => {
             let dynamic_schema = build_dynamic_schema(&schema)?;
             let user_query = req.0.query.clone();
-            let response =
-                execute_query(req.into_inner(), dynamic_schema, user_query, pool, schema)
-                    .await?;
+            let response = execute_query(
+                req.into_inner(),
+                dynamic_schema,
+                user_query,
+                pool,
+                schema,
+                config.stringify_large_integers,
+            )
+            .await?;
             let data = serde_json::json!({ "data": response });
             Ok(axum::Json(data))
         }
@@ -164,6 +171,78 @@ pub(crate) async fn indexer_status(
     Ok(Json(json!(json)))
 }
 
+/// Return the last lines logged by the handlers of the given indexer, oldest first.
+///
+/// If `since` is given, only the lines logged after the line with that ID are
+/// returned, which allows clients to follow the logs.
+pub(crate) async fn indexer_logs(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Query(query): Query<LogsQuery>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    let mut conn = pool.acquire().await?;
+
+    if config.authentication.enabled {
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    let LogsQuery { since, limit } = query;
+    let limit = limit.map_or(config.indexer_log_lines as i64, i64::from);
+
+    let logs =
+        queries::indexer_logs(&mut conn, &namespace, &identifier, since, limit).await?;
+
+    Ok(Json(json!(logs)))
+}
+
+/// Given an indexer namespace and identifier, send a `ServiceRequest::Debug` to the
+/// service, which runs the indexer against the given block without persisting
+/// anything, and return the report of the run.
+pub(crate) async fn debug_indexer(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Query(query): Query<DebugQuery>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(claims): Extension<Claims>,
+    Extension(config): Extension<IndexerConfig>,
+) -> ApiResult<axum::Json<Value>> {
+    if claims.is_unauthenticated() {
+        return Err(ApiError::Http(HttpError::Unauthorized));
+    }
+
+    if config.authentication.enabled {
+        let mut conn = pool.acquire().await?;
+        queries::indexer_owned_by(&mut conn, &namespace, &identifier, claims.sub())
+            .await
+            .map_err(|_e| ApiError::Http(HttpError::Unauthorized))?;
+    }
+
+    let (response, rx) = oneshot::channel();
+
+    tx.send(ServiceRequest::Debug(DebugRequest {
+        namespace,
+        identifier,
+        block_height: query.block,
+        response,
+    }))
+    .await?;
+
+    let report = rx
+        .await
+        .map_err(|_e| ApiError::Http(HttpError::InternalServer))?
+        .map_err(ApiError::Debug)?;
+
+    Ok(Json(json!(report)))
+}
+
 /// Given an indexer namespace and identifier, remove the indexer from the database
 /// and send a `ServiceRequest::Stop` to the service for this indexer.
 pub(crate) async fn remove_indexer(
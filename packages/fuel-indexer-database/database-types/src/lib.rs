@@ -13,9 +13,10 @@ use chrono::{
     {DateTime, Utc},
 };
 use fuel_indexer_lib::{
+    fully_qualified_namespace,
     graphql::{
         check_for_directive, extract_foreign_key_info, field_id, is_list_type,
-        types::{IdCol, ObjectCol},
+        types::{HistoryCol, IdCol, ObjectCol, RetainCol},
         JoinTableMeta, ParsedGraphQLSchema,
     },
     MAX_ARRAY_LENGTH,
@@ -79,6 +80,9 @@ pub enum ColumnType {
     UID = 25,
     I16 = 26,
     U16 = 27,
+    U256 = 28,
+    I256 = 29,
+    Timestamp = 30,
 }
 
 impl From<ColumnType> for i32 {
@@ -112,6 +116,9 @@ impl From<ColumnType> for i32 {
             ColumnType::UID => 25,
             ColumnType::I16 => 26,
             ColumnType::U16 => 27,
+            ColumnType::U256 => 28,
+            ColumnType::I256 => 29,
+            ColumnType::Timestamp => 30,
         }
     }
 }
@@ -160,6 +167,9 @@ impl From<i32> for ColumnType {
             25 => ColumnType::UID,
             26 => ColumnType::I16,
             27 => ColumnType::U16,
+            28 => ColumnType::U256,
+            29 => ColumnType::I256,
+            30 => ColumnType::Timestamp,
             _ => unimplemented!("Invalid ColumnType: {num}."),
         }
     }
@@ -181,6 +191,7 @@ impl From<&str> for ColumnType {
             "Enum" => ColumnType::Enum,
             "ForeignKey" => ColumnType::ForeignKey,
             "I128" => ColumnType::I128,
+            "I256" => ColumnType::I256,
             "I32" => ColumnType::I32,
             "I64" => ColumnType::I64,
             "I8" => ColumnType::I8,
@@ -189,7 +200,9 @@ impl From<&str> for ColumnType {
             "Json" => ColumnType::Json,
             "Object" => ColumnType::Object,
             "String" => ColumnType::String,
+            "Timestamp" => ColumnType::Timestamp,
             "U128" => ColumnType::U128,
+            "U256" => ColumnType::U256,
             "U32" => ColumnType::U32,
             "U64" => ColumnType::U64,
             "U8" => ColumnType::U8,
@@ -348,6 +361,7 @@ impl Column {
             ColumnType::Bytes64 => "varchar(128)".to_string(),
             ColumnType::Bytes8 => "varchar(16)".to_string(),
             ColumnType::String => "varchar(255)".to_string(),
+            ColumnType::Timestamp => "timestamptz".to_string(),
             ColumnType::ContractId => "varchar(64)".to_string(),
             ColumnType::Enum => "varchar(255)".to_string(),
             ColumnType::ForeignKey => "numeric(20, 0)".to_string(),
@@ -355,13 +369,15 @@ impl Column {
             ColumnType::Identity => "varchar(66)".to_string(),
             ColumnType::I8 => "integer".to_string(),
             ColumnType::I128 => "numeric(39, 0)".to_string(),
+            ColumnType::I256 => "numeric(78, 0)".to_string(),
             ColumnType::I32 => "integer".to_string(),
             ColumnType::I64 => "bigint".to_string(),
             ColumnType::Json => "json".to_string(),
             ColumnType::Object => "bytea".to_string(),
             ColumnType::U8 => "integer".to_string(),
             ColumnType::U128 => "numeric(39, 0)".to_string(),
-            ColumnType::U32 => "integer".to_string(),
+            ColumnType::U256 => "numeric(78, 0)".to_string(),
+            ColumnType::U32 => "bigint".to_string(),
             ColumnType::U64 => "numeric(20, 0)".to_string(),
             ColumnType::UID => "varchar(64)".to_string(),
             ColumnType::U16 => "integer".to_string(),
@@ -379,6 +395,7 @@ impl Column {
                     | ColumnType::I64 => "bigint",
                     ColumnType::U64 => "numeric(20, 0)",
                     ColumnType::U128 | ColumnType::I128 => "numeric(39, 0)",
+                    ColumnType::U256 | ColumnType::I256 => "numeric(78, 0)",
                     ColumnType::Address
                     | ColumnType::Bytes4
                     | ColumnType::Bytes8
@@ -392,6 +409,7 @@ impl Column {
                     | ColumnType::UID => "varchar(64)",
                     ColumnType::Bytes => "bytea",
                     ColumnType::Json => "json",
+                    ColumnType::Timestamp => "timestamptz",
                     _ => unimplemented!(),
                 };
 
@@ -601,6 +619,19 @@ pub struct IndexerStatus {
     pub status_kind: IndexerStatusKind,
     /// Additional status message. Might be empty.
     pub status_message: String,
+    /// Height of the block that was being processed when a handler failed.
+    #[serde(default)]
+    pub block_height: Option<u32>,
+    /// Name of the handler that failed.
+    #[serde(default)]
+    pub handler: Option<String>,
+    /// Height of the block at which rows were last pruned from tables with a
+    /// retention policy.
+    #[serde(default)]
+    pub pruned_block_height: Option<u32>,
+    /// Number of rows deleted the last time tables with a retention policy were pruned.
+    #[serde(default)]
+    pub pruned_rows: Option<u64>,
 }
 
 impl IndexerStatus {
@@ -608,6 +639,10 @@ impl IndexerStatus {
         IndexerStatus {
             status_kind: IndexerStatusKind::Instantiating,
             status_message: "".to_string(),
+            block_height: None,
+            handler: None,
+            pruned_block_height: None,
+            pruned_rows: None,
         }
     }
 
@@ -615,6 +650,10 @@ impl IndexerStatus {
         IndexerStatus {
             status_kind: IndexerStatusKind::Starting,
             status_message: "".to_string(),
+            block_height: None,
+            handler: None,
+            pruned_block_height: None,
+            pruned_rows: None,
         }
     }
 
@@ -622,25 +661,106 @@ impl IndexerStatus {
         IndexerStatus {
             status_kind: IndexerStatusKind::Running,
             status_message,
+            block_height: None,
+            handler: None,
+            pruned_block_height: None,
+            pruned_rows: None,
         }
     }
     pub fn stopped(status_message: String) -> Self {
         IndexerStatus {
             status_kind: IndexerStatusKind::Stopped,
             status_message,
+            block_height: None,
+            handler: None,
+            pruned_block_height: None,
+            pruned_rows: None,
         }
     }
     pub fn error(status_message: String) -> Self {
         IndexerStatus {
             status_kind: IndexerStatusKind::Error,
             status_message,
+            block_height: None,
+            handler: None,
+            pruned_block_height: None,
+            pruned_rows: None,
         }
     }
+
+    /// Status of an indexer whose handler failed while processing the given block.
+    pub fn handler_error(
+        status_message: String,
+        block_height: Option<u32>,
+        handler: Option<String>,
+    ) -> Self {
+        IndexerStatus {
+            status_kind: IndexerStatusKind::Error,
+            status_message,
+            block_height,
+            handler,
+            pruned_block_height: None,
+            pruned_rows: None,
+        }
+    }
+
     pub fn unknown() -> Self {
         IndexerStatus {
             status_kind: IndexerStatusKind::Unknown,
             status_message: "".to_string(),
+            block_height: None,
+            handler: None,
+            pruned_block_height: None,
+            pruned_rows: None,
+        }
+    }
+}
+
+/// A line logged by the handlers of an indexer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexerLog {
+    /// Database ID of the log line. Later lines have greater IDs.
+    ///
+    /// Zero until the line has been persisted.
+    pub id: i64,
+
+    /// Height of the block that was being processed when the line was logged.
+    pub block_height: u32,
+
+    /// Level of the log line (e.g., `INFO`).
+    pub level: String,
+
+    /// Target of the log line, usually the module path of the handler.
+    pub target: String,
+
+    /// Message of the log line.
+    pub message: String,
+
+    /// Key/value fields attached to the log line.
+    pub fields: Vec<(String, String)>,
+
+    /// Time at which the line was logged.
+    #[serde(with = "ts_microseconds")]
+    pub created_at: DateTime<Utc>,
+}
+
+impl fmt::Display for IndexerLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:>5} [block {}] ",
+            self.created_at.format("%Y-%m-%dT%H:%M:%S%.6fZ"),
+            self.level,
+            self.block_height,
+        )?;
+        if !self.target.is_empty() {
+            write!(f, "{}: ", self.target)?;
         }
+        write!(f, "{}", self.message)?;
+        for (key, value) in &self.fields {
+            write!(f, " {key}={value}")?;
+        }
+        Ok(())
     }
 }
 
@@ -926,6 +1046,12 @@ pub struct Table {
     /// The type of table.
     #[allow(unused)]
     table_type: TableType,
+
+    /// Whether every version of the rows of this table is kept in a history table.
+    history: bool,
+
+    /// Whether the rows of this table are pruned once they fall behind a retention horizon.
+    retained: bool,
 }
 
 impl SqlNamed for Table {
@@ -946,6 +1072,109 @@ impl Table {
         &self.columns
     }
 
+    /// The history table of this table, if it keeps history.
+    ///
+    /// A history table has the columns of its table, along with the range of block
+    /// heights for which each version of a row was current: from `block_height_start`
+    /// up to, but not including, `block_height_end`, which is `NULL` for the current
+    /// version.
+    pub fn history_table(&self) -> Option<Table> {
+        if !self.history {
+            return None;
+        }
+
+        let name = HistoryCol::table_name(&self.name);
+        let namespace = fully_qualified_namespace(&self.namespace, &self.identifier);
+
+        let mut columns = self
+            .columns
+            .iter()
+            .map(|c| Column {
+                // A row has many versions, so the `ID` is not a primary key here.
+                coltype: if c.coltype == ColumnType::ID {
+                    ColumnType::UID
+                } else {
+                    c.coltype
+                },
+                unique: false,
+                ..c.clone()
+            })
+            .collect::<Vec<Column>>();
+
+        for (name, nullable) in [
+            (HistoryCol::block_height_start(), false),
+            (HistoryCol::block_height_end(), true),
+        ] {
+            columns.push(Column {
+                name: name.to_string(),
+                graphql_type: ColumnType::I64.to_string(),
+                coltype: ColumnType::I64,
+                position: columns.len() as i32,
+                nullable,
+                persistence: Persistence::Scalar,
+                ..Column::default()
+            });
+        }
+
+        let constraints = vec![
+            Constraint::Pk(PrimaryKey {
+                db_type: DbType::Postgres,
+                namespace: namespace.clone(),
+                table_name: name.clone(),
+                column_names: vec![
+                    IdCol::to_lowercase_string(),
+                    HistoryCol::block_height_start().to_string(),
+                ],
+            }),
+            Constraint::Index(SqlIndex {
+                db_type: DbType::Postgres,
+                namespace,
+                table_name: name.clone(),
+                column_name: HistoryCol::block_height_end().to_string(),
+                ..SqlIndex::default()
+            }),
+        ];
+
+        Some(Self {
+            name,
+            namespace: self.namespace.clone(),
+            identifier: self.identifier.clone(),
+            columns,
+            constraints,
+            persistence: Persistence::Scalar,
+            table_type: TableType::Regular,
+            history: false,
+            retained: false,
+        })
+    }
+
+    /// Return the statements adding, to a table with a retention policy, the column
+    /// recording the height of the block at which each row was last written, along
+    /// with its index.
+    pub fn retention_stmnts(&self) -> Vec<String> {
+        if !self.retained {
+            return vec![];
+        }
+
+        let namespace = fully_qualified_namespace(&self.namespace, &self.identifier);
+
+        vec![
+            format!(
+                "ALTER TABLE {namespace}.{} ADD COLUMN {} bigint;",
+                self.name,
+                RetainCol::block_height()
+            ),
+            SqlIndex {
+                db_type: DbType::Postgres,
+                namespace,
+                table_name: self.name.clone(),
+                column_name: RetainCol::block_height().to_string(),
+                ..SqlIndex::default()
+            }
+            .create(),
+        ]
+    }
+
     /// Create a new `Table` from a given `TypeDefinition`.
     pub fn from_typedef(typ: &TypeDefinition, parsed: &ParsedGraphQLSchema) -> Self {
         let ty_id = type_id(&parsed.fully_qualified_namespace(), &typ.name.to_string());
@@ -960,7 +1189,10 @@ impl Table {
                 let mut columns = o
                     .fields
                     .iter()
-                    .filter(|f| !check_for_directive(&f.node.directives, "internal"))
+                    .filter(|f| {
+                        !check_for_directive(&f.node.directives, "internal")
+                            && !check_for_directive(&f.node.directives, "derivedFrom")
+                    })
                     .enumerate()
                     .map(|(i, f)|
                         Column::from_field_def(
@@ -1044,7 +1276,9 @@ impl Table {
                     columns,
                     constraints,
                     persistence,
-                    table_type: TableType::Regular
+                    table_type: TableType::Regular,
+                    history: parsed.is_history_typedef(&typ.name.to_string()),
+                    retained: parsed.retained_blocks(&typ.name.to_string()).is_some(),
                 }
             }
             TypeKind::Union(u) => {
@@ -1156,7 +1390,7 @@ impl Table {
             },
         ];
 
-        let constraints = vec![
+        let mut constraints = vec![
             Constraint::Fk(ForeignKey {
                 db_type: DbType::Postgres,
                 namespace: parsed.fully_qualified_namespace(),
@@ -1187,16 +1421,6 @@ impl Table {
                 ref_coltype: ColumnType::UID.to_string(),
                 ..ForeignKey::default()
             }),
-            // Prevent duplicate rows in the join table.
-            Constraint::Pk(PrimaryKey {
-                db_type: DbType::Postgres,
-                namespace: parsed.fully_qualified_namespace(),
-                table_name: item.table_name(),
-                column_names: vec![
-                    format!("{}_{}", item.parent_table_name(), item.parent_column_name()),
-                    format!("{}_{}", item.child_table_name(), item.child_column_name()),
-                ],
-            }),
             // Support quick lookups on either side of the join.
             Constraint::Index(SqlIndex {
                 db_type: DbType::Postgres,
@@ -1224,6 +1448,31 @@ impl Table {
             }),
         ];
 
+        // Prevent duplicate rows in the join table. The records of an immutable
+        // parent are inserted only once, so there are no conflicts to resolve.
+        if !parsed.is_immutable_typedef(&item.parent().typedef_name) {
+            constraints.insert(
+                2,
+                Constraint::Pk(PrimaryKey {
+                    db_type: DbType::Postgres,
+                    namespace: parsed.fully_qualified_namespace(),
+                    table_name: item.table_name(),
+                    column_names: vec![
+                        format!(
+                            "{}_{}",
+                            item.parent_table_name(),
+                            item.parent_column_name()
+                        ),
+                        format!(
+                            "{}_{}",
+                            item.child_table_name(),
+                            item.child_column_name()
+                        ),
+                    ],
+                }),
+            );
+        }
+
         Self {
             name: item.table_name(),
             namespace: parsed.namespace().to_string(),
@@ -1232,6 +1481,8 @@ impl Table {
             constraints,
             persistence: Persistence::Scalar,
             table_type: TableType::Join,
+            history: false,
+            retained: false,
         }
     }
 }
@@ -1403,6 +1654,49 @@ type Person @entity {
         assert!(!column.nullable);
     }
 
+    #[test]
+    fn test_can_create_numeric_column_for_256_bit_integers() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    balance: U256!
+    delta: I256
+}"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let field_def = FieldDefinition {
+            description: None,
+            name: Positioned {
+                pos: Pos::default(),
+                node: Name::new("balance"),
+            },
+            arguments: vec![],
+            ty: Positioned {
+                pos: Pos::default(),
+                node: Type {
+                    base: BaseType::Named(Name::new("U256")),
+                    nullable: false,
+                },
+            },
+            directives: vec![],
+        };
+
+        let type_id = type_id(&schema.fully_qualified_namespace(), "Account");
+        let column =
+            Column::from_field_def(&field_def, &schema, type_id, 0, Persistence::Scalar);
+        assert_eq!(column.coltype, ColumnType::U256);
+        assert_eq!(
+            column.create(),
+            "balance numeric(78, 0) not null".to_string()
+        );
+    }
+
     #[test]
     fn test_can_create_well_formed_join_table_from_m2m_relationship() {
         let schema = r#"
@@ -1467,4 +1761,176 @@ type Wallet @entity {
             })
         );
     }
+
+    #[test]
+    fn test_join_table_of_immutable_parent_has_no_primary_key() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    index: U64!
+}
+
+type Transfer @entity @immutable {
+    id: ID!
+    account: [Account!]!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let meta = schema.join_table_meta().get("Transfer").unwrap()[0].to_owned();
+        let table = Table::from_join_meta(meta, &schema);
+
+        assert_eq!(table.constraints().len(), 4);
+        assert!(!table
+            .constraints()
+            .iter()
+            .any(|c| matches!(c, Constraint::Pk(_))));
+    }
+
+    #[test]
+    fn test_can_create_well_formed_history_table_from_history_typedef() {
+        let schema = r#"
+type Balance @entity @history {
+    id: ID!
+    amount: U64!
+}
+
+type Account @entity {
+    id: ID!
+    index: U64!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let account = Table::from_typedef(&schema.type_defs()["Account"], &schema);
+        assert!(account.history_table().is_none());
+
+        let balance = Table::from_typedef(&schema.type_defs()["Balance"], &schema);
+        let history = balance.history_table().unwrap();
+
+        assert_eq!(
+            history.create(),
+            "CREATE TABLE test_test.balance_history (
+id varchar(64) not null,
+amount numeric(20, 0) not null,
+object bytea not null,
+block_height_start bigint not null,
+block_height_end bigint
+);"
+        );
+
+        assert_eq!(
+            history.constraints()[0],
+            Constraint::Pk(PrimaryKey {
+                db_type: DbType::Postgres,
+                namespace: schema.fully_qualified_namespace(),
+                table_name: "balance_history".to_string(),
+                column_names: vec!["id".to_string(), "block_height_start".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn test_can_create_retention_column_for_retained_typedef() {
+        let schema = r#"
+type Snapshot @entity @retain(blocks: 1000) {
+    id: ID!
+    amount: U64!
+}
+
+type Account @entity {
+    id: ID!
+    index: U64!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let account = Table::from_typedef(&schema.type_defs()["Account"], &schema);
+        assert!(account.retention_stmnts().is_empty());
+
+        let snapshot = Table::from_typedef(&schema.type_defs()["Snapshot"], &schema);
+        assert_eq!(
+            snapshot.retention_stmnts(),
+            vec![
+                "ALTER TABLE test_test.snapshot ADD COLUMN block_height bigint;"
+                    .to_string(),
+                "CREATE INDEX snapshot_block_height_idx ON test_test.snapshot USING btree (block_height);"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_derived_fields_do_not_create_columns_or_constraints() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    index: U64!
+    transfers: [Transfer!]! @derivedFrom(field: "owner")
+}
+
+type Transfer @entity {
+    id: ID!
+    owner: Account!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let account = Table::from_typedef(&schema.type_defs()["Account"], &schema);
+        assert_eq!(
+            account
+                .columns()
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["id", "index", "object"]
+        );
+        assert!(account.constraints().is_empty());
+    }
+
+    #[test]
+    fn test_u32_columns_can_hold_every_u32() {
+        let column = Column {
+            name: "block_height".to_string(),
+            coltype: ColumnType::U32,
+            ..Column::default()
+        };
+        assert_eq!(column.create(), "block_height bigint not null");
+
+        let column = Column {
+            name: "heights".to_string(),
+            coltype: ColumnType::Array,
+            array_coltype: Some(ColumnType::U32),
+            nullable: true,
+            ..Column::default()
+        };
+        assert_eq!(
+            column.create(),
+            format!("heights bigint [{MAX_ARRAY_LENGTH}]")
+        );
+    }
 }
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS index_watched_contracts CASCADE;
\ No newline at end of file
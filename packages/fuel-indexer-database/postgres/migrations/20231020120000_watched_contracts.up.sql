@@ -0,0 +1,6 @@
+CREATE TABLE IF NOT EXISTS index_watched_contracts (
+   namespace TEXT NOT NULL,
+   identifier TEXT NOT NULL,
+   contract_id TEXT NOT NULL,
+   UNIQUE (namespace, identifier, contract_id)
+);
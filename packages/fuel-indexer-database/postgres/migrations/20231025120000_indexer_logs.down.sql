@@ -0,0 +1 @@
+DROP TABLE IF EXISTS index_logs CASCADE;
\ No newline at end of file
@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS index_logs (
+   id BIGSERIAL PRIMARY KEY,
+   namespace TEXT NOT NULL,
+   identifier TEXT NOT NULL,
+   block_height INTEGER NOT NULL,
+   level TEXT NOT NULL,
+   target TEXT NOT NULL,
+   message TEXT NOT NULL,
+   fields JSONB NOT NULL,
+   created_at TIMESTAMP NOT NULL DEFAULT (now() AT TIME ZONE 'utc')
+);
+
+CREATE INDEX IF NOT EXISTS index_logs_indexer_idx ON index_logs (namespace, identifier, id);
@@ -0,0 +1,2 @@
+ALTER TABLE index_status DROP COLUMN IF EXISTS block_height;
+ALTER TABLE index_status DROP COLUMN IF EXISTS handler;
@@ -0,0 +1,2 @@
+ALTER TABLE index_status ADD COLUMN IF NOT EXISTS block_height INTEGER;
+ALTER TABLE index_status ADD COLUMN IF NOT EXISTS handler TEXT;
@@ -0,0 +1,2 @@
+ALTER TABLE index_status ADD COLUMN IF NOT EXISTS pruned_block_height INTEGER;
+ALTER TABLE index_status ADD COLUMN IF NOT EXISTS pruned_rows BIGINT;
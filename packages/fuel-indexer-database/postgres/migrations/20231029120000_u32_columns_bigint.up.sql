@@ -0,0 +1,28 @@
+-- `U32` columns used to be created as `integer`, which cannot hold values above 2^31 - 1.
+DO $$
+DECLARE
+    col record;
+BEGIN
+    FOR col IN
+        SELECT DISTINCT lower(t.schema_name || '_' || t.schema_identifier) AS table_schema,
+            lower(t.table_name) AS table_name,
+            lower(c.column_name) AS column_name
+        FROM graph_registry_columns AS c
+        INNER JOIN graph_registry_type_ids AS t ON t.id = c.type_id
+        WHERE c.column_type = 'U32'
+    LOOP
+        IF EXISTS (
+            SELECT 1 FROM information_schema.columns
+            WHERE table_schema = col.table_schema
+            AND table_name = col.table_name
+            AND column_name = col.column_name
+            AND data_type = 'integer'
+        ) THEN
+            EXECUTE format(
+                'ALTER TABLE %I.%I ALTER COLUMN %I TYPE bigint',
+                col.table_schema, col.table_name, col.column_name
+            );
+        END IF;
+    END LOOP;
+END;
+$$;
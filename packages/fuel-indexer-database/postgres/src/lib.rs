@@ -836,6 +836,18 @@ pub async fn remove_indexer(
             format!("DROP SCHEMA IF EXISTS {namespace}_{identifier} CASCADE"),
         )
         .await?;
+
+        sqlx::query("DELETE FROM index_watched_contracts WHERE namespace = $1 AND identifier = $2;")
+            .bind(namespace)
+            .bind(identifier)
+            .execute(&mut *conn)
+            .await?;
+
+        sqlx::query("DELETE FROM index_logs WHERE namespace = $1 AND identifier = $2;")
+            .bind(namespace)
+            .bind(identifier)
+            .execute(conn)
+            .await?;
     }
 
     Ok(())
@@ -927,6 +939,10 @@ pub async fn put_many_to_many_record(
     Ok(())
 }
 
+/// Setting that lifts the check of the `ensure_block_height_consecutive` trigger
+/// when it is `on`.
+const SKIP_BLOCK_HEIGHT_CHECK_SETTING: &str = "fuel_indexer.skip_block_height_check";
+
 /// Create a database trigger on the indexer's indexmetadataentity table that
 /// ensures no blocks can be missing.
 pub async fn create_ensure_block_height_consecutive_trigger(
@@ -934,11 +950,15 @@ pub async fn create_ensure_block_height_consecutive_trigger(
     namespace: &str,
     identifier: &str,
 ) -> sqlx::Result<()> {
-    let trigger_function = "CREATE OR REPLACE FUNCTION ensure_block_height_consecutive()
+    let trigger_function = format!("CREATE OR REPLACE FUNCTION ensure_block_height_consecutive()
     RETURNS TRIGGER AS $$
     DECLARE
       block_height integer;
     BEGIN
+      IF current_setting('{SKIP_BLOCK_HEIGHT_CHECK_SETTING}', true) = 'on' THEN
+        RETURN NEW;
+      END IF;
+
       EXECUTE format('SELECT MAX(block_height) FROM %I.%I', TG_TABLE_SCHEMA, TG_TABLE_NAME) INTO block_height;
 
       IF NEW.block_height IS NOT NULL AND block_height IS NOT NULL AND NEW.block_height != block_height + 1 THEN
@@ -947,7 +967,7 @@ pub async fn create_ensure_block_height_consecutive_trigger(
 
       RETURN NEW;
     END;
-    $$ LANGUAGE plpgsql;".to_string();
+    $$ LANGUAGE plpgsql;");
 
     execute_query(conn, trigger_function).await.unwrap();
 
@@ -975,6 +995,21 @@ pub async fn create_ensure_block_height_consecutive_trigger(
     Ok(())
 }
 
+/// Lift the check of the `ensure_block_height_consecutive` trigger until the end
+/// of the current transaction.
+///
+/// Unlike removing the trigger, this doesn't lock the indexmetadataentity table.
+pub async fn skip_ensure_block_height_consecutive_check(
+    conn: &mut PoolConnection<Postgres>,
+) -> sqlx::Result<()> {
+    sqlx::query("SELECT set_config($1, 'on', true);")
+        .bind(SKIP_BLOCK_HEIGHT_CHECK_SETTING)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
 /// When -allow-non-sequential-blocks is set, we need to remove the trigger from
 /// indexer's indexmetadataentity table.
 pub async fn remove_ensure_block_height_consecutive_trigger(
@@ -1000,14 +1035,40 @@ pub async fn set_indexer_status(
 ) -> sqlx::Result<()> {
     let indexer_id = get_indexer_id(conn, namespace, identifier).await?;
     sqlx::query(
-        "INSERT INTO index_status (indexer_id, status, status_message)
-        VALUES ($1, $2, $3)
+        "INSERT INTO index_status (indexer_id, status, status_message, block_height, handler)
+        VALUES ($1, $2, $3, $4, $5)
         ON CONFLICT (indexer_id) DO UPDATE
-        SET status = EXCLUDED.status, status_message = EXCLUDED.status_message;",
+        SET status = EXCLUDED.status, status_message = EXCLUDED.status_message,
+        block_height = EXCLUDED.block_height, handler = EXCLUDED.handler;",
     )
     .bind(indexer_id)
     .bind(status.status_kind.to_string())
     .bind(status.status_message)
+    .bind(status.block_height.map(|h| h as i32))
+    .bind(status.handler)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Record that the tables of an indexer with a retention policy were pruned at the
+/// given block height. The rest of the indexer's status is left as is.
+pub async fn set_indexer_pruned(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    block_height: u32,
+    rows: u64,
+) -> sqlx::Result<()> {
+    let indexer_id = get_indexer_id(conn, namespace, identifier).await?;
+    sqlx::query(
+        "UPDATE index_status SET pruned_block_height = $2, pruned_rows = $3
+        WHERE indexer_id = $1;",
+    )
+    .bind(indexer_id)
+    .bind(block_height as i32)
+    .bind(rows as i64)
     .execute(conn)
     .await?;
 
@@ -1019,7 +1080,7 @@ pub async fn all_registered_indexer_statuses(
     conn: &mut PoolConnection<Postgres>,
 ) -> sqlx::Result<HashMap<(String, String), IndexerStatus>> {
     let rows = sqlx::query(
-        "SELECT index_registry.namespace, index_registry.identifier, status, status_message
+        "SELECT index_registry.namespace, index_registry.identifier, status, status_message, block_height, handler, pruned_block_height, pruned_rows
         FROM index_status
         INNER JOIN index_registry
         ON index_status.indexer_id = index_registry.id;"
@@ -1034,12 +1095,181 @@ pub async fn all_registered_indexer_statuses(
         let status_kind =
             IndexerStatusKind::from_str(row.get(2)).unwrap_or(IndexerStatusKind::Unknown);
         let status_message: String = row.get(3);
+        let block_height: Option<i32> = row.get(4);
+        let handler: Option<String> = row.get(5);
+        let pruned_block_height: Option<i32> = row.get(6);
+        let pruned_rows: Option<i64> = row.get(7);
         let status = IndexerStatus {
             status_kind,
             status_message,
+            block_height: block_height.map(|h| h as u32),
+            handler,
+            pruned_block_height: pruned_block_height.map(|h| h as u32),
+            pruned_rows: pruned_rows.map(|r| r as u64),
         };
         result.insert((namespace, identifier), status);
     }
 
     Ok(result)
 }
+
+/// Persist a contract ID that the indexer has started watching at runtime.
+pub async fn put_watched_contract(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    contract_id: &str,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "INSERT INTO index_watched_contracts (namespace, identifier, contract_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (namespace, identifier, contract_id) DO NOTHING;",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(contract_id)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Fetch the contract IDs that the indexer has started watching at runtime.
+pub async fn watched_contracts(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    let rows = sqlx::query(
+        "SELECT contract_id FROM index_watched_contracts
+        WHERE namespace = $1 AND identifier = $2;",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
+/// Persist lines logged by the handlers of an indexer.
+pub async fn put_indexer_logs(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    logs: &[IndexerLog],
+) -> sqlx::Result<()> {
+    if logs.is_empty() {
+        return Ok(());
+    }
+
+    let mut builder = sqlx::QueryBuilder::new(
+        "INSERT INTO index_logs
+        (namespace, identifier, block_height, level, target, message, fields, created_at) ",
+    );
+    builder.push_values(logs, |mut row, log| {
+        row.push_bind(namespace)
+            .push_bind(identifier)
+            .push_bind(log.block_height as i32)
+            .push_bind(&log.level)
+            .push_bind(&log.target)
+            .push_bind(&log.message)
+            .push_bind(sqlx::types::Json(&log.fields))
+            .push_bind(log.created_at.naive_utc());
+    });
+    builder.build().execute(conn).await?;
+
+    Ok(())
+}
+
+/// Delete the lines logged by the handlers of an indexer, except for the last
+/// `max_lines` lines.
+pub async fn prune_indexer_logs(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    max_lines: usize,
+) -> sqlx::Result<()> {
+    sqlx::query(
+        "DELETE FROM index_logs
+        WHERE namespace = $1 AND identifier = $2 AND id <= (
+            SELECT id FROM index_logs
+            WHERE namespace = $1 AND identifier = $2
+            ORDER BY id DESC
+            OFFSET $3 LIMIT 1
+        );",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(max_lines as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Fetch the lines logged by the handlers of an indexer, oldest first.
+///
+/// Only lines with an ID greater than `since` are returned, if given. Otherwise,
+/// the last `limit` lines are returned.
+pub async fn indexer_logs(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    since: Option<i64>,
+    limit: i64,
+) -> sqlx::Result<Vec<IndexerLog>> {
+    let rows = match since {
+        Some(since) => {
+            sqlx::query(
+                "SELECT id, block_height, level, target, message, fields, created_at
+                FROM index_logs
+                WHERE namespace = $1 AND identifier = $2 AND id > $3
+                ORDER BY id ASC
+                LIMIT $4;",
+            )
+            .bind(namespace)
+            .bind(identifier)
+            .bind(since)
+            .bind(limit)
+            .fetch_all(conn)
+            .await?
+        }
+        None => {
+            sqlx::query(
+                "SELECT * FROM (
+                    SELECT id, block_height, level, target, message, fields, created_at
+                    FROM index_logs
+                    WHERE namespace = $1 AND identifier = $2
+                    ORDER BY id DESC
+                    LIMIT $3
+                ) AS logs ORDER BY id ASC;",
+            )
+            .bind(namespace)
+            .bind(identifier)
+            .bind(limit)
+            .fetch_all(conn)
+            .await?
+        }
+    };
+
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let block_height: i32 = row.get(1);
+            let sqlx::types::Json(fields): sqlx::types::Json<Vec<(String, String)>> =
+                row.get(5);
+            let created_at: NaiveDateTime = row.get(6);
+
+            IndexerLog {
+                id: row.get(0),
+                block_height: block_height as u32,
+                level: row.get(2),
+                target: row.get(3),
+                message: row.get(4),
+                fields,
+                created_at: DateTime::<Utc>::from_naive_utc_and_offset(created_at, Utc),
+            }
+        })
+        .collect())
+}
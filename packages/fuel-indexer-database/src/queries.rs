@@ -439,6 +439,18 @@ pub async fn create_ensure_block_height_consecutive_trigger(
     }
 }
 
+/// Lift the check of the `ensure_block_height_consecutive` trigger until the end
+/// of the current transaction.
+pub async fn skip_ensure_block_height_consecutive_check(
+    conn: &mut IndexerConnection,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::skip_ensure_block_height_consecutive_check(c).await
+        }
+    }
+}
+
 /// When -allow-non-sequential-blocks is set, we need to remove the trigger from
 /// indexer's indexmetadataentity table.
 pub async fn remove_ensure_block_height_consecutive_trigger(
@@ -470,6 +482,23 @@ pub async fn set_indexer_status(
     }
 }
 
+/// Record that the tables of an indexer with a retention policy were pruned at the
+/// given block height.
+pub async fn set_indexer_pruned(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    block_height: u32,
+    rows: u64,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::set_indexer_pruned(c, namespace, identifier, block_height, rows)
+                .await
+        }
+    }
+}
+
 /// Return the statuses of all indexers registered with the service.
 pub async fn all_registered_indexer_statuses(
     conn: &mut IndexerConnection,
@@ -480,3 +509,74 @@ pub async fn all_registered_indexer_statuses(
         }
     }
 }
+
+/// Persist a contract ID that the indexer has started watching at runtime.
+pub async fn put_watched_contract(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    contract_id: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_watched_contract(c, namespace, identifier, contract_id).await
+        }
+    }
+}
+
+/// Return the contract IDs that the indexer has started watching at runtime.
+pub async fn watched_contracts(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Vec<String>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::watched_contracts(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Persist lines logged by the handlers of an indexer.
+pub async fn put_indexer_logs(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    logs: &[IndexerLog],
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_indexer_logs(c, namespace, identifier, logs).await
+        }
+    }
+}
+
+/// Delete the lines logged by the handlers of an indexer, except for the last
+/// `max_lines` lines.
+pub async fn prune_indexer_logs(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    max_lines: usize,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::prune_indexer_logs(c, namespace, identifier, max_lines).await
+        }
+    }
+}
+
+/// Return the lines logged by the handlers of an indexer, oldest first.
+pub async fn indexer_logs(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    since: Option<i64>,
+    limit: i64,
+) -> sqlx::Result<Vec<IndexerLog>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::indexer_logs(c, namespace, identifier, since, limit).await
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use super::graphql::GraphqlError;
 use fuel_indexer_database::DbType;
+use fuel_indexer_lib::graphql::constants::{
+    LARGE_INTEGER_TYPES, STRING_ENCODED_NUMERIC_TYPES,
+};
 use fuel_indexer_schema::db::tables::IndexerSchema;
+use fuel_indexer_types::scalar::Timestamp;
 
 use async_graphql_value::{indexmap::IndexMap, Name, Value};
 use std::fmt;
@@ -12,6 +16,8 @@ pub struct QueryParams {
     pub sorts: Vec<Sort>,
     pub offset: Option<u64>,
     pub limit: Option<u64>,
+    /// Height of the block at which entities are queried, if any.
+    pub block_height: Option<u64>,
 }
 
 impl QueryParams {
@@ -36,6 +42,7 @@ impl QueryParams {
                 }),
                 ParamType::Offset(n) => self.offset = Some(n),
                 ParamType::Limit(n) => self.limit = Some(n),
+                ParamType::Block(n) => self.block_height = Some(n),
             }
         }
     }
@@ -102,6 +109,7 @@ pub enum ParamType {
     Sort(String, SortOrder),
     Offset(u64),
     Limit(u64),
+    Block(u64),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -129,6 +137,8 @@ impl fmt::Display for SortOrder {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParsedValue {
     BigNumber(u128),
+    /// Integer of arbitrary precision, given as a decimal string.
+    Numeric(String),
     Number(u64),
     String(String),
     Boolean(bool),
@@ -145,6 +155,9 @@ impl fmt::Display for ParsedValue {
             Self::BigNumber(bn) => {
                 write!(f, "{bn}")
             }
+            Self::Numeric(n) => {
+                write!(f, "{n}")
+            }
             Self::Boolean(b) => {
                 write!(f, "{b}")
             }
@@ -458,6 +471,24 @@ pub fn parse_argument_into_param(
                 Err(GraphqlError::UnsupportedValueType(value.to_string()))
             }
         }
+        "block" => {
+            if let Value::Object(obj) = value {
+                match obj.get("height") {
+                    Some(Value::Number(number)) => number
+                        .as_u64()
+                        .map(ParamType::Block)
+                        .ok_or(GraphqlError::UnsupportedValueType(number.to_string())),
+                    Some(other) => {
+                        Err(GraphqlError::UnsupportedValueType(other.to_string()))
+                    }
+                    None => Err(GraphqlError::UnsupportedValueType(
+                        Value::Object(obj).to_string(),
+                    )),
+                }
+            } else {
+                Err(GraphqlError::UnsupportedValueType(value.to_string()))
+            }
+        }
         _ => {
             if let Some(entity) = entity_type {
                 Err(GraphqlError::UnrecognizedArgument(
@@ -570,7 +601,8 @@ fn parse_arg_pred_pair(
             }
         }
         other => {
-            if schema.parsed().graphql_type(entity_type, other).is_some() {
+            if let Some(field_type) = schema.parsed().graphql_type(entity_type, other) {
+                let parse_field = |value: &Value| parse_field_value(value, field_type);
                 if let Value::Object(inner_obj) = predicate {
                     for (key, predicate) in inner_obj.iter() {
                         match key.as_str() {
@@ -581,7 +613,7 @@ fn parse_arg_pred_pair(
                                         complex_comparison_obj.get("max"),
                                     ) {
                                         let (min, max) =
-                                            (parse_value(min)?, parse_value(max)?);
+                                            (parse_field(min)?, parse_field(max)?);
                                         return Ok(FilterType::Comparison(
                                             Comparison::Between(
                                                 other.to_string(),
@@ -595,40 +627,40 @@ fn parse_arg_pred_pair(
                             "equals" => {
                                 return Ok(FilterType::Comparison(Comparison::Equals(
                                     other.to_string(),
-                                    parse_value(predicate)?,
+                                    parse_field(predicate)?,
                                 )))
                             }
                             "gt" => {
                                 return Ok(FilterType::Comparison(Comparison::Greater(
                                     other.to_string(),
-                                    parse_value(predicate)?,
+                                    parse_field(predicate)?,
                                 )))
                             }
                             "gte" => {
                                 return Ok(FilterType::Comparison(
                                     Comparison::GreaterEqual(
                                         other.to_string(),
-                                        parse_value(predicate)?,
+                                        parse_field(predicate)?,
                                     ),
                                 ));
                             }
                             "lt" => {
                                 return Ok(FilterType::Comparison(Comparison::Less(
                                     other.to_string(),
-                                    parse_value(predicate)?,
+                                    parse_field(predicate)?,
                                 )))
                             }
                             "lte" => {
                                 return Ok(FilterType::Comparison(Comparison::LessEqual(
                                     other.to_string(),
-                                    parse_value(predicate)?,
+                                    parse_field(predicate)?,
                                 )))
                             }
                             "in" => {
                                 if let Value::List(elements) = predicate {
                                     let parsed_elements = elements
                                             .iter()
-                                            .map(parse_value)
+                                            .map(&parse_field)
                                             .collect::<Result<Vec<ParsedValue>,GraphqlError>>();
                                     if let Ok(elements) = parsed_elements {
                                         return Ok(FilterType::Membership(
@@ -739,6 +771,56 @@ fn parse_binary_logical_operator(
     }
 }
 
+/// Parse a value that is compared against a field of the given type.
+///
+/// Integers that do not fit into JSON numbers are given as decimal strings, and
+/// are compared as numbers rather than as strings. 64-bit and 128-bit integers
+/// may be given either as numbers or as decimal strings, and are checked against
+/// the range of the field type. Timestamps are given as RFC 3339
+/// date-times, and are compared as points in time.
+fn parse_field_value(
+    value: &Value,
+    field_type: &str,
+) -> Result<ParsedValue, GraphqlError> {
+    if field_type == "Timestamp" {
+        return match value {
+            Value::String(s) => s
+                .parse::<Timestamp>()
+                .map(|t| ParsedValue::String(t.to_string()))
+                .map_err(GraphqlError::UnableToParseValue),
+            _ => Err(GraphqlError::UnsupportedValueType(value.to_string())),
+        };
+    }
+
+    if !STRING_ENCODED_NUMERIC_TYPES.contains(field_type)
+        && !LARGE_INTEGER_TYPES.contains(field_type)
+    {
+        return parse_value(value);
+    }
+
+    let number = match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => return Err(GraphqlError::UnsupportedValueType(value.to_string())),
+    };
+
+    let digits = number.strip_prefix('-').unwrap_or(&number);
+    let in_range = match field_type {
+        "I64" => number.parse::<i64>().is_ok(),
+        "U64" => number.parse::<u64>().is_ok(),
+        "I128" => number.parse::<i128>().is_ok(),
+        "U128" => number.parse::<u128>().is_ok(),
+        _ => true,
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) || !in_range {
+        return Err(GraphqlError::UnableToParseValue(format!(
+            "Could not parse {number:?} into {field_type}"
+        )));
+    }
+
+    Ok(ParsedValue::Numeric(number))
+}
+
 /// Parse a value from the parsed GraphQL document into a `ParsedValue` for use in the indexer.
 ///
 /// Value types from the parsed GraphQL query should be turned into `ParsedValue`
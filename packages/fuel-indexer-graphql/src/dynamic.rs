@@ -15,7 +15,9 @@ use fuel_indexer_schema::db::tables::IndexerSchema;
 use lazy_static::lazy_static;
 use serde_json::Value;
 
-use crate::graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult};
+use crate::graphql::{
+    GraphqlError, GraphqlQueryBuilder, GraphqlResult, META_FIELD, META_TYPES,
+};
 
 lazy_static! {
     /// Scalar types supported by the Fuel indexer. These should always stay up-to-date
@@ -32,14 +34,17 @@ lazy_static! {
         "ContractId",
         "I128",
         "I16",
+        "I256",
         "I32",
         "I64",
         "I8",
         "ID",
         "Identity",
         "Json",
+        "Timestamp",
         "U128",
         "U16",
+        "U256",
         "U32",
         "U64",
         "U8",
@@ -61,6 +66,9 @@ lazy_static! {
 
     /// Scalar types that are represented by a string type. This ensures that the
     /// value type provided for a field filter matches the type of the scalar itself.
+    ///
+    /// 256-bit integers are passed as decimal strings, as they do not fit into the
+    /// numbers of most JSON clients, and timestamps as RFC 3339 date-times.
     static ref STRING_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
         "Address",
         "AssetId",
@@ -71,9 +79,12 @@ lazy_static! {
         "Bytes64",
         "Bytes8",
         "ContractId",
+        "I256",
         "ID",
         "Identity",
         "Json",
+        "Timestamp",
+        "U256",
         "UID",
     ]);
 
@@ -84,12 +95,15 @@ lazy_static! {
         "ContractId",
         "I128",
         "I16",
+        "I256",
         "I32",
         "I64",
         "ID",
         "Identity",
+        "Timestamp",
         "U128",
         "U16",
+        "U256",
         "U32",
         "U64",
         "UID",
@@ -115,6 +129,7 @@ pub async fn execute_query(
     user_query: String,
     pool: IndexerConnectionPool,
     schema: IndexerSchema,
+    stringify_large_integers: bool,
 ) -> GraphqlResult<Value> {
     // Because the schema types from async-graphql expect each field to be resolved
     // separately, it became untenable to use the .execute() method of the dynamic
@@ -129,8 +144,11 @@ pub async fn execute_query(
             Ok(data)
         }
         Some(_) | None => {
-            let query =
-                GraphqlQueryBuilder::new(&schema, user_query.as_str())?.build()?;
+            let query = GraphqlQueryBuilder::new(&schema, user_query.as_str())?
+                .operation_name(dynamic_request.operation_name.clone())
+                .variables(dynamic_request.variables)
+                .stringify_large_integers(stringify_large_integers)
+                .build()?;
 
             let queries = query.as_sql(&schema, pool.database_type())?.join(";\n");
 
@@ -180,6 +198,10 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
 
     let sort_enum = Enum::new("SortOrder").item("asc").item("desc");
 
+    // Entities that keep history can be queried at a given block height.
+    let block_height_object = InputObject::new("BlockHeight")
+        .field(InputValue::new("height", TypeRef::named_nn(TypeRef::INT)));
+
     for (entity_type, field_map) in schema.parsed().object_field_mappings() {
         if IGNORED_ENTITY_TYPES.contains(&entity_type.as_str()) {
             continue;
@@ -199,6 +221,15 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
                 continue;
             }
 
+            // Derived fields have no column by which entities could be filtered or sorted.
+            if schema
+                .parsed()
+                .derived_from(entity_type, &field_name)
+                .is_some()
+            {
+                continue;
+            }
+
             let (field_filter_input_val, mut field_input_objects, sort_input_val) =
                 create_input_values_and_objects_for_field(
                     field_name.clone(),
@@ -317,7 +348,7 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             .fold(Object::new(entity_type.clone()), |obj, f| obj.field(f));
 
         // Create field for entity object and add it to root level query object.
-        let field = create_field_with_assoc_args(
+        let mut field = create_field_with_assoc_args(
             entity_type.to_string().to_lowercase(),
             TypeRef::named(obj.type_name()),
             &BaseType::Named(Name::new(obj.type_name())),
@@ -326,6 +357,12 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             &sorter_tracker,
             &sort_object_list,
         );
+        if schema.parsed().is_history_typedef(entity_type) {
+            field = field.argument(InputValue::new(
+                "block",
+                TypeRef::named(block_height_object.type_name()),
+            ));
+        }
         if !SCALAR_TYPES.contains(&obj.type_name()) {
             query_root = query_root.field(field);
         }
@@ -333,6 +370,32 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
         schema_builder = schema_builder.register(obj).register(object_field_enum);
     }
 
+    // The last indexed block and the status of the indexer can be queried on the
+    // `_meta` root field, so that clients can tell how fresh the results are.
+    for (type_name, fields) in META_TYPES {
+        let obj = fields.iter().fold(
+            Object::new(*type_name),
+            |obj, (field_name, field_type)| {
+                obj.field(Field::new(
+                    *field_name,
+                    TypeRef::named(*field_type),
+                    move |_ctx: ResolverContext| {
+                        FieldFuture::new(async move { Ok(Some(FieldValue::value(1))) })
+                    },
+                ))
+            },
+        );
+        schema_builder = schema_builder.register(obj);
+    }
+
+    query_root = query_root.field(Field::new(
+        META_FIELD,
+        TypeRef::named_nn(META_TYPES[0].0),
+        move |_ctx: ResolverContext| {
+            FieldFuture::new(async move { Ok(Some(FieldValue::value(1))) })
+        },
+    ));
+
     // In order for the schema to successfully use the input objects
     // that make up the filter and sort arguments, the objects have to
     // be registered with the schema.
@@ -349,6 +412,7 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
     }
 
     schema_builder = schema_builder.register(sort_enum);
+    schema_builder = schema_builder.register(block_height_object);
     schema_builder = schema_builder.register(query_root);
 
     Ok(schema_builder.finish()?)
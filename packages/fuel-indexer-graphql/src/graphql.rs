@@ -1,21 +1,48 @@
 use super::{
     arguments::{parse_argument_into_param, ParamType, QueryParams},
-    queries::{JoinCondition, QueryElement, QueryJoinNode, UserQuery},
+    queries::{JoinCondition, NestedQuery, QueryElement, QueryJoinNode, UserQuery},
 };
+use async_graphql::Variables;
 use async_graphql_parser::{
     parse_query,
     types::{
-        DocumentOperations, ExecutableDocument, Field, FragmentDefinition,
-        FragmentSpread, OperationDefinition, OperationType, SelectionSet, TypeCondition,
+        BaseType, Directive, DocumentOperations, ExecutableDocument, Field,
+        FragmentDefinition, FragmentSpread, InlineFragment, OperationDefinition,
+        OperationType, SelectionSet, Type, TypeCondition, VariableDefinition,
     },
+    Positioned,
 };
+use async_graphql_value::{ConstValue, Value};
 use fuel_indexer_database_types::DbType;
+use fuel_indexer_lib::graphql::constants::{
+    LARGE_INTEGER_TYPES, STRING_ENCODED_NUMERIC_TYPES,
+};
 use fuel_indexer_schema::db::tables::IndexerSchema;
 use std::collections::HashMap;
 use thiserror::Error;
 
 pub type GraphqlResult<T> = Result<T, GraphqlError>;
 
+/// Values of the variables of an operation, indexed by variable name.
+pub type VariableValues = HashMap<String, ConstValue>;
+
+/// Name of the root field on which the metadata of an indexer can be queried.
+pub const META_FIELD: &str = "_meta";
+
+/// Types of the metadata that can be selected on the `_meta` root field, along
+/// with the names and types of their fields.
+pub const META_TYPES: &[(&str, &[(&str, &str)])] = &[
+    ("_Meta", &[("block", "_Block"), ("indexer", "_Indexer")]),
+    (
+        "_Block",
+        &[("height", "U32"), ("id", "Bytes32"), ("time", "U64")],
+    ),
+    (
+        "_Indexer",
+        &[("status", "String"), ("deployedAt", "Timestamp")],
+    ),
+];
+
 #[derive(Debug, Error)]
 pub enum GraphqlError {
     #[error("GraphQl Parser error: {0:?}")]
@@ -54,6 +81,22 @@ pub enum GraphqlError {
     UnorderedPaginatedQuery,
     #[error("Query error: {0:?}")]
     QueryError(String),
+    #[error("Entity {0:?} does not keep history.")]
+    NoEntityHistory(String),
+    #[error("Union and interface entities can not be paginated: {0:?}")]
+    PaginatedAbstractTypeQuery(String),
+    #[error("Variable {0:?} is not defined by the operation.")]
+    UndefinedVariable(String),
+    #[error("Variable {0:?} is required, but no value was provided.")]
+    MissingVariable(String),
+    #[error("Value of variable {0:?} is not of type {1:?}.")]
+    InvalidVariable(String, String),
+    #[error("Directive {0:?} requires an \"if\" argument.")]
+    MissingDirectiveCondition(String),
+    #[error("Unknown operation named {0:?}.")]
+    UnknownOperation(String),
+    #[error("Operation name required in request.")]
+    MissingOperationName,
 }
 
 #[derive(Clone, Debug)]
@@ -65,9 +108,10 @@ pub enum Selection {
         alias: Option<String>,
     },
     Fragment(String),
+    InlineFragment(Fragment),
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Selections {
     has_fragments: bool,
     selections: Vec<Selection>,
@@ -78,14 +122,18 @@ impl Selections {
         schema: &IndexerSchema,
         field_type: Option<&String>,
         set: &SelectionSet,
+        variables: &VariableValues,
     ) -> GraphqlResult<Selections> {
         let mut selections = Vec::with_capacity(set.items.len());
         let mut has_fragments = false;
 
         for item in &set.items {
+            if !is_included(item.node.directives(), variables)? {
+                continue;
+            }
+
             match &item.node {
                 async_graphql_parser::types::Selection::Field(field) => {
-                    // TODO: directives and sub-selections for nested types...
                     let Field {
                         name,
                         selection_set,
@@ -93,6 +141,40 @@ impl Selections {
                         alias,
                         ..
                     } = &field.node;
+
+                    // The name of the type of an entity can be selected on any entity.
+                    if name.node == "__typename" && field_type.is_some() {
+                        selections.push(Selection::Field {
+                            name: name.to_string(),
+                            params: Vec::new(),
+                            sub_selections: Selections::default(),
+                            alias: alias.as_ref().map(|a| a.to_string()),
+                        });
+                        continue;
+                    }
+
+                    // The metadata of the indexer can be selected alongside the entities.
+                    if name.node == META_FIELD && field_type.is_none() {
+                        if let Some((arg, _)) = arguments.first() {
+                            return Err(GraphqlError::UnrecognizedArgument(
+                                name.to_string(),
+                                arg.to_string(),
+                            ));
+                        }
+
+                        selections.push(Selection::Field {
+                            name: name.to_string(),
+                            params: Vec::new(),
+                            sub_selections: Selections::new_meta(
+                                META_TYPES[0].0,
+                                &selection_set.node,
+                                variables,
+                            )?,
+                            alias: alias.as_ref().map(|a| a.to_string()),
+                        });
+                        continue;
+                    }
+
                     let subfield_type =
                         match schema.parsed().graphql_type(field_type, &name.to_string())
                         {
@@ -111,13 +193,26 @@ impl Selections {
                             }
                         };
 
+                    // Only top-level entities can be queried at a block height.
+                    if let Some(field_type) = field_type {
+                        if arguments
+                            .iter()
+                            .any(|(arg, _)| arg.node.as_str() == "block")
+                        {
+                            return Err(GraphqlError::UnrecognizedArgument(
+                                field_type.to_string(),
+                                "block".to_string(),
+                            ));
+                        }
+                    }
+
                     let params = arguments
                         .iter()
                         .map(|(arg, value)| {
                             parse_argument_into_param(
                                 Some(subfield_type),
                                 &arg.to_string(),
-                                value.node.clone(),
+                                resolve_variables(value.node.clone(), variables)?,
                                 schema,
                             )
                         })
@@ -127,6 +222,7 @@ impl Selections {
                         schema,
                         Some(subfield_type),
                         &selection_set.node,
+                        variables,
                     )?;
                     selections.push(Selection::Field {
                         name: name.to_string(),
@@ -144,8 +240,38 @@ impl Selections {
                     has_fragments = true;
                     selections.push(Selection::Fragment(fragment_name.to_string()));
                 }
-                // TODO: Support inline fragments
-                _ => return Err(GraphqlError::SelectionNotSupported),
+                async_graphql_parser::types::Selection::InlineFragment(frag) => {
+                    let InlineFragment {
+                        type_condition,
+                        selection_set,
+                        ..
+                    } = &frag.node;
+
+                    // Inline fragments can only be used within the selections on an entity.
+                    let field_type =
+                        field_type.ok_or(GraphqlError::SelectionNotSupported)?;
+
+                    let cond = match type_condition {
+                        Some(c) => c.node.on.to_string(),
+                        None => field_type.to_string(),
+                    };
+
+                    if !schema.parsed().has_type(&cond) {
+                        return Err(GraphqlError::UnrecognizedType(cond));
+                    }
+
+                    let fragment =
+                        Fragment::new(schema, cond, &selection_set.node, variables)?;
+                    if !fragment.applies_to(schema, field_type) {
+                        return Err(GraphqlError::InvalidFragmentSelection(
+                            fragment,
+                            field_type.to_string(),
+                        ));
+                    }
+
+                    has_fragments |= fragment.has_fragments();
+                    selections.push(Selection::InlineFragment(fragment));
+                }
             }
         }
 
@@ -155,6 +281,60 @@ impl Selections {
         })
     }
 
+    /// Create the selections on a type of the metadata of an indexer.
+    fn new_meta(
+        typ: &str,
+        set: &SelectionSet,
+        variables: &VariableValues,
+    ) -> GraphqlResult<Selections> {
+        let mut selections = Vec::with_capacity(set.items.len());
+
+        for item in &set.items {
+            if !is_included(item.node.directives(), variables)? {
+                continue;
+            }
+
+            let Field {
+                name,
+                selection_set,
+                arguments,
+                alias,
+                ..
+            } = match &item.node {
+                async_graphql_parser::types::Selection::Field(field) => &field.node,
+                _ => return Err(GraphqlError::SelectionNotSupported),
+            };
+
+            let subfield_type =
+                meta_field_type(typ, name.node.as_str()).ok_or_else(|| {
+                    GraphqlError::UnrecognizedField(typ.to_string(), name.to_string())
+                })?;
+
+            if let Some((arg, _)) = arguments.first() {
+                return Err(GraphqlError::UnrecognizedArgument(
+                    typ.to_string(),
+                    arg.to_string(),
+                ));
+            }
+
+            selections.push(Selection::Field {
+                name: name.to_string(),
+                params: Vec::new(),
+                sub_selections: Selections::new_meta(
+                    subfield_type,
+                    &selection_set.node,
+                    variables,
+                )?,
+                alias: alias.as_ref().map(|a| a.to_string()),
+            });
+        }
+
+        Ok(Selections {
+            has_fragments: false,
+            selections,
+        })
+    }
+
     pub fn resolve_fragments(
         &mut self,
         schema: &IndexerSchema,
@@ -169,6 +349,16 @@ impl Selections {
             match selection {
                 Selection::Fragment(name) => {
                     if let Some(frag) = fragments.get(name) {
+                        // Fragments on a possible type of a union or interface, or on
+                        // an interface of an object, are kept as inline fragments.
+                        if let Some(c) = cond {
+                            if !frag.check_cond(cond) && frag.applies_to(schema, c) {
+                                resolved += 1;
+                                selections.push(Selection::InlineFragment(frag.clone()));
+                                continue;
+                            }
+                        }
+
                         if !frag.check_cond(cond) {
                             if let Some(c) = cond {
                                 return Err(GraphqlError::InvalidFragmentSelection(
@@ -186,6 +376,17 @@ impl Selections {
                         selections.push(Selection::Fragment(name.to_string()));
                     }
                 }
+                Selection::InlineFragment(frag) => {
+                    resolved += frag.resolve_fragments(schema, fragments)?;
+                    has_fragments |= frag.has_fragments();
+                    selections.push(Selection::InlineFragment(frag.clone()));
+                }
+                Selection::Field { name, .. } if name == "__typename" => {
+                    selections.push(selection.clone());
+                }
+                Selection::Field { name, .. } if cond.is_none() && name == META_FIELD => {
+                    selections.push(selection.clone());
+                }
                 Selection::Field {
                     name,
                     params,
@@ -227,6 +428,171 @@ impl Selections {
     pub fn get_selections(&self) -> Vec<Selection> {
         self.selections.clone()
     }
+
+    /// Merge inline fragments into the selections on the top-level entities of
+    /// an operation, and on the entities nested within them.
+    ///
+    /// Selections on top-level union and interface entities are split into one
+    /// inline fragment for each of their possible types, as these entities are
+    /// queried through the tables of their possible types.
+    pub fn resolve_inline_fragments(
+        &mut self,
+        schema: &IndexerSchema,
+    ) -> GraphqlResult<()> {
+        let mut selections = Vec::new();
+
+        for selection in self.get_selections() {
+            if matches!(&selection, Selection::Field { name, .. } if name == META_FIELD) {
+                selections.push(selection);
+                continue;
+            }
+
+            match selection {
+                Selection::Field {
+                    name,
+                    params,
+                    sub_selections,
+                    alias,
+                } => {
+                    let entity_type = schema
+                        .parsed()
+                        .graphql_type(None, &name)
+                        .ok_or_else(|| GraphqlError::UnrecognizedType(name.clone()))?;
+
+                    let sub_selections = match schema.parsed().possible_types(entity_type)
+                    {
+                        Some(possible_types) => Selections {
+                            has_fragments: false,
+                            selections: possible_types
+                                .into_iter()
+                                .map(|typ| {
+                                    let selections = sub_selections
+                                        .merge_inline_fragments(schema, &typ, true)?;
+                                    Ok(Selection::InlineFragment(Fragment {
+                                        cond: typ,
+                                        selections,
+                                    }))
+                                })
+                                .collect::<GraphqlResult<Vec<Selection>>>()?,
+                        },
+                        None => sub_selections.merge_inline_fragments(
+                            schema,
+                            entity_type,
+                            false,
+                        )?,
+                    };
+
+                    selections.push(Selection::Field {
+                        name,
+                        params,
+                        sub_selections,
+                        alias,
+                    });
+                }
+                _ => return Err(GraphqlError::SelectionNotSupported),
+            }
+        }
+
+        self.selections = selections;
+        Ok(())
+    }
+
+    /// Return the selections on an entity of the given type, with the inline
+    /// fragments that apply to that type merged into them.
+    ///
+    /// If the selections were made on a union or interface, then selections on
+    /// fields that the type does not have are left out.
+    fn merge_inline_fragments(
+        &self,
+        schema: &IndexerSchema,
+        typ: &str,
+        is_possible_type: bool,
+    ) -> GraphqlResult<Selections> {
+        let mut selections: Vec<Selection> = Vec::new();
+
+        for selection in self.get_selections() {
+            match selection {
+                Selection::Field {
+                    name,
+                    params,
+                    sub_selections,
+                    alias,
+                } => {
+                    let key = alias.clone().unwrap_or(name.clone());
+                    let is_selected = selections.iter().any(|s| {
+                        matches!(s, Selection::Field { name, alias, sub_selections, .. }
+                            if alias.clone().unwrap_or(name.clone()) == key
+                                && sub_selections.selections.is_empty())
+                    });
+                    if is_selected && sub_selections.selections.is_empty() {
+                        continue;
+                    }
+
+                    if name == "__typename" {
+                        selections.push(Selection::Field {
+                            name,
+                            params,
+                            sub_selections,
+                            alias,
+                        });
+                        continue;
+                    }
+
+                    let field_type =
+                        match schema.parsed().graphql_type(Some(&typ.to_string()), &name)
+                        {
+                            Some(field_type) => field_type,
+                            None if is_possible_type => continue,
+                            None => {
+                                return Err(GraphqlError::UnrecognizedField(
+                                    typ.to_string(),
+                                    name,
+                                ))
+                            }
+                        };
+
+                    let sub_selections = if sub_selections.selections.is_empty() {
+                        sub_selections
+                    } else {
+                        sub_selections
+                            .merge_inline_fragments(schema, field_type, false)?
+                    };
+
+                    selections.push(Selection::Field {
+                        name,
+                        params,
+                        sub_selections,
+                        alias,
+                    });
+                }
+                Selection::InlineFragment(frag) => {
+                    if frag.cond == typ || frag.is_implemented_by(schema, typ) {
+                        let merged = frag.selections.merge_inline_fragments(
+                            schema,
+                            typ,
+                            is_possible_type,
+                        )?;
+                        selections.extend(merged.selections);
+                    } else if !is_possible_type {
+                        // The possible type of a nested union or interface entity
+                        // is not known, as these are stored in tables of their own.
+                        return Err(GraphqlError::InvalidFragmentSelection(
+                            frag,
+                            typ.to_string(),
+                        ));
+                    }
+                }
+                Selection::Fragment(_) => {
+                    return Err(GraphqlError::FragmentResolverFailed)
+                }
+            }
+        }
+
+        Ok(Selections {
+            has_fragments: false,
+            selections,
+        })
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -240,8 +606,9 @@ impl Fragment {
         schema: &IndexerSchema,
         cond: String,
         selection_set: &async_graphql_parser::types::SelectionSet,
+        variables: &VariableValues,
     ) -> GraphqlResult<Fragment> {
-        let selections = Selections::new(schema, Some(&cond), selection_set)?;
+        let selections = Selections::new(schema, Some(&cond), selection_set, variables)?;
 
         Ok(Fragment { cond, selections })
     }
@@ -254,6 +621,27 @@ impl Fragment {
         }
     }
 
+    /// Whether this fragment can be used within the selections on the given type;
+    /// that is, whether the type condition of this fragment is that type, one of
+    /// its possible types, or an interface that it implements.
+    pub fn applies_to(&self, schema: &IndexerSchema, typ: &str) -> bool {
+        self.cond == typ
+            || schema
+                .parsed()
+                .possible_types(typ)
+                .is_some_and(|types| types.contains(&self.cond))
+            || self.is_implemented_by(schema, typ)
+    }
+
+    /// Whether the type condition of this fragment is a union or interface, of
+    /// which the given type is a possible type.
+    fn is_implemented_by(&self, schema: &IndexerSchema, typ: &str) -> bool {
+        schema
+            .parsed()
+            .possible_types(&self.cond)
+            .is_some_and(|types| types.iter().any(|t| t == typ))
+    }
+
     pub fn has_fragments(&self) -> bool {
         self.selections.has_fragments
     }
@@ -274,6 +662,8 @@ pub struct Operation {
     namespace: String,
     identifier: String,
     selections: Selections,
+    /// Whether 64-bit and 128-bit integers are returned as strings.
+    stringify_large_integers: bool,
 }
 
 impl Operation {
@@ -281,11 +671,13 @@ impl Operation {
         namespace: String,
         identifier: String,
         selections: Selections,
+        stringify_large_integers: bool,
     ) -> Operation {
         Operation {
             namespace,
             identifier,
             selections,
+            stringify_large_integers,
         }
     }
 
@@ -300,17 +692,6 @@ impl Operation {
         let mut queries = Vec::new();
 
         for selection in selections.get_selections() {
-            let mut elements: Vec<QueryElement> = Vec::new();
-            let mut entities: Vec<String> = Vec::new();
-
-            let mut joins: HashMap<String, QueryJoinNode> = HashMap::new();
-            let mut query_params: QueryParams = QueryParams::default();
-
-            let mut nested_entity_stack: Vec<String> = Vec::new();
-
-            // Selections can have their own set of subselections and so on, so a queue
-            // is created with the first level of selections. In order to track the containing
-            // entity of the selection, an entity list of the same length is created.
             if let Selection::Field {
                 name: entity_name,
                 params: filters,
@@ -318,243 +699,609 @@ impl Operation {
                 alias,
             } = selection
             {
-                let mut queue: Vec<Selection> = Vec::new();
-
-                // Selections and entities will be popped from their respective vectors
-                // easy access to an element. In order to be compliant with the GraphQL
-                // spec (which says that a query should be resovled top-down), the order
-                // of the elements is reversed prior to insertion in the queues.
-                entities.append(
-                    &mut vec![entity_name.clone(); selections.selections.len()]
-                        .drain(..)
-                        .rev()
-                        .collect::<Vec<String>>(),
-                );
-                queue.append(
-                    &mut selections
-                        .get_selections()
-                        .drain(..)
-                        .rev()
-                        .collect::<Vec<Selection>>(),
-                );
-
-                if !filters.is_empty() {
-                    query_params.add_params(
-                        filters,
-                        format!("{namespace}_{identifier}.{entity_name}"),
-                    );
+                // The metadata of the indexer is not stored in an entity table.
+                if entity_name == META_FIELD {
+                    continue;
                 }
 
-                let mut last_seen_entities_len = entities.len();
+                let is_abstract = schema
+                    .parsed()
+                    .graphql_type(None, &entity_name)
+                    .is_some_and(|typ| schema.parsed().possible_types(typ).is_some());
 
-                while let Some(current) = queue.pop() {
-                    let entity_name = entities.pop().unwrap();
+                if !is_abstract {
+                    queries.push(self.parse_entity(
+                        schema,
+                        entity_name,
+                        filters,
+                        selections,
+                        alias,
+                        None,
+                    ));
+                    continue;
+                }
 
-                    // If a selection was processed without adding additional selections
-                    // to the queue, then check the entity of the selection against the
-                    // current nesting level. If they differ, then the operation has moved
-                    // out of a child entity into a parent entity.
-                    if let Some(current_nesting_level) = nested_entity_stack.last() {
-                        if entities.len() < last_seen_entities_len
-                            && current_nesting_level != &entity_name
-                        {
-                            let _ = nested_entity_stack.pop();
-                            elements.push(QueryElement::ObjectClosingBoundary);
+                // Union and interface types are queried through the tables of their
+                // possible types, for each of which the selections have been merged
+                // into an inline fragment.
+                let members = selections
+                    .get_selections()
+                    .into_iter()
+                    .filter_map(|selection| match selection {
+                        Selection::InlineFragment(Fragment { cond, selections }) => {
+                            Some(self.parse_entity(
+                                schema,
+                                cond.to_lowercase(),
+                                filters.clone(),
+                                selections,
+                                alias.clone(),
+                                None,
+                            ))
                         }
+                        _ => None,
+                    })
+                    .collect();
+
+                queries.push(UserQuery {
+                    elements: Vec::new(),
+                    joins: HashMap::new(),
+                    namespace_identifier: format!("{namespace}_{identifier}"),
+                    entity_name,
+                    query_params: QueryParams::default(),
+                    alias,
+                    members,
+                    nested: Vec::new(),
+                });
+            }
+        }
+
+        queries
+    }
+
+    /// Parse the selections made on an entity into a query.
+    ///
+    /// Lists of related entities are selected with queries of their own, made
+    /// at the block height of the query of their parent entity, if any.
+    fn parse_entity(
+        &self,
+        schema: &IndexerSchema,
+        entity_name: String,
+        filters: Vec<ParamType>,
+        selections: Selections,
+        alias: Option<String>,
+        block_height: Option<u64>,
+    ) -> UserQuery {
+        let Operation {
+            namespace,
+            identifier,
+            stringify_large_integers,
+            ..
+        } = self;
+
+        let mut elements: Vec<QueryElement> = Vec::new();
+        let mut entities: Vec<String> = Vec::new();
+
+        let mut joins: HashMap<String, QueryJoinNode> = HashMap::new();
+        let mut nested: Vec<NestedQuery> = Vec::new();
+        let mut query_params: QueryParams = QueryParams {
+            block_height,
+            ..QueryParams::default()
+        };
+
+        let mut nested_entity_stack: Vec<String> = Vec::new();
+
+        // Selections can have their own set of subselections and so on, so a queue
+        // is created with the first level of selections. In order to track the containing
+        // entity of the selection, an entity list of the same length is created.
+        let mut queue: Vec<Selection> = Vec::new();
+
+        // Queries made at a block height select from common table
+        // expressions named after the entity tables, rather than from
+        // the tables themselves.
+        let table_prefix = if block_height.is_some()
+            || filters
+                .iter()
+                .any(|param| matches!(param, ParamType::Block(_)))
+        {
+            "".to_string()
+        } else {
+            format!("{namespace}_{identifier}.")
+        };
+
+        // Selections and entities will be popped from their respective vectors
+        // easy access to an element. In order to be compliant with the GraphQL
+        // spec (which says that a query should be resovled top-down), the order
+        // of the elements is reversed prior to insertion in the queues.
+        entities.append(
+            &mut vec![entity_name.clone(); selections.selections.len()]
+                .drain(..)
+                .rev()
+                .collect::<Vec<String>>(),
+        );
+        queue.append(
+            &mut selections
+                .get_selections()
+                .drain(..)
+                .rev()
+                .collect::<Vec<Selection>>(),
+        );
+
+        if !filters.is_empty() {
+            query_params.add_params(filters, format!("{table_prefix}{entity_name}"));
+        }
+
+        let mut last_seen_entities_len = entities.len();
+
+        while let Some(current) = queue.pop() {
+            let entity_name = entities.pop().unwrap();
+
+            // If a selection was processed without adding additional selections
+            // to the queue, then check the entity of the selection against the
+            // current nesting level. If they differ, then the operation has moved
+            // out of a child entity into a parent entity.
+            if let Some(current_nesting_level) = nested_entity_stack.last() {
+                if entities.len() < last_seen_entities_len
+                    && current_nesting_level != &entity_name
+                {
+                    let _ = nested_entity_stack.pop();
+                    elements.push(QueryElement::ObjectClosingBoundary);
+                }
+            }
+
+            last_seen_entities_len = entities.len();
+
+            if let Selection::Field {
+                name: field_name,
+                params: filters,
+                sub_selections: subselections,
+                alias,
+            } = current
+            {
+                if field_name == "__typename" {
+                    let type_name = schema
+                        .parsed()
+                        .graphql_type(None, &entity_name.to_lowercase())
+                        .cloned()
+                        .unwrap_or(entity_name.clone());
+                    elements.push(QueryElement::Field {
+                        key: alias.unwrap_or(field_name.clone()),
+                        value: format!("'{type_name}'"),
+                    });
+                } else if subselections.selections.is_empty() {
+                    let value = select_field(
+                        schema,
+                        &entity_name,
+                        &field_name,
+                        format!("{table_prefix}{entity_name}.{field_name}"),
+                        *stringify_large_integers,
+                    );
+                    elements.push(QueryElement::Field {
+                        key: alias.unwrap_or(field_name.clone()),
+                        value,
+                    });
+                    if !filters.is_empty() {
+                        query_params
+                            .add_params(filters, format!("{table_prefix}{entity_name}"));
                     }
+                } else if let Some((child_table_name, join, condition)) =
+                    self.list_relation(schema, &entity_name, &field_name, &table_prefix)
+                {
+                    // Lists of related entities are selected in a query of their
+                    // own, so that the arguments of the field only apply to the
+                    // entities in the list.
+                    let key = alias.unwrap_or(field_name.clone());
+
+                    let query = self.parse_entity(
+                        schema,
+                        child_table_name,
+                        filters,
+                        subselections,
+                        None,
+                        query_params.block_height,
+                    );
 
-                    last_seen_entities_len = entities.len();
+                    let nested_query = NestedQuery {
+                        alias: format!("{entity_name}_{key}_{}", nested.len()),
+                        join,
+                        condition,
+                        query,
+                    };
 
-                    if let Selection::Field {
-                        name: field_name,
-                        params: filters,
-                        sub_selections: subselections,
-                        alias,
-                    } = current
+                    elements.push(QueryElement::Field {
+                        key,
+                        value: format!("{}.items", nested_query.alias),
+                    });
+                    nested.push(nested_query);
+                } else {
+                    let mut new_entity = field_name.clone();
+                    // If the current entity has a foreign key on the current
+                    // selection, join the foreign table on that primary key
+                    // and set the field as the innermost entity by pushing to the stack.
+                    if let Some(field_to_foreign_key) = schema
+                        .parsed()
+                        .foreign_key_mappings()
+                        .get(&entity_name.to_lowercase())
                     {
-                        if subselections.selections.is_empty() {
-                            elements.push(QueryElement::Field {
-                                key: alias.unwrap_or(field_name.clone()),
-                                value: format!(
-                                    "{namespace}_{identifier}.{entity_name}.{field_name}"
+                        if let Some((foreign_key_table, foreign_key_col)) =
+                            field_to_foreign_key.get(&field_name.to_lowercase())
+                        {
+                            let join_condition = JoinCondition {
+                                referencing_key_table: format!(
+                                    "{table_prefix}{entity_name}"
                                 ),
-                            });
-                            if !filters.is_empty() {
-                                query_params.add_params(
-                                    filters,
-                                    format!("{namespace}_{identifier}.{entity_name}"),
-                                );
-                            }
-                        } else {
-                            let mut new_entity = field_name.clone();
-                            // If the current entity has a foreign key on the current
-                            // selection, join the foreign table on that primary key
-                            // and set the field as the innermost entity by pushing to the stack.
-                            if let Some(field_to_foreign_key) = schema
-                                .parsed()
-                                .foreign_key_mappings()
-                                .get(&entity_name.to_lowercase())
-                            {
-                                if let Some((foreign_key_table, foreign_key_col)) =
-                                    field_to_foreign_key.get(&field_name.to_lowercase())
-                                {
-                                    let join_condition = JoinCondition {
-                                        referencing_key_table: format!(
-                                            "{namespace}_{identifier}.{entity_name}"
-                                        ),
-                                        referencing_key_col: field_name.clone(),
-                                        primary_key_table: format!(
-                                            "{namespace}_{identifier}.{foreign_key_table}"
-                                        ),
-                                        primary_key_col: foreign_key_col.clone(),
-                                    };
-
-                                    // Joins are modelled like a directed graph in
-                                    // order to ensure that tables can be joined in
-                                    // a dependent order, if necessary.
-                                    match joins
-                                        .get_mut(&join_condition.referencing_key_table)
-                                    {
-                                        Some(join_node) => {
-                                            join_node.dependencies.insert(
+                                referencing_key_col: field_name.clone(),
+                                primary_key_table: format!(
+                                    "{table_prefix}{foreign_key_table}"
+                                ),
+                                primary_key_col: foreign_key_col.clone(),
+                            };
+
+                            // Joins are modelled like a directed graph in
+                            // order to ensure that tables can be joined in
+                            // a dependent order, if necessary.
+                            match joins.get_mut(&join_condition.referencing_key_table) {
+                                Some(join_node) => {
+                                    join_node.dependencies.insert(
+                                        join_condition.primary_key_table.clone(),
+                                        join_condition.clone(),
+                                    );
+                                }
+                                None => {
+                                    joins.insert(
+                                        join_condition.referencing_key_table.clone(),
+                                        QueryJoinNode {
+                                            dependencies: HashMap::from([(
                                                 join_condition.primary_key_table.clone(),
                                                 join_condition.clone(),
-                                            );
-                                        }
-                                        None => {
-                                            joins.insert(
-                                                join_condition
-                                                    .referencing_key_table
-                                                    .clone(),
-                                                QueryJoinNode {
-                                                    dependencies: HashMap::from([(
-                                                        join_condition
-                                                            .primary_key_table
-                                                            .clone(),
-                                                        join_condition.clone(),
-                                                    )]),
-                                                    dependents: HashMap::new(),
-                                                },
-                                            );
-                                        }
-                                    };
-
-                                    if *foreign_key_table != field_name {
-                                        new_entity = foreign_key_table.to_string();
-                                    }
-
-                                    match joins.get_mut(&join_condition.primary_key_table)
-                                    {
-                                        Some(join_node) => {
-                                            join_node.dependents.insert(
+                                            )]),
+                                            dependents: HashMap::new(),
+                                        },
+                                    );
+                                }
+                            };
+
+                            if *foreign_key_table != field_name {
+                                new_entity = foreign_key_table.to_string();
+                            }
+
+                            match joins.get_mut(&join_condition.primary_key_table) {
+                                Some(join_node) => {
+                                    join_node.dependents.insert(
+                                        join_condition.referencing_key_table.clone(),
+                                        join_condition.clone(),
+                                    );
+                                }
+                                None => {
+                                    joins.insert(
+                                        join_condition.primary_key_table.clone(),
+                                        QueryJoinNode {
+                                            dependencies: HashMap::new(),
+                                            dependents: HashMap::from([(
                                                 join_condition
                                                     .referencing_key_table
                                                     .clone(),
                                                 join_condition.clone(),
-                                            );
-                                        }
-                                        None => {
-                                            joins.insert(
-                                                join_condition.primary_key_table.clone(),
-                                                QueryJoinNode {
-                                                    dependencies: HashMap::new(),
-                                                    dependents: HashMap::from([(
-                                                        join_condition
-                                                            .referencing_key_table
-                                                            .clone(),
-                                                        join_condition.clone(),
-                                                    )]),
-                                                },
-                                            );
-                                        }
-                                    };
-                                    if !filters.is_empty() {
-                                        query_params.add_params(
-                                    filters,
-                                    format!("{namespace}_{identifier}.{foreign_key_table}"),
-                                        );
-                                    }
+                                            )]),
+                                        },
+                                    );
                                 }
+                            };
+                            if !filters.is_empty() {
+                                query_params.add_params(
+                                    filters,
+                                    format!("{table_prefix}{foreign_key_table}"),
+                                );
                             }
-
-                            // Add the subselections and entities to the ends of
-                            // their respective vectors so that they are resolved
-                            // immediately after their parent selection.
-                            entities.append(&mut vec![
-                                new_entity.clone();
-                                subselections.selections.len()
-                            ]);
-                            nested_entity_stack.push(new_entity.clone());
-
-                            elements.push(QueryElement::ObjectOpeningBoundary {
-                                key: alias.unwrap_or(field_name.clone()),
-                            });
-
-                            queue.append(&mut subselections.get_selections());
                         }
                     }
-                }
 
-                // If the query document ends without selections from outer entities,
-                // then append the requisite number of object closing boundaries in
-                // order to properly format the JSON structure for the database query.
-                if !nested_entity_stack.is_empty() {
-                    elements.append(&mut vec![
-                        QueryElement::ObjectClosingBoundary;
-                        nested_entity_stack.len()
+                    // Add the subselections and entities to the ends of
+                    // their respective vectors so that they are resolved
+                    // immediately after their parent selection.
+                    entities.append(&mut vec![
+                        new_entity.clone();
+                        subselections.selections.len()
                     ]);
-                }
+                    nested_entity_stack.push(new_entity.clone());
 
-                let query = UserQuery {
-                    elements,
-                    joins,
-                    namespace_identifier: format!("{namespace}_{identifier}"),
-                    entity_name,
-                    query_params,
-                    alias,
-                };
+                    elements.push(QueryElement::ObjectOpeningBoundary {
+                        key: alias.unwrap_or(field_name.clone()),
+                    });
 
-                queries.push(query)
+                    queue.append(&mut subselections.get_selections());
+                }
             }
         }
 
-        queries
-    }
-}
-
-#[derive(Debug)]
-pub struct GraphqlQuery {
-    operations: Vec<Operation>,
-}
-
-impl GraphqlQuery {
-    pub fn parse(&self, schema: &IndexerSchema) -> Vec<UserQuery> {
-        let queries: Vec<UserQuery> = self
-            .operations
-            .iter()
-            .flat_map(|o| o.parse(schema))
-            .collect::<Vec<UserQuery>>();
+        // If the query document ends without selections from outer entities,
+        // then append the requisite number of object closing boundaries in
+        // order to properly format the JSON structure for the database query.
+        if !nested_entity_stack.is_empty() {
+            elements.append(&mut vec![
+                QueryElement::ObjectClosingBoundary;
+                nested_entity_stack.len()
+            ]);
+        }
 
-        queries
+        UserQuery {
+            elements,
+            joins,
+            namespace_identifier: format!("{namespace}_{identifier}"),
+            entity_name,
+            query_params,
+            alias,
+            members: Vec::new(),
+            nested,
+        }
     }
 
-    pub fn as_sql(
+    /// Return the table of the entities listed by the given field of an entity,
+    /// along with the join of the table through which they are related to the
+    /// entity, if any, and the condition on which they are related to it.
+    ///
+    /// Lists are either derived from the foreign keys of the listed entities,
+    /// or stored in a join table.
+    fn list_relation(
         &self,
         schema: &IndexerSchema,
-        db_type: DbType,
-    ) -> Result<Vec<String>, GraphqlError> {
-        let queries = self.parse(schema);
+        entity_name: &str,
+        field_name: &str,
+        table_prefix: &str,
+    ) -> Option<(String, Option<JoinCondition>, JoinCondition)> {
+        let Operation {
+            namespace,
+            identifier,
+            ..
+        } = self;
 
-        queries
-            .into_iter()
-            .map(|mut q| q.to_sql(&db_type))
-            .collect::<Result<Vec<String>, GraphqlError>>()
-    }
-}
+        let parsed = schema.parsed();
+        let typ = parsed.graphql_type(None, entity_name)?;
+
+        if let Some(derived_from) = parsed.derived_from(typ, field_name) {
+            let child_table_name =
+                parsed.graphql_type(Some(typ), field_name)?.to_lowercase();
+            let (_, primary_key_col) = parsed
+                .foreign_key_mappings()
+                .get(&child_table_name)?
+                .get(derived_from)?;
+
+            let condition = JoinCondition {
+                referencing_key_table: format!("{table_prefix}{child_table_name}"),
+                referencing_key_col: derived_from.clone(),
+                primary_key_table: format!("{table_prefix}{entity_name}"),
+                primary_key_col: primary_key_col.clone(),
+            };
+
+            return Some((child_table_name, None, condition));
+        }
+
+        let meta = parsed.field_join_table_meta(typ, field_name)?;
+        let join_table = format!("{namespace}_{identifier}.{}", meta.table_name());
+        let child_table_name = meta.child_table_name();
+
+        let join = JoinCondition {
+            referencing_key_table: join_table.clone(),
+            referencing_key_col: format!(
+                "{child_table_name}_{}",
+                meta.child_column_name()
+            ),
+            primary_key_table: format!("{table_prefix}{child_table_name}"),
+            primary_key_col: meta.child_column_name(),
+        };
+
+        let condition = JoinCondition {
+            referencing_key_table: join_table,
+            referencing_key_col: format!(
+                "{}_{}",
+                meta.parent_table_name(),
+                meta.parent_column_name()
+            ),
+            primary_key_table: format!("{table_prefix}{entity_name}"),
+            primary_key_col: meta.parent_column_name(),
+        };
+
+        Some((child_table_name, Some(join), condition))
+    }
+
+    /// Parse the selections made on the `_meta` root field into queries.
+    ///
+    /// The block is the last block that was indexed, and the status of the
+    /// indexer is taken from the registry of the indexer service.
+    pub fn parse_meta(&self) -> Vec<String> {
+        let Operation {
+            namespace,
+            identifier,
+            selections,
+            stringify_large_integers,
+        } = self;
+
+        let select = |selections: Selections, column: &dyn Fn(&str) -> String| {
+            selections
+                .get_selections()
+                .into_iter()
+                .filter_map(|selection| match selection {
+                    Selection::Field { name, alias, .. } => Some(format!(
+                        "'{}', {}",
+                        alias.unwrap_or(name.clone()),
+                        column(&name)
+                    )),
+                    _ => None,
+                })
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let block_column = |name: &str| match name {
+            "height" => "block_height".to_string(),
+            "id" => "block_id".to_string(),
+            "time" if *stringify_large_integers => "time::text".to_string(),
+            _ => name.to_string(),
+        };
+
+        let indexer_column = |name: &str| match name {
+            "status" => "COALESCE(index_status.status, 'unknown')".to_string(),
+            _ => "to_char(index_registry.created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')"
+                .to_string(),
+        };
+
+        let mut queries = Vec::new();
+
+        for selection in selections.get_selections() {
+            if let Selection::Field {
+                name,
+                sub_selections,
+                alias,
+                ..
+            } = selection
+            {
+                if name != META_FIELD {
+                    continue;
+                }
+
+                let objects = sub_selections
+                    .get_selections()
+                    .into_iter()
+                    .filter_map(|selection| match selection {
+                        Selection::Field {
+                            name,
+                            sub_selections,
+                            alias,
+                            ..
+                        } => {
+                            let object = if name == "block" {
+                                format!(
+                                    "(SELECT json_build_object({}) FROM {namespace}_{identifier}.indexmetadataentity ORDER BY block_height DESC LIMIT 1)",
+                                    select(sub_selections, &block_column)
+                                )
+                            } else {
+                                format!(
+                                    "(SELECT json_build_object({}) FROM index_registry LEFT JOIN index_status ON index_status.indexer_id = index_registry.id WHERE index_registry.namespace = '{namespace}' AND index_registry.identifier = '{identifier}')",
+                                    select(sub_selections, &indexer_column)
+                                )
+                            };
+
+                            Some(format!("'{}', {object}", alias.unwrap_or(name)))
+                        }
+                        _ => None,
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                queries.push(format!(
+                    "SELECT json_build_object('{}', json_build_object({objects}))",
+                    alias.unwrap_or(name)
+                ));
+            }
+        }
+
+        queries
+    }
+}
+
+/// Return the type of a field of a type of the metadata of an indexer.
+fn meta_field_type(typ: &str, field_name: &str) -> Option<&'static str> {
+    META_TYPES
+        .iter()
+        .find(|(name, _)| *name == typ)
+        .and_then(|(_, fields)| fields.iter().find(|(name, _)| *name == field_name))
+        .map(|(_, field_type)| *field_type)
+}
+
+/// Return the expression with which the given entity field is selected into
+/// query results.
+///
+/// Integers that do not fit into JSON numbers are returned as decimal strings,
+/// and timestamps as RFC 3339 date-times in UTC. If `stringify_large_integers`
+/// is set, 64-bit and 128-bit integers are returned as decimal strings as well.
+fn select_field(
+    schema: &IndexerSchema,
+    entity_name: &str,
+    field_name: &str,
+    column: String,
+    stringify_large_integers: bool,
+) -> String {
+    let field_type = schema
+        .parsed()
+        .object_field_mappings()
+        .iter()
+        .find(|(typ, _)| typ.to_lowercase() == entity_name.to_lowercase())
+        .and_then(|(_, fields)| fields.get(field_name));
+
+    match field_type.map(|typ| typ.as_str()) {
+        Some(typ) if STRING_ENCODED_NUMERIC_TYPES.contains(typ) => {
+            format!("{column}::text")
+        }
+        Some(typ) if stringify_large_integers && LARGE_INTEGER_TYPES.contains(typ) => {
+            format!("{column}::text")
+        }
+        Some("Timestamp") => format!(
+            "to_char({column} AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')"
+        ),
+        _ => column,
+    }
+}
+
+#[derive(Debug)]
+pub struct GraphqlQuery {
+    operations: Vec<Operation>,
+}
+
+impl GraphqlQuery {
+    pub fn parse(&self, schema: &IndexerSchema) -> Vec<UserQuery> {
+        let queries: Vec<UserQuery> = self
+            .operations
+            .iter()
+            .flat_map(|o| o.parse(schema))
+            .collect::<Vec<UserQuery>>();
+
+        queries
+    }
+
+    pub fn as_sql(
+        &self,
+        schema: &IndexerSchema,
+        db_type: DbType,
+    ) -> Result<Vec<String>, GraphqlError> {
+        let queries = self.parse(schema);
+
+        // Entities can only be queried at a block height if they keep history.
+        for query in queries
+            .iter()
+            .flat_map(|q| std::iter::once(q).chain(q.members.iter()))
+        {
+            if query.query_params.block_height.is_some() {
+                for table in query.tables() {
+                    let keeps_history = schema
+                        .parsed()
+                        .graphql_type(None, table)
+                        .map(|name| schema.parsed().is_history_typedef(name))
+                        .unwrap_or(false);
+
+                    if !keeps_history {
+                        return Err(GraphqlError::NoEntityHistory(table.to_string()));
+                    }
+                }
+            }
+        }
+
+        let mut queries = queries
+            .into_iter()
+            .map(|mut q| q.to_sql(&db_type))
+            .collect::<Result<Vec<String>, GraphqlError>>()?;
+
+        // The metadata of the indexer is queried after its entities.
+        queries.extend(self.operations.iter().flat_map(|o| o.parse_meta()));
+
+        Ok(queries)
+    }
+}
 
 pub struct GraphqlQueryBuilder<'a> {
     schema: &'a IndexerSchema,
     document: ExecutableDocument,
+    variables: Variables,
+    operation_name: Option<String>,
+    stringify_large_integers: bool,
 }
 
 impl<'a> GraphqlQueryBuilder<'a> {
@@ -563,32 +1310,67 @@ impl<'a> GraphqlQueryBuilder<'a> {
         query: &'a str,
     ) -> GraphqlResult<GraphqlQueryBuilder<'a>> {
         let document = parse_query::<&str>(query)?;
-        Ok(GraphqlQueryBuilder { schema, document })
+        Ok(GraphqlQueryBuilder {
+            schema,
+            document,
+            variables: Variables::default(),
+            operation_name: None,
+            stringify_large_integers: false,
+        })
+    }
+
+    /// Values of the variables used in the query document.
+    pub fn variables(mut self, variables: Variables) -> Self {
+        self.variables = variables;
+        self
+    }
+
+    /// Name of the operation to run, which is required when the query document
+    /// defines several operations.
+    pub fn operation_name(mut self, operation_name: Option<String>) -> Self {
+        self.operation_name = operation_name;
+        self
+    }
+
+    /// Return 64-bit and 128-bit integers as strings, so that they can be read
+    /// by clients without loss of precision.
+    pub fn stringify_large_integers(mut self, stringify_large_integers: bool) -> Self {
+        self.stringify_large_integers = stringify_large_integers;
+        self
     }
 
     pub fn build(self) -> GraphqlResult<GraphqlQuery> {
-        let fragments = self.process_fragments()?;
-        let operations = self.process_operations(fragments)?;
+        let operations = self.process_operations()?;
         Ok(GraphqlQuery { operations })
     }
 
     fn process_operation(
         &self,
         operation: &OperationDefinition,
-        fragments: &HashMap<String, Fragment>,
     ) -> GraphqlResult<Operation> {
         match operation.ty {
             OperationType::Query => {
-                // TODO: directives and variable definitions....
-                let OperationDefinition { selection_set, .. } = operation;
+                let OperationDefinition {
+                    selection_set,
+                    variable_definitions,
+                    ..
+                } = operation;
+
+                // Fragments are processed for each operation, as the arguments and
+                // directives in them can refer to the variables of the operation.
+                let variables = self.process_variables(variable_definitions)?;
+                let fragments = self.process_fragments(&variables)?;
+
                 let mut selections =
-                    Selections::new(self.schema, None, &selection_set.node)?;
-                selections.resolve_fragments(self.schema, None, fragments)?;
+                    Selections::new(self.schema, None, &selection_set.node, &variables)?;
+                selections.resolve_fragments(self.schema, None, &fragments)?;
+                selections.resolve_inline_fragments(self.schema)?;
 
                 Ok(Operation::new(
                     self.schema.parsed().namespace().to_string(),
                     self.schema.parsed().identifier().to_string(),
                     selections,
+                    self.stringify_large_integers,
                 ))
             }
             OperationType::Mutation => {
@@ -600,29 +1382,71 @@ impl<'a> GraphqlQueryBuilder<'a> {
         }
     }
 
-    fn process_operations(
+    /// Process the operation to run: the one named by the request, or else the
+    /// only operation of the query document.
+    fn process_operations(&self) -> GraphqlResult<Vec<Operation>> {
+        let operation_def = match (&self.document.operations, &self.operation_name) {
+            (DocumentOperations::Single(operation_def), None) => operation_def,
+            (DocumentOperations::Multiple(operation_map), Some(name)) => operation_map
+                .get(name.as_str())
+                .ok_or_else(|| GraphqlError::UnknownOperation(name.to_string()))?,
+            (DocumentOperations::Multiple(operation_map), None) => {
+                match operation_map.values().next() {
+                    Some(operation_def) if operation_map.len() == 1 => operation_def,
+                    _ => return Err(GraphqlError::MissingOperationName),
+                }
+            }
+            // Anonymous operations can't be selected by name.
+            (DocumentOperations::Single(_), Some(name)) => {
+                return Err(GraphqlError::UnknownOperation(name.to_string()))
+            }
+        };
+
+        Ok(vec![self.process_operation(&operation_def.node)?])
+    }
+
+    /// Return the values of the variables defined by an operation, taken from the
+    /// given variables or from the defaults in their definitions.
+    fn process_variables(
         &self,
-        fragments: HashMap<String, Fragment>,
-    ) -> GraphqlResult<Vec<Operation>> {
-        let mut operations = vec![];
-
-        match &self.document.operations {
-            DocumentOperations::Single(operation_def) => {
-                let op = self.process_operation(&operation_def.node, &fragments)?;
-                operations.push(op);
-            }
-            DocumentOperations::Multiple(operation_map) => {
-                for (_name, operation_def) in operation_map.iter() {
-                    let op = self.process_operation(&operation_def.node, &fragments)?;
-                    operations.push(op);
+        definitions: &[Positioned<VariableDefinition>],
+    ) -> GraphqlResult<VariableValues> {
+        let mut values = HashMap::new();
+
+        for definition in definitions {
+            let VariableDefinition {
+                name,
+                var_type,
+                default_value,
+                ..
+            } = &definition.node;
+
+            let value = match (self.variables.get(&name.node), default_value) {
+                (Some(value), _) => value.clone(),
+                (None, Some(default_value)) => default_value.node.clone(),
+                (None, None) if var_type.node.nullable => ConstValue::Null,
+                (None, None) => {
+                    return Err(GraphqlError::MissingVariable(name.to_string()))
                 }
+            };
+
+            if !is_valid_variable_value(&var_type.node, &value) {
+                return Err(GraphqlError::InvalidVariable(
+                    name.to_string(),
+                    var_type.to_string(),
+                ));
             }
+
+            values.insert(name.to_string(), value);
         }
 
-        Ok(operations)
+        Ok(values)
     }
 
-    fn process_fragments(&self) -> GraphqlResult<HashMap<String, Fragment>> {
+    fn process_fragments(
+        &self,
+        variables: &VariableValues,
+    ) -> GraphqlResult<HashMap<String, Fragment>> {
         let mut fragments = HashMap::new();
         let mut to_resolve = Vec::new();
 
@@ -639,7 +1463,12 @@ impl<'a> GraphqlQueryBuilder<'a> {
                 return Err(GraphqlError::UnrecognizedType(cond.to_string()));
             }
 
-            let frag = Fragment::new(self.schema, cond.to_string(), &selection_set.node)?;
+            let frag = Fragment::new(
+                self.schema,
+                cond.to_string(),
+                &selection_set.node,
+                variables,
+            )?;
 
             if frag.has_fragments() {
                 to_resolve.push((name.to_string(), frag));
@@ -675,6 +1504,75 @@ impl<'a> GraphqlQueryBuilder<'a> {
     }
 }
 
+/// Replace the variables in a value from a query document with their values.
+fn resolve_variables(value: Value, variables: &VariableValues) -> GraphqlResult<Value> {
+    value
+        .into_const_with(|name| {
+            variables
+                .get(name.as_str())
+                .cloned()
+                .ok_or_else(|| GraphqlError::UndefinedVariable(name.to_string()))
+        })
+        .map(ConstValue::into_value)
+}
+
+/// Whether a value can be used for a variable of the given type.
+///
+/// Values for custom scalars, enums and input objects are checked when the
+/// arguments in which the variable is used are parsed.
+fn is_valid_variable_value(ty: &Type, value: &ConstValue) -> bool {
+    match (&ty.base, value) {
+        (_, ConstValue::Null) => ty.nullable,
+        (BaseType::List(inner), ConstValue::List(values)) => values
+            .iter()
+            .all(|value| is_valid_variable_value(inner, value)),
+        (BaseType::List(inner), value) => is_valid_variable_value(inner, value),
+        (BaseType::Named(name), value) => match (name.as_str(), value) {
+            ("Int", ConstValue::Number(n)) => {
+                n.as_i64().map_or(false, |n| i32::try_from(n).is_ok())
+            }
+            ("Float", ConstValue::Number(_)) => true,
+            ("String", ConstValue::String(_)) => true,
+            ("Boolean", ConstValue::Boolean(_)) => true,
+            ("Int" | "Float" | "String" | "Boolean", _) => false,
+            _ => true,
+        },
+    }
+}
+
+/// Whether a selection is included in a query, according to its `@skip` and
+/// `@include` directives.
+fn is_included(
+    directives: &[Positioned<Directive>],
+    variables: &VariableValues,
+) -> GraphqlResult<bool> {
+    for directive in directives {
+        let skip = match directive.node.name.node.as_str() {
+            "skip" => true,
+            "include" => false,
+            _ => continue,
+        };
+
+        let condition = directive
+            .node
+            .arguments
+            .iter()
+            .find(|(name, _)| name.node == "if")
+            .map(|(_, value)| value.node.clone())
+            .ok_or_else(|| {
+                GraphqlError::MissingDirectiveCondition(directive.node.name.to_string())
+            })?;
+
+        match resolve_variables(condition, variables)? {
+            Value::Boolean(condition) if condition == skip => return Ok(false),
+            Value::Boolean(_) => {}
+            other => return Err(GraphqlError::UnsupportedValueType(other.to_string())),
+        }
+    }
+
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -751,6 +1649,7 @@ mod tests {
                 has_fragments: false,
                 selections: query_selections,
             },
+            stringify_large_integers: false,
         };
 
         let schema = r#"
@@ -839,7 +1738,652 @@ type Tx @entity {
             entity_name: "tx".to_string(),
             query_params: QueryParams::default(),
             alias: None,
+            members: Vec::new(),
+            nested: Vec::new(),
         }];
         assert_eq!(expected, operation.parse(&schema));
     }
+
+    #[test]
+    fn test_unions_are_queried_through_the_tables_of_their_members() {
+        let schema = r#"
+type Safe @entity {
+    id: ID!
+    balance: U64!
+}
+
+type Vault @entity {
+    id: ID!
+    label: String!
+}
+
+union Storage = Safe | Vault
+
+type Owner @entity {
+    id: ID!
+    storage: Storage!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query {
+            storage {
+                id
+                __typename
+                ... on Safe { balance }
+                ...VaultFields
+            }
+        }
+
+        fragment VaultFields on Vault {
+            id
+            label
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "(SELECT json_build_object('id', fuel_indexer_test_test_index.safe.id, '__typename', 'Safe', 'balance', fuel_indexer_test_test_index.safe.balance) FROM fuel_indexer_test_test_index.safe   ) UNION ALL (SELECT json_build_object('id', fuel_indexer_test_test_index.vault.id, '__typename', 'Vault', 'label', fuel_indexer_test_test_index.vault.label) FROM fuel_indexer_test_test_index.vault   )";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        // Fragments must be on the union or one of its members.
+        let query = r#"query {
+            storage {
+                ... on Owner { id }
+            }
+        }"#;
+
+        assert!(matches!(
+            GraphqlQueryBuilder::new(&schema, query).unwrap().build(),
+            Err(GraphqlError::InvalidFragmentSelection(_, _))
+        ));
+
+        // The member type of a nested union entity is not known.
+        let query = r#"query {
+            owner {
+                storage {
+                    ... on Safe { balance }
+                }
+            }
+        }"#;
+
+        assert!(matches!(
+            GraphqlQueryBuilder::new(&schema, query).unwrap().build(),
+            Err(GraphqlError::InvalidFragmentSelection(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_interfaces_are_queried_through_the_tables_of_their_implementors() {
+        let schema = r#"
+interface Named {
+    id: ID!
+    name: String!
+}
+
+type Safe implements Named @entity {
+    id: ID!
+    name: String!
+    balance: U64!
+}
+
+type Vault implements Named @entity {
+    id: ID!
+    name: String!
+    label: String!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query {
+            named(filter: { name: { equals: "main" } }) {
+                kind: __typename
+                name
+                ... on Safe { balance }
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "(SELECT json_build_object('kind', 'Safe', 'name', fuel_indexer_test_test_index.safe.name, 'balance', fuel_indexer_test_test_index.safe.balance) FROM fuel_indexer_test_test_index.safe  WHERE  fuel_indexer_test_test_index.safe.name = 'main' ) UNION ALL (SELECT json_build_object('kind', 'Vault', 'name', fuel_indexer_test_test_index.vault.name) FROM fuel_indexer_test_test_index.vault  WHERE  fuel_indexer_test_test_index.vault.name = 'main' )";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        // Union and interface entities are combined from several tables, and so
+        // can not be paginated.
+        let query = r#"query {
+            named(first: 10, order: { name: asc }) {
+                name
+            }
+        }"#;
+
+        assert!(matches!(
+            GraphqlQueryBuilder::new(&schema, query)
+                .unwrap()
+                .build()
+                .unwrap()
+                .as_sql(&schema, DbType::Postgres),
+            Err(GraphqlError::PaginatedAbstractTypeQuery(_))
+        ));
+    }
+
+    #[test]
+    fn test_256_bit_integers_are_filtered_as_numbers_and_returned_as_strings() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    balance: U256!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query {
+            account(filter: { balance: { gt: "340282366920938463463374607431768211456" } }) {
+                id
+                balance
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "SELECT json_build_object('id', fuel_indexer_test_test_index.account.id, 'balance', fuel_indexer_test_test_index.account.balance::text) FROM fuel_indexer_test_test_index.account  WHERE  fuel_indexer_test_test_index.account.balance > 340282366920938463463374607431768211456 ";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        let query = r#"query {
+            account(filter: { balance: { gt: "1; DROP TABLE account" } }) {
+                id
+            }
+        }"#;
+
+        assert!(GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_large_integers_are_filtered_losslessly_and_optionally_returned_as_strings() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    nonce: U64!
+    delta: I64!
+    balance: U128!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query {
+            account(filter: { delta: { lt: -9223372036854775808 }, and: { balance: { gt: "340282366920938463463374607431768211455" } } }) {
+                nonce
+                delta
+                balance
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "SELECT json_build_object('nonce', fuel_indexer_test_test_index.account.nonce, 'delta', fuel_indexer_test_test_index.account.delta, 'balance', fuel_indexer_test_test_index.account.balance) FROM fuel_indexer_test_test_index.account  WHERE  (fuel_indexer_test_test_index.account.delta < -9223372036854775808 AND fuel_indexer_test_test_index.account.balance > 340282366920938463463374607431768211455) ";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .stringify_large_integers(true)
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "SELECT json_build_object('nonce', fuel_indexer_test_test_index.account.nonce::text, 'delta', fuel_indexer_test_test_index.account.delta::text, 'balance', fuel_indexer_test_test_index.account.balance::text) FROM fuel_indexer_test_test_index.account  WHERE  (fuel_indexer_test_test_index.account.delta < -9223372036854775808 AND fuel_indexer_test_test_index.account.balance > 340282366920938463463374607431768211455) ";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        let query = r#"query {
+            account(filter: { nonce: { gt: 18446744073709551616 } }) {
+                id
+            }
+        }"#;
+
+        assert!(GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_variables_and_directives_are_resolved_before_building_queries() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    name: String!
+    nonce: U64!
+    delta: I64!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query Accounts($name: String = "main", $minNonce: U64!, $withDelta: Boolean!) {
+            account(filter: { name: { equals: $name }, and: { nonce: { gt: $minNonce } } }) {
+                id
+                delta @include(if: $withDelta)
+                nonce @skip(if: true)
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .variables(Variables::from_json(
+                serde_json::json!({ "minNonce": 10, "withDelta": false }),
+            ))
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "SELECT json_build_object('id', fuel_indexer_test_test_index.account.id) FROM fuel_indexer_test_test_index.account  WHERE  (fuel_indexer_test_test_index.account.name = 'main' AND fuel_indexer_test_test_index.account.nonce > 10) ";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .variables(Variables::from_json(
+                serde_json::json!({ "name": "other", "minNonce": 10, "withDelta": true }),
+            ))
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "SELECT json_build_object('id', fuel_indexer_test_test_index.account.id, 'delta', fuel_indexer_test_test_index.account.delta) FROM fuel_indexer_test_test_index.account  WHERE  (fuel_indexer_test_test_index.account.name = 'other' AND fuel_indexer_test_test_index.account.nonce > 10) ";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        // Required variables must be provided, and values must match their types.
+        for value in [
+            serde_json::json!({ "withDelta": true }),
+            serde_json::json!({ "minNonce": 10, "withDelta": "yes" }),
+        ] {
+            assert!(GraphqlQueryBuilder::new(&schema, query)
+                .unwrap()
+                .variables(Variables::from_json(value))
+                .build()
+                .is_err());
+        }
+
+        // Variables must be defined by the operation that uses them.
+        let query = r#"query {
+            account(filter: { name: { equals: $name } }) {
+                id
+            }
+        }"#;
+
+        assert!(GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .variables(Variables::from_json(serde_json::json!({ "name": "main" })))
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_the_operation_named_by_the_request_is_run() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    name: String!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"
+        query Main {
+            account(filter: { name: { equals: "main" } }) {
+                id
+            }
+        }
+
+        query Other {
+            account(filter: { name: { equals: "other" } }) {
+                id
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .operation_name(Some("Other".to_string()))
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        assert_eq!(queries.len(), 1);
+        assert!(
+            queries[0].ends_with("fuel_indexer_test_test_index.account.name = 'other' ")
+        );
+
+        for operation_name in [None, Some("Missing".to_string())] {
+            assert!(GraphqlQueryBuilder::new(&schema, query)
+                .unwrap()
+                .operation_name(operation_name)
+                .build()
+                .is_err());
+        }
+
+        // The only operation of a document is run without being named.
+        let query = r#"query Main {
+            account {
+                id
+            }
+        }"#;
+
+        assert!(GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .is_ok());
+        assert!(GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .operation_name(Some("Main".to_string()))
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_int_variables_are_32_bit() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    name: String!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query Accounts($first: Int!) {
+            account(first: $first, order: { name: asc }) {
+                id
+            }
+        }"#;
+
+        let build = |first: serde_json::Value| {
+            GraphqlQueryBuilder::new(&schema, query)
+                .unwrap()
+                .variables(Variables::from_json(serde_json::json!({ "first": first })))
+                .build()
+        };
+
+        assert!(build(serde_json::json!(i32::MAX)).is_ok());
+        assert!(build(serde_json::json!(i32::MIN)).is_ok());
+        assert!(build(serde_json::json!(i64::from(i32::MAX) + 1)).is_err());
+        assert!(build(serde_json::json!(i64::from(i32::MIN) - 1)).is_err());
+        assert!(build(serde_json::json!(1.5)).is_err());
+    }
+
+    #[test]
+    fn test_lists_of_related_entities_are_filtered_ordered_and_paginated() {
+        let schema = r#"
+type Transfer @entity {
+    id: ID!
+    amount: U64!
+    height: U64!
+}
+
+type Account @entity {
+    id: ID!
+    name: String!
+    transfers: [Transfer!]!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query {
+            account(filter: { name: { equals: "main" } }) {
+                id
+                transfers(filter: { amount: { gt: 100 } }, order: { height: desc }, first: 10, offset: 5) {
+                    id
+                    amount
+                }
+                everything: transfers {
+                    id
+                }
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "SELECT json_build_object('id', fuel_indexer_test_test_index.account.id, 'transfers', account_transfers_0.items, 'everything', account_everything_1.items) FROM fuel_indexer_test_test_index.account LEFT JOIN LATERAL (SELECT COALESCE(json_agg(item ORDER BY position), '[]') AS items FROM (SELECT json_build_object('id', fuel_indexer_test_test_index.transfer.id, 'amount', fuel_indexer_test_test_index.transfer.amount) AS item, row_number() OVER (ORDER BY fuel_indexer_test_test_index.transfer.height DESC) AS position FROM fuel_indexer_test_test_index.transfer INNER JOIN fuel_indexer_test_test_index.accounts_transfers ON fuel_indexer_test_test_index.accounts_transfers.transfer_id = fuel_indexer_test_test_index.transfer.id WHERE fuel_indexer_test_test_index.accounts_transfers.account_id = fuel_indexer_test_test_index.account.id AND fuel_indexer_test_test_index.transfer.amount > 100) AS items WHERE position > 5 AND position <= 15) AS account_transfers_0 ON true LEFT JOIN LATERAL (SELECT COALESCE(json_agg(item ORDER BY position), '[]') AS items FROM (SELECT json_build_object('id', fuel_indexer_test_test_index.transfer.id) AS item, row_number() OVER () AS position FROM fuel_indexer_test_test_index.transfer INNER JOIN fuel_indexer_test_test_index.accounts_transfers ON fuel_indexer_test_test_index.accounts_transfers.transfer_id = fuel_indexer_test_test_index.transfer.id WHERE fuel_indexer_test_test_index.accounts_transfers.account_id = fuel_indexer_test_test_index.account.id) AS items) AS account_everything_1 ON true WHERE  fuel_indexer_test_test_index.account.name = 'main' ";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        // As with top-level entities, lists can only be paginated once ordered.
+        let query = r#"query {
+            account {
+                transfers(first: 10) {
+                    id
+                }
+            }
+        }"#;
+
+        assert!(GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .is_err());
+    }
+
+    #[test]
+    fn test_derived_lists_are_selected_through_the_foreign_keys_of_their_entities() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    transfers: [Transfer!]! @derivedFrom(field: "owner")
+}
+
+type Transfer @entity {
+    id: ID!
+    owner: Account!
+    amount: U64!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query {
+            account {
+                id
+                transfers(filter: { amount: { gt: 100 } }) {
+                    amount
+                }
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "SELECT json_build_object('id', fuel_indexer_test_test_index.account.id, 'transfers', account_transfers_0.items) FROM fuel_indexer_test_test_index.account LEFT JOIN LATERAL (SELECT COALESCE(json_agg(item ORDER BY position), '[]') AS items FROM (SELECT json_build_object('amount', fuel_indexer_test_test_index.transfer.amount) AS item, row_number() OVER () AS position FROM fuel_indexer_test_test_index.transfer  WHERE fuel_indexer_test_test_index.transfer.owner = fuel_indexer_test_test_index.account.id AND fuel_indexer_test_test_index.transfer.amount > 100) AS items) AS account_transfers_0 ON true  ";
+        assert_eq!(queries, vec![expected.to_string()]);
+    }
+
+    #[test]
+    fn test_indexer_metadata_is_queried_alongside_entities() {
+        let schema = r#"
+type Transfer @entity {
+    id: ID!
+    amount: U64!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query {
+            transfer {
+                id
+            }
+            meta: _meta {
+                block {
+                    height
+                    time
+                }
+                indexer {
+                    status
+                    deployedAt
+                }
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .stringify_large_integers(true)
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = vec![
+            "SELECT json_build_object('id', fuel_indexer_test_test_index.transfer.id) FROM fuel_indexer_test_test_index.transfer   ".to_string(),
+            "SELECT json_build_object('meta', json_build_object('block', (SELECT json_build_object('height', block_height, 'time', time::text) FROM fuel_indexer_test_test_index.indexmetadataentity ORDER BY block_height DESC LIMIT 1), 'indexer', (SELECT json_build_object('status', COALESCE(index_status.status, 'unknown'), 'deployedAt', to_char(index_registry.created_at, 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')) FROM index_registry LEFT JOIN index_status ON index_status.indexer_id = index_registry.id WHERE index_registry.namespace = 'fuel_indexer_test' AND index_registry.identifier = 'test_index')))".to_string(),
+        ];
+        assert_eq!(queries, expected);
+
+        let query = r#"query { _meta { block { hash } } }"#;
+        assert!(matches!(
+            GraphqlQueryBuilder::new(&schema, query).unwrap().build(),
+            Err(GraphqlError::UnrecognizedField(typ, field)) if typ == "_Block" && field == "hash"
+        ));
+    }
+
+    #[test]
+    fn test_timestamps_are_filtered_by_time_and_returned_as_rfc_3339() {
+        let schema = r#"
+type Transfer @entity {
+    id: ID!
+    time: Timestamp!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+        )
+        .unwrap();
+
+        let query = r#"query {
+            transfer(filter: { time: { between: { min: "2023-11-14", max: "2023-11-14T23:13:20+01:00" } } }) {
+                id
+                time
+            }
+        }"#;
+
+        let queries = GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+
+        let expected = "SELECT json_build_object('id', fuel_indexer_test_test_index.transfer.id, 'time', to_char(fuel_indexer_test_test_index.transfer.time AT TIME ZONE 'UTC', 'YYYY-MM-DD\"T\"HH24:MI:SS\"Z\"')) FROM fuel_indexer_test_test_index.transfer  WHERE  fuel_indexer_test_test_index.transfer.time BETWEEN '2023-11-14T00:00:00Z' AND '2023-11-14T22:13:20Z' ";
+        assert_eq!(queries, vec![expected.to_string()]);
+
+        let query = r#"query {
+            transfer(filter: { time: { gt: "yesterday" } }) {
+                id
+            }
+        }"#;
+
+        assert!(GraphqlQueryBuilder::new(&schema, query)
+            .unwrap()
+            .build()
+            .is_err());
+    }
 }
@@ -1,7 +1,11 @@
 use super::{arguments::QueryParams, graphql::GraphqlError};
 use fuel_indexer_database::DbType;
+use fuel_indexer_lib::graphql::types::HistoryCol;
 
-use std::{collections::HashMap, fmt::Display};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Display,
+};
 
 /// Represents a part of a user query. Each part can be a key-value pair
 /// describing an entity field and its corresponding database table, or a
@@ -44,6 +48,91 @@ pub struct QueryJoinNode {
     pub dependents: HashMap<String, JoinCondition>,
 }
 
+/// Represents a list of entities that are related to the entity of a query, and
+/// which are selected with their own filtering, ordering and pagination.
+///
+/// The list is selected in a lateral join of the parent query, in which each
+/// entity is numbered with a window function so that the requested page of the
+/// list can be aggregated in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NestedQuery {
+    /// Name of the lateral join in the parent query.
+    pub alias: String,
+
+    /// Join of the table through which the listed entities are related to the
+    /// parent entity, if any.
+    pub join: Option<JoinCondition>,
+
+    /// Condition relating the listed entities (or the joined table) to the parent entity.
+    pub condition: JoinCondition,
+
+    /// Query for the listed entities.
+    pub query: UserQuery,
+}
+
+impl NestedQuery {
+    /// Returns the lateral join in which the list is selected, as a column named `items`.
+    pub fn to_sql(&mut self, db_type: &DbType) -> Result<String, GraphqlError> {
+        match db_type {
+            DbType::Postgres => {
+                let NestedQuery {
+                    alias,
+                    join,
+                    condition,
+                    query,
+                } = self;
+
+                if query.query_params.limit.is_some()
+                    && query.query_params.sorts.is_empty()
+                {
+                    return Err(GraphqlError::UnorderedPaginatedQuery);
+                }
+
+                let selections_str =
+                    query.parse_query_elements_into_selections(db_type).join("");
+
+                let joins_str = join
+                    .iter()
+                    .map(|join| {
+                        format!("INNER JOIN {} ON {join}", join.referencing_key_table)
+                    })
+                    .chain(Some(query.get_joins(db_type)?))
+                    .filter(|join| !join.is_empty())
+                    .collect::<Vec<String>>()
+                    .join(" ");
+
+                let conditions = std::iter::once(condition.to_string())
+                    .chain(query.query_params.filters.iter().map(|f| f.to_sql(db_type)))
+                    .collect::<Vec<String>>()
+                    .join(" AND ");
+
+                let ordering = query.query_params.get_ordering_modififer(db_type);
+
+                let offset = query.query_params.offset.unwrap_or(0);
+                let page = match query.query_params.limit {
+                    Some(limit) => format!(
+                        " WHERE position > {offset} AND position <= {}",
+                        offset.saturating_add(limit)
+                    ),
+                    None if offset > 0 => format!(" WHERE position > {offset}"),
+                    None => "".to_string(),
+                };
+
+                Ok(format!(
+                    "LEFT JOIN LATERAL (SELECT COALESCE(json_agg(item ORDER BY position), '[]') AS items FROM (SELECT json_build_object({}) AS item, row_number() OVER ({}) AS position FROM {} {} WHERE {}) AS items{}) AS {} ON true",
+                    selections_str,
+                    ordering.trim(),
+                    query.get_from_table(),
+                    joins_str,
+                    conditions,
+                    page,
+                    alias,
+                ))
+            }
+        }
+    }
+}
+
 /// Represents the full amount of requested information from a user query.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct UserQuery {
@@ -64,11 +153,22 @@ pub struct UserQuery {
 
     // An optional user-suppled alias for an entity field.
     pub alias: Option<String>,
+
+    /// Queries for the possible types of a union or interface entity, whose
+    /// results are combined into the results of this query.
+    pub members: Vec<UserQuery>,
+
+    /// Lists of related entities that are selected with their own arguments.
+    pub nested: Vec<NestedQuery>,
 }
 
 impl UserQuery {
     /// Returns the query as a database-specific SQL query.
     pub fn to_sql(&mut self, db_type: &DbType) -> Result<String, GraphqlError> {
+        if !self.members.is_empty() {
+            return self.members_to_sql(db_type);
+        }
+
         // Different database solutions have unique ways of
         // constructing JSON-formatted queries and results.
         match db_type {
@@ -77,36 +177,7 @@ impl UserQuery {
 
                 let selections_str = selections.join("");
 
-                let sorted_joins = self.get_topologically_sorted_joins();
-
-                let mut last_seen_primary_key_table = "".to_string();
-                let mut joins: Vec<String> = Vec::new();
-
-                // For each clause in the list of topologically-sorted joins,
-                // check if the clause's primary key table matches the last primary key
-                // key table that was seen in this loop. If so, add the join condition to
-                // the last join condition; if not, push this clause into the list of joins.
-                // This is required because Postgres does not allow for joined primary key tables
-                // to be mentioned multiple times.
-                for sj in sorted_joins {
-                    if sj.primary_key_table == last_seen_primary_key_table {
-                        if let Some(elem) = joins.last_mut() {
-                            *elem = format!("{elem} AND {sj}")
-                        }
-                    } else {
-                        joins.push(format!(
-                            "INNER JOIN {} ON {}",
-                            sj.primary_key_table, sj
-                        ));
-                        last_seen_primary_key_table = sj.primary_key_table;
-                    }
-                }
-
-                let joins_str = if !joins.is_empty() {
-                    joins.join(" ")
-                } else {
-                    "".to_string()
-                };
+                let joins_str = self.get_joins(db_type)?;
 
                 // If there's a limit applied to the query, then we need to create a query
                 // with pagination info. Otherwise, we can return the entire result set.
@@ -123,11 +194,18 @@ impl UserQuery {
                         return Err(GraphqlError::UnorderedPaginatedQuery);
                     }
                 } else {
+                    let history_ctes = self.get_history_ctes();
+                    let with_clause = if history_ctes.is_empty() {
+                        "".to_string()
+                    } else {
+                        format!("WITH {} ", history_ctes.join(", "))
+                    };
+
                     format!(
-                        "SELECT json_build_object({}) FROM {}.{} {} {} {}",
+                        "{}SELECT json_build_object({}) FROM {} {} {} {}",
+                        with_clause,
                         selections_str,
-                        self.namespace_identifier,
-                        self.entity_name,
+                        self.get_from_table(),
                         joins_str,
                         self.query_params.get_filtering_expression(db_type),
                         self.query_params.get_ordering_modififer(db_type)
@@ -139,6 +217,102 @@ impl UserQuery {
         }
     }
 
+    /// Returns the queries for the possible types of a union or interface entity,
+    /// combined into a single query.
+    fn members_to_sql(&mut self, db_type: &DbType) -> Result<String, GraphqlError> {
+        if self.members.iter().any(|m| m.query_params.limit.is_some()) {
+            return Err(GraphqlError::PaginatedAbstractTypeQuery(
+                self.entity_name.clone(),
+            ));
+        }
+
+        let queries = self
+            .members
+            .iter_mut()
+            .map(|member| member.to_sql(db_type).map(|query| format!("({query})")))
+            .collect::<Result<Vec<String>, GraphqlError>>()?;
+
+        Ok(queries.join(" UNION ALL "))
+    }
+
+    /// Returns the table from which the top-level entity is selected.
+    ///
+    /// When the query is made at a block height, the table is a common table
+    /// expression over the entity's history table.
+    fn get_from_table(&self) -> String {
+        if self.query_params.block_height.is_some() {
+            self.entity_name.clone()
+        } else {
+            format!("{}.{}", self.namespace_identifier, self.entity_name)
+        }
+    }
+
+    /// Returns the common table expressions that select, from their history
+    /// tables, the versions of the entities in the query that were current at
+    /// the requested block height.
+    fn get_history_ctes(&self) -> Vec<String> {
+        match self.query_params.block_height {
+            Some(height) => {
+                let start = HistoryCol::block_height_start();
+                let end = HistoryCol::block_height_end();
+
+                self.tables()
+                    .into_iter()
+                    .map(|table| {
+                        format!(
+                            "{table} AS (SELECT * FROM {}.{} WHERE {start} <= {height} AND ({end} IS NULL OR {end} > {height}))",
+                            self.namespace_identifier,
+                            HistoryCol::table_name(table),
+                        )
+                    })
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// Returns the names of the tables referenced by the query.
+    pub(crate) fn tables(&self) -> BTreeSet<&String> {
+        std::iter::once(&self.entity_name)
+            .chain(self.joins.keys())
+            .chain(self.nested.iter().flat_map(|nested| nested.query.tables()))
+            .collect()
+    }
+
+    /// Returns the joins of the query, in the order in which they can be applied.
+    ///
+    /// Lists of related entities are joined laterally after all other tables,
+    /// so that they can refer to any of them.
+    fn get_joins(&mut self, db_type: &DbType) -> Result<String, GraphqlError> {
+        let sorted_joins = self.get_topologically_sorted_joins();
+
+        let mut last_seen_primary_key_table = "".to_string();
+        let mut joins: Vec<String> = Vec::new();
+
+        // For each clause in the list of topologically-sorted joins,
+        // check if the clause's primary key table matches the last primary key
+        // key table that was seen in this loop. If so, add the join condition to
+        // the last join condition; if not, push this clause into the list of joins.
+        // This is required because Postgres does not allow for joined primary key tables
+        // to be mentioned multiple times.
+        for sj in sorted_joins {
+            if sj.primary_key_table == last_seen_primary_key_table {
+                if let Some(elem) = joins.last_mut() {
+                    *elem = format!("{elem} AND {sj}")
+                }
+            } else {
+                joins.push(format!("INNER JOIN {} ON {}", sj.primary_key_table, sj));
+                last_seen_primary_key_table = sj.primary_key_table;
+            }
+        }
+
+        for nested in self.nested.iter_mut() {
+            joins.push(nested.to_sql(db_type)?);
+        }
+
+        Ok(joins.join(" "))
+    }
+
     /// Returns a SQL query that contains the requested results and a PageInfo object.
     fn create_query_with_pageinfo(
         &self,
@@ -158,17 +332,23 @@ impl UserQuery {
                 let json_selections_str =
                     self.get_json_selections_from_cte(db_type).join(",");
 
+                let history_ctes = self
+                    .get_history_ctes()
+                    .into_iter()
+                    .map(|cte| format!("{cte},\n"))
+                    .collect::<String>();
+
                 let selection_cte = format!(
-                    r#"WITH selection_cte AS (
+                    r#"WITH {}selection_cte AS (
                         SELECT json_build_object({}) AS {}
-                        FROM {}.{}
+                        FROM {}
                         {}
                         {}
                         {}),"#,
+                    history_ctes,
                     selections_str,
                     self.entity_name,
-                    self.namespace_identifier,
-                    self.entity_name,
+                    self.get_from_table(),
                     joins_str,
                     self.query_params.get_filtering_expression(db_type),
                     self.query_params.get_ordering_modififer(db_type),
@@ -383,6 +563,8 @@ mod tests {
             entity_name: "".to_string(),
             query_params: QueryParams::default(),
             alias: None,
+            members: Vec::new(),
+            nested: Vec::new(),
         };
 
         let expected = vec![
@@ -464,12 +646,47 @@ mod tests {
                 sorts: vec![],
                 offset: None,
                 limit: None,
+                block_height: None,
             },
             alias: None,
+            members: Vec::new(),
+            nested: Vec::new(),
         };
 
         let expected = "SELECT json_build_object('hash', name_ident.block.hash, 'tx', json_build_object('hash', name_ident.tx.hash), 'height', name_ident.block.height) FROM name_ident.entity_name INNER JOIN name_ident.block ON name_ident.tx.block = name_ident.block.id WHERE  name_ident.entity_name.id = 1 "
             .to_string();
         assert_eq!(expected, uq.to_sql(&DbType::Postgres).unwrap());
     }
+
+    #[test]
+    fn test_user_query_to_sql_at_block_height() {
+        let elements = vec![
+            QueryElement::Field {
+                key: "id".to_string(),
+                value: "balance.id".to_string(),
+            },
+            QueryElement::Field {
+                key: "amount".to_string(),
+                value: "balance.amount".to_string(),
+            },
+        ];
+
+        let mut uq = UserQuery {
+            elements,
+            joins: HashMap::new(),
+            namespace_identifier: "name_ident".to_string(),
+            entity_name: "balance".to_string(),
+            query_params: QueryParams {
+                block_height: Some(100),
+                ..QueryParams::default()
+            },
+            alias: None,
+            members: Vec::new(),
+            nested: Vec::new(),
+        };
+
+        let expected = "WITH balance AS (SELECT * FROM name_ident.balance_history WHERE block_height_start <= 100 AND (block_height_end IS NULL OR block_height_end > 100)) SELECT json_build_object('id', balance.id, 'amount', balance.amount) FROM balance   "
+            .to_string();
+        assert_eq!(expected, uq.to_sql(&DbType::Postgres).unwrap());
+    }
 }
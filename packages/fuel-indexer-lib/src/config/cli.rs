@@ -193,10 +193,33 @@ pub struct IndexerArgs {
     #[clap(long, help = "Allow the web server to accept raw SQL queries.")]
     pub accept_sql_queries: bool,
 
+    /// Emit 64-bit and 128-bit integers as strings in GraphQL responses, so that
+    /// clients can read them without loss of precision.
+    #[clap(
+        long,
+        help = "Emit 64-bit and 128-bit integers as strings in GraphQL responses."
+    )]
+    pub stringify_large_integers: bool,
+
     /// Amount of blocks to return in a request to a Fuel node.
     #[clap(long, help = "Amount of blocks to return in a request to a Fuel node.", default_value_t = defaults::NODE_BLOCK_PAGE_SIZE)]
     pub block_page_size: usize,
 
+    /// Size (MB) of the block cache shared by indexers using the same Fuel node.
+    #[clap(
+        long,
+        help = "Size (MB) of the block cache shared by indexers using the same Fuel node."
+    )]
+    pub block_cache_size: Option<usize>,
+
+    /// Number of log lines to keep for each indexer. Set to 0 to disable persisting logs.
+    #[clap(
+        long,
+        help = "Number of log lines to keep for each indexer. Set to 0 to disable persisting logs.",
+        default_value_t = defaults::INDEXER_LOG_LINES
+    )]
+    pub indexer_log_lines: usize,
+
     /// Allow missing blocks or non-sequential block processing.
     #[clap(
         long,
@@ -350,6 +373,14 @@ pub struct ApiServerArgs {
     /// Allow the web server to accept raw SQL queries.
     #[clap(long, help = "Allow the web server to accept raw SQL queries.")]
     pub accept_sql_queries: bool,
+
+    /// Emit 64-bit and 128-bit integers as strings in GraphQL responses, so that
+    /// clients can read them without loss of precision.
+    #[clap(
+        long,
+        help = "Emit 64-bit and 128-bit integers as strings in GraphQL responses."
+    )]
+    pub stringify_large_integers: bool,
     /// By default, Fuel Indexer will only accept WASM indexer modules compiled with the same toolchain version as the version of Fuel Indexer.
     #[clap(
         long,
@@ -107,7 +107,10 @@ impl Default for IndexerArgs {
             replace_indexer: defaults::REPLACE_INDEXER,
             remove_data: defaults::REMOVE_DATA,
             accept_sql_queries: defaults::ACCEPT_SQL,
+            stringify_large_integers: defaults::STRINGIFY_LARGE_INTEGERS,
             block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            block_cache_size: None,
+            indexer_log_lines: defaults::INDEXER_LOG_LINES,
             allow_non_sequential_blocks: defaults::ALLOW_NON_SEQUENTIAL_BLOCKS,
             disable_toolchain_version_check: defaults::DISABLE_TOOLCHAIN_VERSION_CHECK,
             client_request_delay: None,
@@ -141,7 +144,11 @@ pub struct IndexerConfig {
     pub rate_limit: RateLimitConfig,
     pub replace_indexer: bool,
     pub accept_sql_queries: bool,
+    #[serde(default)]
+    pub stringify_large_integers: bool,
     pub block_page_size: usize,
+    pub block_cache_size: Option<usize>,
+    pub indexer_log_lines: usize,
     pub allow_non_sequential_blocks: bool,
     pub disable_toolchain_version_check: bool,
     pub client_request_delay: Option<u64>,
@@ -167,7 +174,10 @@ impl Default for IndexerConfig {
             rate_limit: RateLimitConfig::default(),
             replace_indexer: defaults::REPLACE_INDEXER,
             accept_sql_queries: defaults::ACCEPT_SQL,
+            stringify_large_integers: defaults::STRINGIFY_LARGE_INTEGERS,
             block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            block_cache_size: None,
+            indexer_log_lines: defaults::INDEXER_LOG_LINES,
             allow_non_sequential_blocks: defaults::ALLOW_NON_SEQUENTIAL_BLOCKS,
             disable_toolchain_version_check: defaults::DISABLE_TOOLCHAIN_VERSION_CHECK,
             client_request_delay: None,
@@ -253,7 +263,10 @@ impl From<IndexerArgs> for IndexerConfig {
             },
             replace_indexer: args.replace_indexer,
             accept_sql_queries: args.accept_sql_queries,
+            stringify_large_integers: args.stringify_large_integers,
             block_page_size: args.block_page_size,
+            block_cache_size: args.block_cache_size,
+            indexer_log_lines: args.indexer_log_lines,
             allow_non_sequential_blocks: args.allow_non_sequential_blocks,
             disable_toolchain_version_check: args.disable_toolchain_version_check,
             client_request_delay: args.client_request_delay,
@@ -353,7 +366,10 @@ impl From<ApiServerArgs> for IndexerConfig {
             },
             replace_indexer: defaults::REPLACE_INDEXER,
             accept_sql_queries: args.accept_sql_queries,
+            stringify_large_integers: args.stringify_large_integers,
             block_page_size: defaults::NODE_BLOCK_PAGE_SIZE,
+            block_cache_size: None,
+            indexer_log_lines: defaults::INDEXER_LOG_LINES,
             allow_non_sequential_blocks: defaults::ALLOW_NON_SEQUENTIAL_BLOCKS,
             disable_toolchain_version_check: args.disable_toolchain_version_check,
             client_request_delay: None,
@@ -401,8 +417,12 @@ impl IndexerConfig {
 
         let accept_sql_config_key =
             serde_yaml::Value::String("accept_sql_queries".into());
+        let stringify_large_integers_key =
+            serde_yaml::Value::String("stringify_large_integers".into());
 
         let block_page_size_key = serde_yaml::Value::String("block_page_size".into());
+        let block_cache_size_key = serde_yaml::Value::String("block_cache_size".into());
+        let indexer_log_lines_key = serde_yaml::Value::String("indexer_log_lines".into());
         let client_request_delay_key =
             serde_yaml::Value::String("client_request_delay".into());
 
@@ -410,6 +430,11 @@ impl IndexerConfig {
             config.accept_sql_queries = accept_sql_queries.as_bool().unwrap();
         }
 
+        if let Some(stringify_large_integers) = content.get(stringify_large_integers_key)
+        {
+            config.stringify_large_integers = stringify_large_integers.as_bool().unwrap();
+        }
+
         if let Some(replace_indexer) = content.get(replace_indexer_key) {
             config.replace_indexer = replace_indexer.as_bool().unwrap();
         }
@@ -450,6 +475,14 @@ impl IndexerConfig {
             config.block_page_size = block_page_size.as_u64().unwrap() as usize;
         }
 
+        if let Some(block_cache_size) = content.get(block_cache_size_key) {
+            config.block_cache_size = block_cache_size.as_u64().map(|x| x as usize);
+        }
+
+        if let Some(indexer_log_lines) = content.get(indexer_log_lines_key) {
+            config.indexer_log_lines = indexer_log_lines.as_u64().unwrap() as usize;
+        }
+
         if let Some(client_request_delay) = content.get(client_request_delay_key) {
             config.client_request_delay = client_request_delay.as_u64();
         }
@@ -106,6 +106,7 @@ lazy_static! {
         "TransferOut",
         "Mint",
         "Burn",
+        "UndecodedLog",
     ]);
 
     /// Type names that are not allowed in GraphQL schema.
@@ -122,6 +123,7 @@ lazy_static! {
         "Bytes8",
         "ContractId",
         "I128",
+        "I256",
         "I32",
         "I64",
         "I8",
@@ -129,11 +131,13 @@ lazy_static! {
         "Identity",
         "Json",
         "U128",
+        "U256",
         "U32",
         "U64",
         "U8",
         "UID",
         "String",
+        "Timestamp",
 
         // Imports for transaction fields.
         // https://github.com/FuelLabs/fuel-indexer/issues/286
@@ -172,12 +176,12 @@ lazy_static! {
         "TxFieldSalt",
         "TxFieldScript",
         "TxId",
+        "UndecodedLog",
         "Witnesses",
-    ]);
-
 
-    /// ABI types not allowed in the contract ABI.
-    pub static ref UNSUPPORTED_ABI_JSON_TYPES: HashSet<&'static str> = HashSet::from(["Vec"]);
+        // Handler context generated by the `#[indexer]` macro.
+        "Context",
+    ]);
 
     /// Generic Sway ABI types.
     pub static ref IGNORED_GENERIC_METADATA: HashSet<&'static str> = HashSet::from([
@@ -186,8 +190,6 @@ lazy_static! {
         "raw untyped ptr",
         "struct RawVec",
         "struct RawBytes",
-        "struct Bytes",
-        "enum Result"
     ]);
 
     pub static ref GENERIC_STRUCTS: HashSet<&'static str> = HashSet::from([
@@ -103,6 +103,12 @@ pub const VERBOSE_DB_LOGGING: &str = "false";
 /// Amount of blocks to return in a request to a Fuel node.
 pub const NODE_BLOCK_PAGE_SIZE: usize = 20;
 
+/// Number of log lines to keep for each indexer.
+pub const INDEXER_LOG_LINES: usize = 1000;
+
+/// Number of blocks indexed between two prunings of the tables with a retention policy.
+pub const RETENTION_PRUNE_INTERVAL: u32 = 1000;
+
 /// Start a local Fuel node.
 pub const LOCAL_FUEL_NODE: bool = false;
 
@@ -135,6 +141,9 @@ pub const REMOVE_DATA: bool = false;
 /// Allow the web server to accept raw SQL queries.
 pub const ACCEPT_SQL: bool = false;
 
+/// Emit 64-bit and 128-bit integers as strings in GraphQL responses.
+pub const STRINGIFY_LARGE_INTEGERS: bool = false;
+
 /// Allow missing blocks or non-sequential block processing.
 pub const ALLOW_NON_SEQUENTIAL_BLOCKS: bool = false;
 
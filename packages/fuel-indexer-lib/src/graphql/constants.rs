@@ -50,6 +50,22 @@ lazy_static! {
     ]);
 
 
+    /// Set of numeric types that do not fit into JSON numbers, and so are passed
+    /// to and returned from GraphQL queries as decimal strings.
+    pub static ref STRING_ENCODED_NUMERIC_TYPES: HashSet<&'static str> = HashSet::from([
+        "I256",
+        "U256",
+    ]);
+
+    /// Set of integer types that may exceed the precision of JSON numbers
+    /// (2^53), and so can optionally be returned from GraphQL queries as strings.
+    pub static ref LARGE_INTEGER_TYPES: HashSet<&'static str> = HashSet::from([
+        "I64",
+        "U64",
+        "I128",
+        "U128",
+    ]);
+
     /// Set of types that should be copied instead of referenced.
     pub static ref COPY_TYPES: HashSet<&'static str> = HashSet::from([
         "Bytes",
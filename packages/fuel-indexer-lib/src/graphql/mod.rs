@@ -3,7 +3,7 @@ pub mod parser;
 pub mod types;
 pub mod validator;
 
-use async_graphql_value::Name;
+use async_graphql_value::{ConstValue, Name};
 pub use parser::{JoinTableMeta, ParsedError, ParsedGraphQLSchema};
 pub use validator::GraphQLSchemaValidator;
 
@@ -500,6 +500,24 @@ pub fn extract_foreign_key_info(
     (ref_coltype, ref_colname, ref_tablename)
 }
 
+/// Return the name of the `FieldDefinition` from which a given `FieldDefinition` is
+/// derived, if it has a `@derivedFrom` directive.
+pub fn derived_from_field_name(f: &FieldDefinition) -> Option<String> {
+    f.directives
+        .iter()
+        .find(|d| d.node.name.node == "derivedFrom")
+        .and_then(|d| {
+            d.node
+                .arguments
+                .iter()
+                .find(|(name, _)| name.node == "field")
+                .and_then(|(_, value)| match &value.node {
+                    ConstValue::String(s) => Some(s.to_string()),
+                    _ => None,
+                })
+        })
+}
+
 /// Return a fully qualified name for a given `FieldDefinition` on a given `TypeDefinition`.
 pub fn field_id(typdef_name: &str, field_name: &str) -> String {
     format!("{typdef_name}.{field_name}")
@@ -6,7 +6,7 @@
 use crate::{
     fully_qualified_namespace,
     graphql::{
-        extract_foreign_key_info, field_id, field_type_name,
+        derived_from_field_name, extract_foreign_key_info, field_id, field_type_name,
         inject_internal_types_into_document, is_list_type, list_field_type_name,
         GraphQLSchema, GraphQLSchemaValidator, IdCol, BASE_SCHEMA,
     },
@@ -15,13 +15,13 @@ use crate::{
 use async_graphql_parser::{
     parse_schema,
     types::{
-        EnumType, FieldDefinition, ObjectType, ServiceDocument, TypeDefinition, TypeKind,
-        TypeSystemDefinition, UnionType,
+        EnumType, FieldDefinition, InterfaceType, ObjectType, ServiceDocument,
+        TypeDefinition, TypeKind, TypeSystemDefinition, UnionType,
     },
 };
 use async_graphql_value::ConstValue;
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use thiserror::Error;
 
 use super::check_for_directive;
@@ -210,6 +210,12 @@ pub struct ParsedGraphQLSchema {
     /// All unique names of union types in the schema.
     union_names: HashSet<String>,
 
+    /// All unique names of interface types in the schema.
+    interface_names: HashSet<String>,
+
+    /// Names of the object types that implement each interface, indexed by interface name.
+    interface_implementors: HashMap<String, BTreeSet<String>>,
+
     /// All objects and their field names and types, indexed by object name.
     object_field_mappings: HashMap<String, BTreeMap<String, String>>,
 
@@ -251,6 +257,11 @@ pub struct ParsedGraphQLSchema {
     /// list type, whose inner content type is a foreign key reference to another `TypeDefinition`.
     join_table_meta: HashMap<String, Vec<JoinTableMeta>>,
 
+    /// Mapping of fully qualified names of list fields marked with the `@derivedFrom`
+    /// directive, to the name of the foreign key field of the listed type that
+    /// refers back to the type of the list field.
+    derived_fields: HashMap<String, String>,
+
     /// A mapping of object `TypeDefinition` names, and their respective `FieldDefinition`s - including
     /// the order of that `FieldDefinition` in the object.
     ///
@@ -266,6 +277,18 @@ pub struct ParsedGraphQLSchema {
     /// Internal types. These types should not be added to a
     /// database in any way; they are used to augment repsonses for introspection queries.
     internal_types: HashSet<String>,
+
+    /// All unique names of types marked with the `@history` directive, for which
+    /// every version of an entity is kept.
+    history_type_names: HashSet<String>,
+
+    /// All unique names of types marked with the `@immutable` directive, whose
+    /// entities are only ever inserted.
+    immutable_type_names: HashSet<String>,
+
+    /// Number of blocks for which the rows of types marked with the `@retain`
+    /// directive are kept, keyed by type name.
+    retained_types: HashMap<String, u32>,
 }
 
 impl Default for ParsedGraphQLSchema {
@@ -277,6 +300,8 @@ impl Default for ParsedGraphQLSchema {
             typedef_names_to_types: HashMap::new(),
             enum_names: HashSet::new(),
             union_names: HashSet::new(),
+            interface_names: HashSet::new(),
+            interface_implementors: HashMap::new(),
             objects: HashMap::new(),
             virtual_type_names: HashSet::new(),
             parsed_typedef_names: HashSet::new(),
@@ -291,9 +316,13 @@ impl Default for ParsedGraphQLSchema {
             list_type_defs: HashMap::new(),
             unions: HashMap::new(),
             join_table_meta: HashMap::new(),
+            derived_fields: HashMap::new(),
             object_ordered_fields: HashMap::new(),
             version: String::default(),
             internal_types: HashSet::new(),
+            history_type_names: HashSet::new(),
+            immutable_type_names: HashSet::new(),
+            retained_types: HashMap::new(),
         }
     }
 }
@@ -387,6 +416,31 @@ impl ParsedGraphQLSchema {
         &self.join_table_meta
     }
 
+    /// Return the name of the foreign key field from which the given list field of
+    /// a type is derived, if it is marked with the `@derivedFrom` directive.
+    pub fn derived_from(&self, typedef_name: &str, field_name: &str) -> Option<&String> {
+        self.derived_fields.get(&field_id(typedef_name, field_name))
+    }
+
+    /// Return the metadata of the join table through which the given list field
+    /// of a type refers to other entities, if any.
+    pub fn field_join_table_meta(
+        &self,
+        typedef_name: &str,
+        field_name: &str,
+    ) -> Option<&JoinTableMeta> {
+        let (field_def, _) = self.field_defs.get(&field_id(typedef_name, field_name))?;
+        if !is_list_type(field_def) {
+            return None;
+        }
+
+        let child_table_name = field_type_name(field_def).to_lowercase();
+        self.join_table_meta
+            .get(typedef_name)?
+            .iter()
+            .find(|meta| meta.child_table_name() == child_table_name)
+    }
+
     pub fn object_ordered_fields(&self) -> &HashMap<String, Vec<OrderedField>> {
         &self.object_ordered_fields
     }
@@ -433,17 +487,46 @@ impl ParsedGraphQLSchema {
     }
 
     /// Return a list of all type definitions that will have a record or table in
-    /// the database; functionally, this means any non-enum or internal type defintions.
+    /// the database; functionally, this means any non-enum, non-interface or internal
+    /// type defintions.
     pub fn storage_backed_typedefs(&self) -> Vec<(&String, &TypeDefinition)> {
         self.type_defs
             .iter()
             .filter(|(_, t)| {
-                !matches!(&t.kind, TypeKind::Enum(_))
+                !matches!(&t.kind, TypeKind::Enum(_) | TypeKind::Interface(_))
                     && !self.is_internal_typedef(t.name.node.as_str())
             })
             .collect()
     }
 
+    /// Return the names of the object types that are members of the given union
+    /// type, or that implement the given interface type, in alphabetical order.
+    ///
+    /// Returns `None` if the given type is neither a union nor an interface.
+    pub fn possible_types(&self, name: &str) -> Option<Vec<String>> {
+        if self.is_interface_typedef(name) {
+            return Some(
+                self.interface_implementors
+                    .get(name)
+                    .map(|names| names.iter().cloned().collect())
+                    .unwrap_or_default(),
+            );
+        }
+
+        match self.unions.get(name).map(|t| &t.kind) {
+            Some(TypeKind::Union(u)) => {
+                let mut members = u
+                    .members
+                    .iter()
+                    .map(|m| m.node.to_string())
+                    .collect::<Vec<String>>();
+                members.sort();
+                Some(members)
+            }
+            _ => None,
+        }
+    }
+
     /// Whether the given field type name is a possible foreign key.
     pub fn is_possible_foreign_key(&self, name: &str) -> bool {
         self.parsed_typedef_names.contains(name)
@@ -478,10 +561,31 @@ impl ParsedGraphQLSchema {
         self.union_names.contains(name)
     }
 
+    /// Whether the given field type name is an interface type.
+    pub fn is_interface_typedef(&self, name: &str) -> bool {
+        self.interface_names.contains(name)
+    }
+
     pub fn is_internal_typedef(&self, name: &str) -> bool {
         self.internal_types.contains(name)
     }
 
+    /// Whether the given type keeps every version of its entities.
+    pub fn is_history_typedef(&self, name: &str) -> bool {
+        self.history_type_names.contains(name)
+    }
+
+    /// Whether the given type's entities can only be inserted.
+    pub fn is_immutable_typedef(&self, name: &str) -> bool {
+        self.immutable_type_names.contains(name)
+    }
+
+    /// Number of blocks for which the rows of the given type are kept, if the type
+    /// has a retention policy.
+    pub fn retained_blocks(&self, name: &str) -> Option<u32> {
+        self.retained_types.get(name).copied()
+    }
+
     /// Return the GraphQL type for a given `FieldDefinition` name.
     fn field_type(&self, cond: &str, name: &str) -> Option<&String> {
         match self.object_field_mappings().get(cond) {
@@ -551,6 +655,7 @@ impl SchemaDecoder {
             self.decode_type_system_definifion(def)?;
         }
         self.build_typedef_names_to_types();
+        self.validate_derived_fields();
         Ok(())
     }
 
@@ -572,6 +677,7 @@ impl SchemaDecoder {
                 TypeKind::Object(o) => self.decode_object_type(name, node, o),
                 TypeKind::Enum(e) => self.decode_enum_type(name, e),
                 TypeKind::Union(u) => self.decode_union_type(name, node, u),
+                TypeKind::Interface(i) => self.decode_interface_type(name, i),
                 TypeKind::Scalar => {
                     self.parsed_graphql_schema.scalar_names.insert(name.clone());
                 }
@@ -585,6 +691,8 @@ impl SchemaDecoder {
     }
 
     fn decode_enum_type(&mut self, name: String, e: &EnumType) {
+        GraphQLSchemaValidator::check_disallowed_graphql_typedef_name(&name);
+
         self.parsed_graphql_schema
             .virtual_type_names
             .insert(name.clone());
@@ -604,6 +712,42 @@ impl SchemaDecoder {
         }
     }
 
+    fn decode_interface_type(&mut self, name: String, i: &InterfaceType) {
+        GraphQLSchemaValidator::check_disallowed_graphql_typedef_name(&name);
+
+        self.parsed_graphql_schema
+            .parsed_typedef_names
+            .insert(name.clone());
+        self.parsed_graphql_schema
+            .interface_names
+            .insert(name.clone());
+
+        // Interfaces are queried through the tables of the objects that implement
+        // them, and so do not have tables of their own.
+        self.parsed_graphql_schema
+            .virtual_type_names
+            .insert(name.clone());
+
+        for field in &i.fields {
+            let field_name = field.node.name.to_string();
+            let fid = field_id(&name, &field_name);
+
+            self.parsed_graphql_schema
+                .field_type_mappings
+                .insert(fid.clone(), field_type_name(&field.node));
+
+            self.parsed_graphql_schema
+                .object_field_mappings
+                .entry(name.clone())
+                .or_default()
+                .insert(field_name, field_type_name(&field.node));
+
+            self.parsed_graphql_schema
+                .field_type_optionality
+                .insert(fid, field.node.ty.node.nullable);
+        }
+    }
+
     fn decode_union_type(
         &mut self,
         union_name: String,
@@ -704,6 +848,7 @@ impl SchemaDecoder {
                         .virtual_type_names
                         .contains(&ftype)
                     && !self.parsed_graphql_schema.internal_types.contains(&ftype)
+                    && !check_for_directive(&f.node.directives, "derivedFrom")
                 {
                     let (_ref_coltype, ref_colname, ref_tablename) =
                         extract_foreign_key_info(
@@ -795,6 +940,14 @@ impl SchemaDecoder {
             .parsed_typedef_names
             .insert(obj_name.clone());
 
+        for interface in &o.implements {
+            self.parsed_graphql_schema
+                .interface_implementors
+                .entry(interface.node.to_string())
+                .or_default()
+                .insert(obj_name.clone());
+        }
+
         let is_virtual = node
             .directives
             .iter()
@@ -809,6 +962,55 @@ impl SchemaDecoder {
             GraphQLSchemaValidator::virtual_type_has_no_id_field(o, &obj_name);
         }
 
+        if check_for_directive(&node.directives, "history") {
+            GraphQLSchemaValidator::history_type_is_not_virtual(is_virtual, &obj_name);
+
+            self.parsed_graphql_schema
+                .history_type_names
+                .insert(obj_name.clone());
+        }
+
+        if check_for_directive(&node.directives, "immutable") {
+            GraphQLSchemaValidator::immutable_type_is_not_virtual(is_virtual, &obj_name);
+            GraphQLSchemaValidator::immutable_type_does_not_keep_history(
+                check_for_directive(&node.directives, "history"),
+                &obj_name,
+            );
+
+            self.parsed_graphql_schema
+                .immutable_type_names
+                .insert(obj_name.clone());
+        }
+
+        if let Some(retain) = node
+            .directives
+            .iter()
+            .find(|d| d.node.name.to_string() == "retain")
+        {
+            let blocks = retain
+                .node
+                .arguments
+                .iter()
+                .find(|(name, _)| name.node == "blocks")
+                .and_then(|(_, value)| match &value.node {
+                    ConstValue::Number(n) => n.as_u64(),
+                    _ => None,
+                });
+
+            GraphQLSchemaValidator::retained_type_is_not_virtual(is_virtual, &obj_name);
+            GraphQLSchemaValidator::retained_type_does_not_keep_history(
+                check_for_directive(&node.directives, "history"),
+                &obj_name,
+            );
+            GraphQLSchemaValidator::retained_type_has_no_block_height_field(o, &obj_name);
+            let blocks =
+                GraphQLSchemaValidator::retained_blocks_are_valid(blocks, &obj_name);
+
+            self.parsed_graphql_schema
+                .retained_types
+                .insert(obj_name.clone(), blocks);
+        }
+
         // Since we have to use this manual `is_list_type` for each field, we might as well
         // keep track of how many m2m fields we have for this object here. We could also move this
         // logic to the `GraphQLSchemaValidator` itself, but that means we'd have to copy over the
@@ -825,6 +1027,20 @@ impl SchemaDecoder {
 
             GraphQLSchemaValidator::ensure_fielddef_is_not_nested_list(&field.node);
 
+            // Fields derived from the foreign keys of another type are resolved when
+            // queried, and so are neither stored nor related through a join table.
+            let derived_from = derived_from_field_name(&field.node);
+            if let Some(derived_from) = &derived_from {
+                GraphQLSchemaValidator::derived_from_field_is_list(
+                    &field.node,
+                    &obj_name,
+                );
+
+                self.parsed_graphql_schema
+                    .derived_fields
+                    .insert(fid.clone(), derived_from.clone());
+            }
+
             self.parsed_graphql_schema
                 .object_ordered_fields
                 .entry(obj_name.clone())
@@ -833,7 +1049,7 @@ impl SchemaDecoder {
 
             // We need to add these field type names to `GraphQLSchemaValidator::list_field_types` prior to
             // doing the foreign key check below, (since we need to know whether a field is a FK type)
-            if is_list_type(&field.node) {
+            if is_list_type(&field.node) && derived_from.is_none() {
                 self.parsed_graphql_schema
                     .list_field_types
                     .insert(field_typ_name.replace('!', ""));
@@ -857,6 +1073,7 @@ impl SchemaDecoder {
                     .contains(&ftype)
                 && !self.parsed_graphql_schema.internal_types.contains(&ftype)
                 && !is_internal
+                && derived_from.is_none()
             {
                 GraphQLSchemaValidator::foreign_key_field_contains_no_unique_directive(
                     &field.node,
@@ -948,6 +1165,27 @@ impl SchemaDecoder {
             .insert(obj_name, field_mapping);
     }
 
+    /// Ensure that each field marked with the `@derivedFrom` directive is derived from
+    /// a foreign key field of the listed type that refers back to the type of the field.
+    fn validate_derived_fields(&self) {
+        for (fid, derived_from) in &self.parsed_graphql_schema.derived_fields {
+            let (obj_name, field_name) = fid.split_once('.').unwrap_or_default();
+            let field_type = &self.parsed_graphql_schema.field_type_mappings[fid];
+            let derived_from_type = self
+                .parsed_graphql_schema
+                .field_type_mappings
+                .get(&field_id(field_type, derived_from));
+
+            GraphQLSchemaValidator::derived_from_field_refers_to_type(
+                obj_name,
+                field_name,
+                field_type,
+                derived_from,
+                derived_from_type,
+            );
+        }
+    }
+
     fn build_typedef_names_to_types(&mut self) {
         self.parsed_graphql_schema.typedef_names_to_types = self
             .parsed_graphql_schema
@@ -1048,6 +1286,11 @@ union Storage = Safe | Vault
             parsed.join_table_meta().get("Wallet").unwrap()[0],
             JoinTableMeta::new("wallet", "id", "account", "id", Some(1))
         );
+        assert_eq!(
+            parsed.field_join_table_meta("Wallet", "accounts"),
+            parsed.join_table_meta().get("Wallet").unwrap().first()
+        );
+        assert!(parsed.field_join_table_meta("User", "account").is_none());
 
         // Many to many for unions
         assert!(!parsed.join_table_meta().contains_key("Safe"));
@@ -1070,6 +1313,123 @@ union Storage = Safe | Vault
         assert!(parsed.internal_types.contains("UserEdge"));
     }
 
+    #[test]
+    fn test_parser_caches_possible_types_of_unions_and_interfaces() {
+        let schema = r#"
+interface Named {
+    id: ID!
+    name: String!
+}
+
+type Vault implements Named @entity {
+    id: ID!
+    name: String!
+    label: String!
+}
+
+type Safe implements Named @entity {
+    id: ID!
+    name: String!
+    balance: U64!
+}
+
+union Storage = Vault | Safe
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert!(parsed.is_interface_typedef("Named"));
+        assert!(!parsed.is_interface_typedef("Storage"));
+        assert_eq!(
+            parsed.possible_types("Named"),
+            Some(vec!["Safe".to_string(), "Vault".to_string()])
+        );
+        assert_eq!(
+            parsed.possible_types("Storage"),
+            Some(vec!["Safe".to_string(), "Vault".to_string()])
+        );
+        assert_eq!(parsed.possible_types("Safe"), None);
+
+        // Interfaces can be queried, but are not backed by tables.
+        assert_eq!(
+            parsed.graphql_type(None, "named"),
+            Some(&"Named".to_string())
+        );
+        assert_eq!(
+            parsed.graphql_type(Some(&"Named".to_string()), "name"),
+            Some(&"String".to_string())
+        );
+        assert!(!parsed
+            .storage_backed_typedefs()
+            .iter()
+            .any(|(name, _)| name.as_str() == "Named"));
+    }
+
+    #[test]
+    fn test_parser_caches_fields_derived_from_foreign_keys() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    transfers: [Transfer!]! @derivedFrom(field: "owner")
+}
+
+type Transfer @entity {
+    id: ID!
+    owner: Account!
+    amount: U64!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.derived_from("Account", "transfers"),
+            Some(&"owner".to_string())
+        );
+        assert_eq!(parsed.derived_from("Transfer", "owner"), None);
+
+        // Derived fields are neither foreign keys nor many-to-many relationships.
+        assert!(!parsed.is_list_typedef("Account"));
+        assert!(!parsed.join_table_meta().contains_key("Account"));
+        assert!(!parsed.foreign_key_mappings().contains_key("account"));
+        assert!(parsed.foreign_key_mappings()["transfer"].contains_key("owner"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(transfers) on TypeDefinition(Account) is derived from `Transfer.amount`, which must be a FieldDefinition of type `Account`."
+    )]
+    fn test_parser_rejects_fields_derived_from_unrelated_fields() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    transfers: [Transfer!]! @derivedFrom(field: "amount")
+}
+
+type Transfer @entity {
+    id: ID!
+    owner: Account!
+    amount: U64!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        );
+    }
+
     #[test]
     fn test_internal_type_defs_in_object_field_mapping() {
         let schema = r#"
@@ -1122,6 +1482,48 @@ type TransactionData @entity {
         .unwrap();
     }
 
+    #[test]
+    #[should_panic(expected = "TypeDefinition name 'Context' is reserved.")]
+    fn test_schema_validator_rejects_the_handler_context_name() {
+        // The `#[indexer]` macro injects a `Context` type for handlers.
+        let schema = r#"
+type Context @entity {
+    id: ID!
+    block_height: U64!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "TypeDefinition name 'Context' is reserved.")]
+    fn test_schema_validator_check_disallowed_graphql_enum_name() {
+        let schema = r#"
+enum Context {
+    BLOCK
+    TRANSACTION
+}
+
+type Foo @entity {
+    id: ID!
+    context: Context!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
     #[test]
     #[should_panic(
         expected = "TypeDefinition(Union(Baz)) does not have consistent virtual/non-virtual members."
@@ -1373,6 +1775,91 @@ type Bar @entity {
     id: ID!
     foo: [Foo!]! @join(on:name)
 }
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Virtual TypeDefinition(Foo) cannot keep history.")]
+    fn test_schema_validator_history_type_is_not_virtual() {
+        let schema = r#"
+type Foo @entity(virtual: true) @history {
+    name: String!
+}
+
+type Bar @entity {
+    id: ID!
+    foo: Foo!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Immutable TypeDefinition(Transfer) cannot keep history.")]
+    fn test_schema_validator_immutable_type_does_not_keep_history() {
+        let schema = r#"
+type Transfer @entity @immutable @history {
+    id: ID!
+    amount: U64!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_caches_retained_blocks_of_retained_typedefs() {
+        let schema = r#"
+type Snapshot @entity @retain(blocks: 1000) {
+    id: ID!
+    amount: U64!
+}
+
+type Account @entity {
+    id: ID!
+    balance: U64!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert_eq!(parsed.retained_blocks("Snapshot"), Some(1000));
+        assert_eq!(parsed.retained_blocks("Account"), None);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "TypeDefinition(Snapshot) must retain a positive number of blocks, e.g. `@retain(blocks: 1000)`."
+    )]
+    fn test_schema_validator_retained_blocks_are_valid() {
+        let schema = r#"
+type Snapshot @entity @retain(blocks: 0) {
+    id: ID!
+    amount: U64!
+}
 "#;
 
         let _ = ParsedGraphQLSchema::new(
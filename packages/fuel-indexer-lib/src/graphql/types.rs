@@ -37,3 +37,30 @@ impl ObjectCol {
         "Object"
     }
 }
+
+/// Represents the history table of a `TypeDefinition` marked with `@history`, and
+/// the columns holding the range of block heights for which a version of an
+/// entity was current.
+pub struct HistoryCol;
+impl HistoryCol {
+    pub fn table_name(table: &str) -> String {
+        format!("{table}_history")
+    }
+
+    pub fn block_height_start() -> &'static str {
+        "block_height_start"
+    }
+
+    pub fn block_height_end() -> &'static str {
+        "block_height_end"
+    }
+}
+
+/// Represents the column recording the height of the block at which a row of a
+/// `TypeDefinition` marked with `@retain` was last written.
+pub struct RetainCol;
+impl RetainCol {
+    pub fn block_height() -> &'static str {
+        "block_height"
+    }
+}
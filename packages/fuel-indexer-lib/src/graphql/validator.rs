@@ -1,10 +1,13 @@
-use crate::{constants::*, graphql::MAX_FOREIGN_KEY_LIST_FIELDS};
+use crate::{
+    constants::*,
+    graphql::{types::RetainCol, MAX_FOREIGN_KEY_LIST_FIELDS},
+};
 use async_graphql_parser::types::{
     FieldDefinition, ObjectType, TypeDefinition, TypeKind,
 };
 use std::collections::HashSet;
 
-use super::check_for_directive;
+use super::{check_for_directive, is_list_type};
 
 /// General container used to store a set of GraphQL schema validation functions.
 pub struct GraphQLSchemaValidator;
@@ -73,6 +76,29 @@ impl GraphQLSchemaValidator {
         }
     }
 
+    /// Ensure that a `FieldDefinition` marked with `@derivedFrom` is a list type.
+    pub fn derived_from_field_is_list(f: &FieldDefinition, obj_name: &str) {
+        let name = f.name.to_string();
+        if !is_list_type(f) {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) is marked with `@derivedFrom`, so it must be a list type.");
+        }
+    }
+
+    /// Ensure that a `FieldDefinition` marked with `@derivedFrom` is derived from a
+    /// `FieldDefinition` of the listed `TypeDefinition` that refers back to the
+    /// `TypeDefinition` of the list field.
+    pub fn derived_from_field_refers_to_type(
+        obj_name: &str,
+        field_name: &str,
+        field_type: &str,
+        derived_from: &str,
+        derived_from_type: Option<&String>,
+    ) {
+        if derived_from_type.map(|t| t.as_str()) != Some(obj_name) {
+            panic!("FieldDefinition({field_name}) on TypeDefinition({obj_name}) is derived from `{field_type}.{derived_from}`, which must be a FieldDefinition of type `{obj_name}`.");
+        }
+    }
+
     /// Ensure a `FieldDefinition` is not a reference to a nested list.
     pub fn ensure_fielddef_is_not_nested_list(f: &FieldDefinition) {
         let name = f.name.to_string();
@@ -103,6 +129,62 @@ impl GraphQLSchemaValidator {
         }
     }
 
+    /// Ensure that a `TypeDefinition` marked with `@history` is persisted to the database.
+    pub fn history_type_is_not_virtual(is_virtual: bool, obj_name: &str) {
+        if is_virtual {
+            panic!("Virtual TypeDefinition({obj_name}) cannot keep history.");
+        }
+    }
+
+    /// Ensure that a `TypeDefinition` marked with `@immutable` is persisted to the database.
+    pub fn immutable_type_is_not_virtual(is_virtual: bool, obj_name: &str) {
+        if is_virtual {
+            panic!("Virtual TypeDefinition({obj_name}) cannot be immutable.");
+        }
+    }
+
+    /// Ensure that a `TypeDefinition` marked with `@immutable` is not also marked
+    /// with `@history`, since its entities never change.
+    pub fn immutable_type_does_not_keep_history(has_history: bool, obj_name: &str) {
+        if has_history {
+            panic!("Immutable TypeDefinition({obj_name}) cannot keep history.");
+        }
+    }
+
+    /// Ensure that a `TypeDefinition` marked with `@retain` is persisted to the database.
+    pub fn retained_type_is_not_virtual(is_virtual: bool, obj_name: &str) {
+        if is_virtual {
+            panic!("Virtual TypeDefinition({obj_name}) cannot have a retention policy.");
+        }
+    }
+
+    /// Ensure that a `TypeDefinition` marked with `@retain` is not also marked with
+    /// `@history`, since pruned rows would leave their history dangling.
+    pub fn retained_type_does_not_keep_history(has_history: bool, obj_name: &str) {
+        if has_history {
+            panic!("TypeDefinition({obj_name}) cannot both keep history and have a retention policy.");
+        }
+    }
+
+    /// Ensure that a `TypeDefinition` marked with `@retain` doesn't contain a
+    /// `FieldDefinition` named like the column recording the height at which its
+    /// rows were written.
+    pub fn retained_type_has_no_block_height_field(o: &ObjectType, obj_name: &str) {
+        let name = RetainCol::block_height();
+        if o.fields.iter().any(|f| f.node.name.to_string() == name) {
+            panic!("TypeDefinition({obj_name}) has a retention policy, so it cannot contain a `{name}` FieldDefinition.");
+        }
+    }
+
+    /// Ensure that the number of blocks of a `@retain` directive is a positive `u32`,
+    /// and return it.
+    pub fn retained_blocks_are_valid(blocks: Option<u64>, obj_name: &str) -> u32 {
+        match blocks.and_then(|b| u32::try_from(b).ok()) {
+            Some(blocks) if blocks > 0 => blocks,
+            _ => panic!("TypeDefinition({obj_name}) must retain a positive number of blocks, e.g. `@retain(blocks: 1000)`."),
+        }
+    }
+
     /// Ensure that any `FieldDefinition` that itself is a foreign relationship, does not contain
     /// a `@unique` directive.
     pub fn foreign_key_field_contains_no_unique_directive(
@@ -30,6 +30,7 @@ pub enum WasmIndexerError {
     AllocFailed,
     Panic,
     GeneralError,
+    ImmutableEntityError,
 }
 
 impl From<u32> for WasmIndexerError {
@@ -49,6 +50,7 @@ impl From<u32> for WasmIndexerError {
             11 => Self::AllocMissing,
             12 => Self::AllocFailed,
             13 => Self::Panic,
+            15 => Self::ImmutableEntityError,
             _ => Self::GeneralError,
         }
     }
@@ -103,6 +105,9 @@ impl std::fmt::Display for WasmIndexerError {
                 write!(f, "Panic")
             }
             Self::GeneralError => write!(f, "Some unspecified WASM error occurred"),
+            Self::ImmutableEntityError => {
+                write!(f, "Failed to write an immutable entity")
+            }
         }
     }
 }
@@ -27,10 +27,15 @@ pub enum ManifestError {
 ///
 /// When using a `Wasm` module, the WASM binary at the given path
 /// is read and those bytes are registered into a `WasmIndexerExecutor`.
+///
+/// When using a `Native` module, the indexer is compiled as regular Rust and
+/// linked into a custom indexer service binary, which registers its handlers
+/// into a `NativeIndexExecutor`.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum Module {
     Wasm(String),
+    Native,
 }
 
 impl From<PathBuf> for Module {
@@ -43,14 +48,19 @@ impl ToString for Module {
     fn to_string(&self) -> String {
         match self {
             Self::Wasm(o) => o.to_string(),
+            Self::Native => "native".to_string(),
         }
     }
 }
 
-impl AsRef<Path> for Module {
-    fn as_ref(&self) -> &Path {
+impl Module {
+    /// Path to the WASM binary of this module, if any.
+    ///
+    /// Native modules are linked into the indexer service binary, and have no path.
+    pub fn path(&self) -> Option<&Path> {
         match self {
-            Self::Wasm(o) => Path::new(o),
+            Self::Wasm(o) => Some(Path::new(o)),
+            Self::Native => None,
         }
     }
 }
@@ -102,6 +112,23 @@ pub struct Manifest {
     /// When set to true, the indexer will resume from the block height at which it last stopped.
     #[serde(default)]
     resumable: Option<bool>,
+
+    /// When set to true, the indexer will only fetch blocks that include transactions
+    /// involving the contracts in `contract_id`, rather than every block.
+    #[serde(default)]
+    sparse: Option<bool>,
+
+    /// Additional contracts to index, each decoded with its own ABI.
+    #[serde(default)]
+    contracts: Option<Vec<ContractAbi>>,
+
+    /// Sway scripts whose `main` arguments should be decoded from script data.
+    #[serde(default)]
+    scripts: Option<Vec<ProgramAbi>>,
+
+    /// Sway predicates whose `main` arguments should be decoded from predicate data.
+    #[serde(default)]
+    predicates: Option<Vec<ProgramAbi>>,
 }
 
 impl Manifest {
@@ -144,6 +171,7 @@ impl Manifest {
 
                 Ok(bytes)
             }
+            Module::Native => Err(ManifestError::NativeModuleError),
         }
     }
 
@@ -231,6 +259,30 @@ impl Manifest {
     pub fn resumable(&self) -> Option<bool> {
         self.resumable
     }
+
+    pub fn sparse(&self) -> Option<bool> {
+        self.sparse
+    }
+
+    pub fn contracts(&self) -> &[ContractAbi] {
+        self.contracts.as_deref().unwrap_or_default()
+    }
+
+    pub fn scripts(&self) -> &[ProgramAbi] {
+        self.scripts.as_deref().unwrap_or_default()
+    }
+
+    pub fn predicates(&self) -> &[ProgramAbi] {
+        self.predicates.as_deref().unwrap_or_default()
+    }
+
+    /// Return the IDs of all contracts this indexer subscribes to, from both
+    /// `contract_id` and `contracts`.
+    pub fn subscribed_contract_ids(&self) -> Vec<String> {
+        let mut ids = self.contract_id.ids();
+        ids.extend(self.contracts().iter().flat_map(|c| c.contract_id().ids()));
+        ids
+    }
 }
 
 impl TryFrom<&str> for Manifest {
@@ -257,6 +309,79 @@ impl TryFrom<&Vec<u8>> for Manifest {
     }
 }
 
+/// A contract indexed with its own ABI, as listed in `Manifest::contracts`.
+///
+/// Types generated from the ABI are placed in a module named after the contract,
+/// so that types with the same name in different ABIs don't collide.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ContractAbi {
+    /// Name of the module in which the ABI's types are generated.
+    name: String,
+
+    /// Filepath to the Sway contract ABI.
+    abi: String,
+
+    /// Set of contract IDs decoded with this ABI.
+    ///
+    /// If no contract ID is specified, this ABI is used to decode contracts that
+    /// are watched at runtime.
+    #[serde(
+        default,
+        serialize_with = "ContractIds::serialize",
+        deserialize_with = "ContractIds::deserialize"
+    )]
+    contract_id: ContractIds,
+}
+
+impl ContractAbi {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn abi(&self) -> &str {
+        &self.abi
+    }
+
+    pub fn contract_id(&self) -> &ContractIds {
+        &self.contract_id
+    }
+}
+
+/// A script or predicate indexed with its own ABI, as listed in `Manifest::scripts`
+/// or `Manifest::predicates`.
+///
+/// As with `ContractAbi`, types generated from the ABI are placed in a module named
+/// after the program.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ProgramAbi {
+    /// Name of the module in which the ABI's types are generated.
+    name: String,
+
+    /// Filepath to the Sway script or predicate ABI.
+    abi: String,
+
+    /// Filepath to the compiled script or predicate bytecode.
+    ///
+    /// If specified, only programs with this bytecode are decoded with this ABI.
+    /// Otherwise, every script (or predicate) is decoded with this ABI.
+    #[serde(default)]
+    bytecode: Option<String>,
+}
+
+impl ProgramAbi {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn abi(&self) -> &str {
+        &self.abi
+    }
+
+    pub fn bytecode(&self) -> Option<&str> {
+        self.bytecode.as_deref()
+    }
+}
+
 /// Represents contract IDs in a `Manifest` struct.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(untagged)]
@@ -270,7 +395,24 @@ pub enum ContractIds {
     Multiple(Vec<String>),
 }
 
+impl Default for ContractIds {
+    fn default() -> Self {
+        ContractIds::Single(None)
+    }
+}
+
 impl ContractIds {
+    /// Return all non-empty contract IDs in this set.
+    pub fn ids(&self) -> Vec<String> {
+        match self {
+            ContractIds::Single(Some(id)) if !id.is_empty() => vec![id.clone()],
+            ContractIds::Single(_) => vec![],
+            ContractIds::Multiple(ids) => {
+                ids.iter().filter(|id| !id.is_empty()).cloned().collect()
+            }
+        }
+    }
+
     fn serialize<S>(ids: &ContractIds, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
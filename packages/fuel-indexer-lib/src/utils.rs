@@ -10,7 +10,10 @@ use std::{
     path::Path,
     str::FromStr,
 };
-use tokio::time::{sleep, Duration};
+use tokio::{
+    sync::oneshot,
+    time::{sleep, Duration},
+};
 use tracing::{debug, info, warn};
 use tracing_subscriber::filter::EnvFilter;
 
@@ -97,11 +100,68 @@ pub struct StopRequest {
     pub identifier: String,
 }
 
+/// Request to run the specified indexer against a single block, in a transaction
+/// that is always rolled back.
+///
+/// Sent from API server to indexer service, which sends the report of the run, or
+/// the reason it could not be run, back through `response`.
+#[derive(Debug)]
+pub struct DebugRequest {
+    /// The namespace of the indexer being debugged.
+    pub namespace: String,
+    /// The identifier of the indexer being debugged.
+    pub identifier: String,
+    /// The height of the block to run the indexer against.
+    pub block_height: u32,
+    /// Channel through which the report is sent back.
+    pub response: oneshot::Sender<Result<DebugReport, String>>,
+}
+
 /// A general request sent from the API server to the indexer service.
 #[derive(Debug)]
 pub enum ServiceRequest {
     Reload(ReloadRequest),
     Stop(StopRequest),
+    Debug(DebugRequest),
+}
+
+/// Kind of change that a handler made to an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityChangeKind {
+    Insert,
+    Update,
+    Delete,
+}
+
+/// Change that a handler made to an entity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityChange {
+    /// Name of the table of the entity.
+    pub entity: String,
+    /// ID of the entity.
+    pub id: String,
+    /// Kind of change.
+    pub kind: EntityChangeKind,
+    /// Columns of the entity before the change, if it existed.
+    pub before: Option<serde_json::Value>,
+    /// Columns of the entity after the change, if it still exists.
+    pub after: Option<serde_json::Value>,
+}
+
+/// Report of the run of an indexer against a single block.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DebugReport {
+    /// Height of the block.
+    pub block_height: u32,
+    /// Changes made to entities, in the order in which they were made.
+    pub changes: Vec<EntityChange>,
+    /// Lines logged by the handlers.
+    pub logs: Vec<String>,
+    /// Metering points used by the handlers, if metering is enabled.
+    pub metering_points_used: Option<u64>,
+    /// Error returned by the handlers, or their panic.
+    pub error: Option<String>,
 }
 
 /// Returns the lower hex representation of a [`sha2::SHA256`] digest of the provided input.
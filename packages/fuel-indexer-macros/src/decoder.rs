@@ -93,7 +93,9 @@ impl Decoder for ImplementationDecoder {
                     .collect::<HashSet<String>>();
 
                 for field in &o.fields {
-                    if check_for_directive(&field.node.directives, "internal") {
+                    if check_for_directive(&field.node.directives, "internal")
+                        || check_for_directive(&field.node.directives, "derivedFrom")
+                    {
                         continue;
                     }
 
@@ -208,9 +210,11 @@ impl Decoder for ImplementationDecoder {
 
                         fields
                             .iter()
-                            // Remove any fields that are marked for internal use
+                            // Remove any fields that are marked for internal use or derived
                             .filter_map(|f| {
-                                if check_for_directive(&f.0.directives, "internal") {
+                                if check_for_directive(&f.0.directives, "internal")
+                                    || check_for_directive(&f.0.directives, "derivedFrom")
+                                {
                                     return None;
                                 }
 
@@ -375,9 +379,11 @@ impl From<ImplementationDecoder> for TokenStream {
 
                         fields
                             .iter()
-                            // Remove any fields marked for internal use
+                            // Remove any fields marked for internal use or derived
                             .filter_map(|f| {
-                                if check_for_directive(&f.0.directives, "internal") {
+                                if check_for_directive(&f.0.directives, "internal")
+                                    || check_for_directive(&f.0.directives, "derivedFrom")
+                                {
                                     return None;
                                 }
 
@@ -510,6 +516,9 @@ pub struct ObjectDecoder {
     /// Tokens used to create fields in the `Entity::to_row` function.
     to_row: TokenStream,
 
+    /// Names of the columns in the `Entity::to_row` function, in order.
+    columns: Vec<String>,
+
     /// Tokens for the parameters of the `Entity::new` function.
     impl_decoder: ImplementationDecoder,
 
@@ -533,6 +542,7 @@ impl Default for ObjectDecoder {
             field_extractors: quote! {},
             from_row: quote! {},
             to_row: quote! {},
+            columns: Vec::new(),
             impl_decoder: ImplementationDecoder::default(),
             type_id: std::i64::MAX,
         }
@@ -553,11 +563,14 @@ impl Decoder for ObjectDecoder {
                 let mut field_extractors = quote! {};
                 let mut from_row = quote! {};
                 let mut to_row = quote! {};
+                let mut columns = Vec::new();
 
                 let mut fields_map = BTreeMap::new();
 
                 for field in o.fields.iter() {
-                    if check_for_directive(&field.node.directives, "internal") {
+                    if check_for_directive(&field.node.directives, "internal")
+                        || check_for_directive(&field.node.directives, "derivedFrom")
+                    {
                         continue;
                     }
 
@@ -608,6 +621,8 @@ impl Decoder for ObjectDecoder {
                         #to_row
                         #field_decoder
                     };
+
+                    columns.push(field_name_ident.to_string());
                 }
 
                 Self {
@@ -616,6 +631,7 @@ impl Decoder for ObjectDecoder {
                     field_extractors,
                     from_row,
                     to_row,
+                    columns,
                     impl_decoder: ImplementationDecoder::from_typedef(typ, parsed),
                     type_id,
                 }
@@ -776,6 +792,7 @@ impl From<ObjectDecoder> for TokenStream {
             field_extractors,
             from_row,
             to_row,
+            columns,
             impl_decoder,
             type_id,
             ..
@@ -814,6 +831,9 @@ impl From<ObjectDecoder> for TokenStream {
                         let child_position = meta.parent().child_position.expect(
                             "Parent `JoinTableMeta` is missing `child_position`.",
                         );
+                        let immutable = impl_decoder
+                            .parsed
+                            .is_immutable_typedef(&meta.parent().typedef_name);
 
                         quote! {
                             Some(JoinMetadata {
@@ -822,6 +842,7 @@ impl From<ObjectDecoder> for TokenStream {
                                 parent_column_name: #parent_column_name,
                                 child_column_name: #child_column_name,
                                 child_position: #child_position,
+                                immutable: #immutable,
                             })
                         }
                     })
@@ -857,6 +878,7 @@ impl From<ObjectDecoder> for TokenStream {
             impl<'a> Entity<'a> for #ident {
                 const TYPE_ID: i64 = #type_id;
                 const JOIN_METADATA: Option<[Option<JoinMetadata<'a>>; MAX_FOREIGN_KEY_LIST_FIELDS]> = #join_metadata;
+                const COLUMNS: &'static [&'static str] = &[#( #columns ),*];
 
                 fn from_row(mut vec: Vec<FtColumn>) -> Self {
                     #field_extractors
@@ -1005,6 +1027,9 @@ type Person @entity {
         assert!(tokenstream.contains("pub fn get_or_create (self) -> Self"));
         assert!(tokenstream.contains("fn from_row (mut vec : Vec < FtColumn >) -> Self"));
         assert!(tokenstream.contains("fn to_row (& self) -> Vec < FtColumn >"));
+        assert!(tokenstream.contains(
+            "const COLUMNS : & 'static [& 'static str] = & [\"id\" , \"name\" , \"age\"] ;"
+        ));
     }
 
     #[test]
@@ -1101,6 +1126,6 @@ type Wallet @entity {
 
         // Trying to assert we have every single token expected might be a bit much, so
         // let's just assert that we have the main/primary method and function definitions.
-        assert!(tokenstream.contains("const JOIN_METADATA : Option < [Option < JoinMetadata < 'a >> ; MAX_FOREIGN_KEY_LIST_FIELDS] > = Some ([Some (JoinMetadata { namespace : \"test_test\" , table_name : \"wallets_accounts\" , parent_column_name : \"id\" , child_column_name : \"id\" , child_position : 1usize , }) , None , None , None , None , None , None , None , None , None]) ;"));
+        assert!(tokenstream.contains("const JOIN_METADATA : Option < [Option < JoinMetadata < 'a >> ; MAX_FOREIGN_KEY_LIST_FIELDS] > = Some ([Some (JoinMetadata { namespace : \"test_test\" , table_name : \"wallets_accounts\" , parent_column_name : \"id\" , child_column_name : \"id\" , child_position : 1usize , immutable : false , }) , None , None , None , None , None , None , None , None , None]) ;"));
     }
 }
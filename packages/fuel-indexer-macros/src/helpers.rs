@@ -2,16 +2,14 @@ use std::collections::{HashMap, HashSet};
 
 use async_graphql_parser::types::{BaseType, FieldDefinition, Type as AsyncGraphQLType};
 use async_graphql_value::Name;
-use fuel_abi_types::abi::program::{
-    ABIFunction, LoggedType, ProgramABI, TypeDeclaration,
-};
+use fuel_abi_types::abi::program::{ProgramABI, TypeApplication, TypeDeclaration};
 use fuel_indexer_lib::{
     constants::*,
     graphql::{list_field_type_name, types::IdCol, ParsedGraphQLSchema},
 };
 use fuel_indexer_types::{type_id, FUEL_TYPES_NAMESPACE};
 use fuels_code_gen::utils::Source;
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::{format_ident, quote};
 use syn::{GenericArgument, Ident, PathArguments, Type, TypePath};
 
@@ -92,56 +90,47 @@ pub fn is_unit_type(typ: &TypeDeclaration) -> bool {
 
 /// Whether the TypeDeclaration should be used to build struct fields and decoders
 pub fn is_non_decodable_type(typ: &TypeDeclaration) -> bool {
-    is_tuple_type(typ)
-        || is_unit_type(typ)
+    is_unit_type(typ)
+        || typ.type_field.starts_with("generic ")
         || IGNORED_GENERIC_METADATA.contains(typ.type_field.as_str())
 }
 
+/// Whether the `TypeDeclaration` is decoded as part of each `TypeApplication` that
+/// references it, rather than with a decoder of its own.
+///
+/// Tuples, arrays, strings, generic types (e.g., `Option<T>` or `Pair<T, U>`), and
+/// standard library heap types can only be decoded once their components and type
+/// arguments are known.
+pub fn is_composite_type(typ: &TypeDeclaration) -> bool {
+    is_tuple_type(typ)
+        || is_array_type(typ)
+        || typ.type_field == "str"
+        || typ.type_field.starts_with("str[")
+        || typ
+            .type_parameters
+            .as_ref()
+            .is_some_and(|params| !params.is_empty())
+        || matches!(typ.type_field.as_str(), "struct Bytes" | "struct String")
+}
+
 /// Derive Ident for decoded type
 ///
 /// These idents are used as fields for the `Decoder` struct.
 fn decoded_ident(typ: &TypeDeclaration) -> Ident {
-    let name = {
-        let name = derive_type_name(typ);
-        if typ.components.is_some() {
-            if is_array_type(typ) {
-                let name = name.replace(['[', ']', ' '], "");
-                let name = name.replace(';', "_");
-                format!("array_{}", name)
-            } else {
-                name
-            }
-        } else if name.starts_with("Option") {
-            typ.type_field.replace(['<', '>'], "_")
-        } else {
-            typ.type_field.replace(['[', ']'], "_")
-        }
+    // Types from namespaced ABIs (e.g., `router::Swap`) are prefixed by their namespace.
+    let name = if typ.components.is_some() {
+        derive_type_name(typ)
+    } else {
+        typ.type_field.clone()
     };
+    let name = name.replace("::", "_");
 
-    if is_generic_type(typ) {
-        let gt = GenericType::from(name.as_str());
-        match gt {
-            GenericType::Vec => {
-                let name = name.replace(['<', '>'], "_").to_ascii_lowercase();
-                format_ident! { "{}decoded", name }
-            }
-            GenericType::Option => {
-                let name = name.replace(['<', '>'], "_").to_ascii_lowercase();
-                format_ident! { "{}decoded", name }
-            }
-            _ => proc_macro_error::abort_call_site!(
-                "Could not derive decoded ident for generic type: {:?}.",
-                name
-            ),
-        }
-    } else {
-        format_ident! { "{}_decoded", name.to_ascii_lowercase() }
-    }
+    format_ident! { "{}_decoded", name.to_ascii_lowercase() }
 }
 
 /// Given a `TypeDeclaration`, return name of the base of its typed path.
 ///
-/// `Vec<T>` returns `Vec`, `Option<T>` returns `Option`, `u8` returns `u8`, etc.
+/// `struct Vec` returns `Vec`, `enum Option` returns `Option`, `u8` returns `u8`, etc.
 pub fn derive_type_name(typ: &TypeDeclaration) -> String {
     if is_array_type(typ) {
         typ.type_field.clone()
@@ -166,21 +155,10 @@ pub fn is_fuel_primitive(typ: &TypeDeclaration) -> bool {
 
 /// Whether or not the given token is a Rust primitive
 fn is_rust_primitive(ty: &proc_macro2::TokenStream) -> bool {
-    let ident_str = ty.to_string();
-    match ident_str.as_str() {
-        "u8" | "u16" | "u32" | "u64" | "bool" | "String" => true,
-        _ => {
-            ident_str.starts_with('[')
-                && ident_str.ends_with(']')
-                && ident_str.contains(';')
-        }
-    }
-}
-
-/// Whether or not the given tokens are a generic type
-pub fn is_generic_type(typ: &TypeDeclaration) -> bool {
-    let gt = GenericType::from(typ);
-    matches!(gt, GenericType::Vec | GenericType::Option)
+    matches!(
+        ty.to_string().as_str(),
+        "u8" | "u16" | "u32" | "u64" | "bool"
+    )
 }
 
 /// Given a `TokenStream` representing this `TypeDeclaration`'s fully typed path,
@@ -197,7 +175,7 @@ pub fn decode_snippet(
         quote! {
             #ty_id => {
                 let obj: #type_tokens = bincode::deserialize(&data).expect("Bad bincode.");
-                self.#name.push(obj);
+                self.#name.push((obj, self.context.clone()));
             }
         }
     } else if is_rust_primitive(type_tokens) {
@@ -206,35 +184,6 @@ pub fn decode_snippet(
                 Logger::warn("Skipping primitive decoder.");
             }
         }
-    } else if is_generic_type(typ) {
-        let gt = GenericType::from(typ);
-        match gt {
-            GenericType::Vec => {
-                // https://github.com/FuelLabs/fuel-indexer/issues/503
-                quote! {
-                    #ty_id => {
-                        Logger::warn("Skipping unsupported vec decoder.");
-                    }
-                }
-            }
-            GenericType::Option => {
-                let (inner, typ) = inner_typedef(typ);
-                let inner = format_ident! { "{}", inner };
-                quote! {
-                    #ty_id => {
-                        let decoded = decoder.decode(&#typ::<#inner>::param_type(), &data)
-                            .with_context(|| format!("[codegen] Failed decoding {}", #type_field_string))?;
-                        let obj = #typ::<#inner>::from_token(decoded)
-                            .with_context(|| format!("[codegen] Failed detokenizing {}",  #type_field_string))?;
-                        self.#name.push(obj);
-                    }
-                }
-            }
-            _ => proc_macro_error::abort_call_site!(
-                "Decoder snippet is unsupported for generic type: {:?}.",
-                gt
-            ),
-        }
     } else {
         quote! {
             #ty_id => {
@@ -243,7 +192,7 @@ pub fn decode_snippet(
                     .with_context(|| format!("[codegen] Failed decoding {}", #type_field_string))?;
                 let obj = #type_tokens::from_token(decoded)
                     .with_context(|| format!("[codegen] Failed detokenizing {}", #type_field_string))?;
-                self.#name.push(obj);
+                self.#name.push((obj, self.context.clone()));
             }
         }
     }
@@ -267,25 +216,9 @@ impl Codegen for TypeDeclaration {
     }
 
     fn rust_tokens(&self) -> proc_macro2::TokenStream {
-        // Array works a bit differently where it's not a complex type (it's a rust primitive),
-        // but we still have to format each part of the array (type and size) separately.
-        if is_array_type(self) {
-            let name = derive_type_name(self).replace(['[', ']', ';'], "");
-            let mut iter = name.split(' ');
-            let ty = iter.next().expect("Malformed derived array type name.");
-            let size = iter
-                .next()
-                .expect("Array type name malformed.")
-                .parse::<usize>()
-                .expect("Could not parse array size.");
-
-            let ty = format_ident! { "{}", ty };
-
-            quote! { [#ty; #size] }
-        } else if self.components.is_some() {
+        if self.components.is_some() {
             let name = derive_type_name(self);
-            let ident = format_ident! { "{}", name };
-            quote! { #ident }
+            path_tokens(&name)
         } else {
             match self.type_field.as_str() {
                 "()" => quote! {},
@@ -306,12 +239,11 @@ impl Codegen for TypeDeclaration {
                 "ScriptResult" => quote! { ScriptResult },
                 "Transfer" => quote! { Transfer },
                 "TransferOut" => quote! { TransferOut },
+                "UndecodedLog" => quote! { UndecodedLog },
                 "u16" => quote! { u16 },
                 "u32" => quote! { u32 },
                 "u64" => quote! { u64 },
                 "u8" => quote! { u8 },
-                "str" => quote! { String },
-                o if o.starts_with("str[") => quote! { String },
                 o => {
                     proc_macro_error::abort_call_site!(
                         "Unrecognized primitive type: {:?}.",
@@ -792,7 +724,7 @@ pub fn can_derive_id(field_set: &HashSet<String>, field_name: &str) -> bool {
 /// Path separators are not allowed to be used as part of an identifier, so this
 /// function removes the qualifying path while keeping the type keyword.
 pub fn strip_callpath_from_type_field(mut typ: TypeDeclaration) -> TypeDeclaration {
-    if is_non_decodable_type(&typ) {
+    if is_non_decodable_type(&typ) || is_tuple_type(&typ) {
         return typ;
     }
 
@@ -810,203 +742,255 @@ pub fn strip_callpath_from_type_field(mut typ: TypeDeclaration) -> TypeDeclarati
     typ
 }
 
-/// Simply represents a value for a generic type.
-#[derive(Debug)]
-pub enum GenericType {
-    /// `Vec<T>`
-    Vec,
-
-    /// `Option<T>`
-    Option,
-    #[allow(unused)]
-    Other,
-}
-
-impl From<&TypeDeclaration> for GenericType {
-    fn from(t: &TypeDeclaration) -> Self {
-        Self::from(derive_type_name(t))
+/// Names of Sway standard library types, which are mapped to types provided by
+/// `fuels` rather than being generated from an ABI.
+const STD_TYPE_NAMES: [&str; 11] = [
+    "Address",
+    "AssetId",
+    "B512",
+    "Bytes",
+    "ContractId",
+    "EvmAddress",
+    "Identity",
+    "Option",
+    "RawBytes",
+    "Result",
+    "String",
+];
+
+/// Whether the given `TypeDeclaration` is a custom struct or enum, for which
+/// `fuels_code_gen` generates a Rust type.
+fn is_custom_abi_type(typ: &TypeDeclaration) -> bool {
+    let mut s = typ.type_field.split_whitespace();
+    match (s.next(), s.last()) {
+        (Some("struct" | "enum"), Some(path)) => {
+            let name = path.split("::").last().unwrap_or(path);
+            !path.starts_with("std::")
+                && !path.starts_with("core::")
+                && !GENERIC_STRUCTS.contains(name)
+                && !STD_TYPE_NAMES.contains(&name)
+        }
+        _ => false,
     }
 }
 
-impl From<String> for GenericType {
-    fn from(s: String) -> Self {
-        if s.starts_with("Vec") {
-            GenericType::Vec
-        } else if s.starts_with("Option") {
-            GenericType::Option
-        } else {
-            GenericType::Other
+/// Strip the call path from the type field of a `TypeDeclaration` and, if it is a
+/// custom type, prefix its name with the given namespace.
+///
+/// Types generated from an ABI listed under the manifest's `contracts` are placed
+/// in a module named after the contract (e.g., `struct Swap` becomes
+/// `struct router::Swap`).
+pub fn namespace_type_field(typ: TypeDeclaration, namespace: &str) -> TypeDeclaration {
+    let is_custom = is_custom_abi_type(&typ);
+    let mut typ = strip_callpath_from_type_field(typ);
+
+    if is_custom {
+        if let Some((keyword, name)) = typ.type_field.split_once(' ') {
+            typ.type_field = format!("{keyword} {namespace}::{name}");
         }
     }
+
+    typ
 }
 
-impl From<GenericType> for &str {
-    fn from(t: GenericType) -> Self {
-        match t {
-            GenericType::Vec => "Vec",
-            GenericType::Option => "Option",
-            GenericType::Other => unimplemented!("Generic type not implemented."),
-        }
+/// Add the given offset to every type ID referenced by a `TypeApplication`.
+fn offset_type_application(app: &mut TypeApplication, offset: usize) {
+    app.type_id += offset;
+    for arg in app.type_arguments.iter_mut().flatten() {
+        offset_type_application(arg, offset);
     }
 }
 
-impl From<&str> for GenericType {
-    fn from(s: &str) -> Self {
-        if s.starts_with("Vec") {
-            GenericType::Vec
-        } else if s.starts_with("Option") {
-            GenericType::Option
-        } else {
-            GenericType::Other
+/// Add the given offset to every type ID in the given ABI.
+///
+/// Type IDs are only unique within a single ABI, so the type IDs of each namespaced
+/// ABI are offset to keep them from colliding with those of other ABIs.
+pub fn offset_type_ids(abi: &mut ProgramABI, offset: usize) {
+    for typ in abi.types.iter_mut() {
+        typ.type_id += offset;
+        for component in typ.components.iter_mut().flatten() {
+            offset_type_application(component, offset);
+        }
+        for param in typ.type_parameters.iter_mut().flatten() {
+            *param += offset;
         }
     }
-}
 
-impl From<GenericType> for TokenStream {
-    fn from(t: GenericType) -> Self {
-        match t {
-            GenericType::Vec => quote! { Vec },
-            GenericType::Option => quote! { Option },
-            GenericType::Other => unimplemented!("Generic type not implemented."),
+    for function in abi.functions.iter_mut() {
+        for input in function.inputs.iter_mut() {
+            offset_type_application(input, offset);
         }
+        offset_type_application(&mut function.output, offset);
+    }
+
+    for log in abi.logged_types.iter_mut().flatten() {
+        offset_type_application(&mut log.application, offset);
+    }
+
+    for message in abi.messages_types.iter_mut().flatten() {
+        offset_type_application(&mut message.application, offset);
+    }
+
+    for configurable in abi.configurables.iter_mut().flatten() {
+        offset_type_application(&mut configurable.application, offset);
     }
 }
 
-/// Same as `derive_generic_inner_typedefs` but specifically for log types.
+/// Return the tokens for a type name that may be qualified by a namespace (e.g., `router::Swap`).
+pub fn path_tokens(name: &str) -> TokenStream {
+    let segments = name
+        .split("::")
+        .map(|segment| format_ident! { "{}", segment });
+    quote! { #(#segments)::* }
+}
+
+/// Resolve the Rust type of the given `TypeApplication`, including its type arguments
+/// and components (e.g., `Option<Ping>`, `(u64, [Bits256; 2])`, or `Pair<u8, bool>`).
 ///
-/// Where as `derive_generic_inner_typedefs` can return multiple inner types, this
-/// only returns the single inner type associated with this log specific logged type
-pub fn derive_log_generic_inner_typedefs<'a>(
-    typ: &'a LoggedType,
-    abi: &ProgramABI,
-    abi_types: &'a HashMap<usize, TypeDeclaration>,
-) -> &'a TypeDeclaration {
-    let result =
-        abi.logged_types
+/// Returns `None` if the type, or any type it is made of, can't be decoded.
+pub fn resolve_type_application(
+    app: &TypeApplication,
+    abi_types: &HashMap<usize, TypeDeclaration>,
+) -> Option<TokenStream> {
+    let typ = abi_types.get(&app.type_id)?;
+
+    if is_non_decodable_type(typ) {
+        return None;
+    }
+
+    if is_tuple_type(typ) || is_array_type(typ) {
+        let components = typ
+            .components
             .iter()
             .flatten()
-            .filter_map(|log| {
-                if log.log_id == typ.log_id && log.application.type_arguments.is_some() {
-                    let args = log
-                        .application
-                        .type_arguments
-                        .as_ref()
-                        .expect("No type args found for log application.");
-                    let inner = args.first().expect("No type args found.");
-                    return Some(abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                        panic!("Inner type not in ABI: {:?}", inner)
-                    }));
-                }
-                None
-            })
-            .collect::<Vec<_>>();
+            .map(|component| resolve_type_application(component, abi_types))
+            .collect::<Option<Vec<_>>>()?;
+
+        if is_array_type(typ) {
+            let inner = components.first()?;
+            let size = typ
+                .type_field
+                .trim_end_matches(']')
+                .rsplit(' ')
+                .next()?
+                .parse::<usize>()
+                .ok()?;
+            let size = Literal::usize_unsuffixed(size);
+            return Some(quote! { [#inner; #size] });
+        }
 
-    result.first().expect("No inner type found.")
+        return match &components[..] {
+            [component] => Some(quote! { (#component,) }),
+            _ => Some(quote! { (#(#components),*) }),
+        };
+    }
+
+    let args = app
+        .type_arguments
+        .iter()
+        .flatten()
+        .map(|arg| resolve_type_application(arg, abi_types))
+        .collect::<Option<Vec<_>>>()?;
+
+    match typ.type_field.as_str() {
+        "bool" | "u8" | "u16" | "u32" | "u64" => {
+            let ident = format_ident! { "{}", typ.type_field };
+            return Some(quote! { #ident });
+        }
+        "b256" => return Some(quote! { Bits256 }),
+        "str" => return Some(quote! { fuels::types::AsciiString }),
+        o if o.starts_with("str[") => {
+            let size = o
+                .trim_start_matches("str[")
+                .trim_end_matches(']')
+                .parse::<usize>()
+                .ok()?;
+            let size = Literal::usize_unsuffixed(size);
+            return Some(quote! { SizedAsciiString<#size> });
+        }
+        _ => {}
+    }
+
+    match (derive_type_name(typ).as_str(), &args[..]) {
+        ("Vec", [inner]) => Some(quote! { Vec<#inner> }),
+        ("Option", [inner]) => Some(quote! { Option<#inner> }),
+        ("Result", [ok, err]) => Some(quote! { Result<#ok, #err> }),
+        ("Bytes", []) => Some(quote! { fuels::types::Bytes }),
+        ("String", []) => Some(quote! { String }),
+        (name, []) if typ.components.is_some() => Some(path_tokens(name)),
+        (name, args) if typ.components.is_some() => {
+            let path = path_tokens(name);
+            Some(quote! { #path<#(#args),*> })
+        }
+        _ => None,
+    }
 }
 
-/// Derive the inner ident names for collections types.
-///
-/// Given a `GenericType`, and ABI JSON metadata, derive the inner `TypeDefinition`s associated with the given
-/// generic `TypeDefinition`.
+/// Return the name used to look up the given resolved type from a handler argument,
+/// which is its typed path without whitespace (e.g., `Option<router::Swap>`).
+pub fn resolved_type_name(tokens: &TokenStream) -> String {
+    tokens.to_string().replace(' ', "")
+}
+
+/// Derive the `Decoder` field ident for a composite type from its resolved type name.
 ///
-/// So this function will parse all function inputs/outputs and log types, find all generics (e.g., `Vec<T>`, `Option<U>`),
-/// and return the inner `TypeDefinition`s associated with those generics (e.g., `T` and `U`)
-pub fn derive_generic_inner_typedefs<'a>(
-    typ: &'a TypeDeclaration,
-    funcs: &[ABIFunction],
-    log_types: &[LoggedType],
-    abi_types: &'a HashMap<usize, TypeDeclaration>,
-) -> Vec<&'a TypeDeclaration> {
-    let name = typ
-        .type_field
-        .split(' ')
-        .last()
-        .expect("Type name derivation expects a space.");
-    let t = GenericType::from(name);
-
-    // Per Ahmed from fuels-rs:
-    //
-    // "So if you wish to see all the various Ts used with SomeStruct<T> (in this case Vec<T>)
-    // you have no choice but to go through all functions and find inputs/outputs that reference
-    // SomeStruct<T> and see what the typeArguments are. Those will replace the typeParameters inside
-    // the type declaration."
-    match t {
-        GenericType::Option | GenericType::Vec => {
-            let mut typs = funcs
-                .iter()
-                .flat_map(|func| {
-                    func.inputs
-                        .iter()
-                        .filter_map(|i| {
-                            if i.type_id == typ.type_id && i.type_arguments.is_some() {
-                                let args = i
-                                    .type_arguments
-                                    .as_ref()
-                                    .expect("No type args found for function input.");
-                                let inner = args.first().expect("No type args found.");
-                                return Some(
-                                    abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                                        panic!("Inner type not in ABI: {:?}", inner)
-                                    }),
-                                );
-                            }
-                            None
-                        })
-                        .collect::<Vec<_>>()
-                })
-                .collect::<Vec<_>>();
+/// E.g., `Option<Ping>` is decoded into `option_ping_decoded`, and `(u64, bool)`
+/// into `tuple_u64_bool_decoded`.
+pub fn composite_decoder_ident(name: &str) -> Ident {
+    let name = name
+        .replace("::", "_")
+        .replace('(', "tuple_")
+        .replace('[', "array_")
+        .replace(|c: char| !c.is_ascii_alphanumeric() && c != '_', "_")
+        .to_ascii_lowercase();
+    let name = name
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+
+    format_ident! { "{}_decoded", name }
+}
 
-            let mut output_typs = funcs
-                .iter()
-                .flat_map(|func| {
-                    if func.output.type_id == typ.type_id
-                        && func.output.type_arguments.is_some()
-                    {
-                        let args = func
-                            .output
-                            .type_arguments
-                            .as_ref()
-                            .expect("No type args found for function output.");
-                        let inner = args.first().expect("No type args found.");
-                        return Some(abi_types.get(&inner.type_id).unwrap_or_else(
-                            || panic!("Inner type not in ABI: {:?}", inner),
-                        ));
-                    }
-                    None
-                })
-                .collect::<Vec<_>>();
+/// Derive the type ID, type tokens, and `Decoder` field ident used to decode
+/// the given `TypeApplication`.
+///
+/// Composite types are identified by their resolved type name, since the same
+/// `TypeDeclaration` (e.g., `enum Option`) is shared by all of its applications.
+pub fn decoded_type_application(
+    app: &TypeApplication,
+    abi_types: &HashMap<usize, TypeDeclaration>,
+) -> Option<(usize, TokenStream, Ident)> {
+    let typ = abi_types.get(&app.type_id)?;
+
+    if is_composite_type(typ) {
+        let tokens = resolve_type_application(app, abi_types)?;
+        let name = resolved_type_name(&tokens);
+        let ty_id = type_id(FUEL_TYPES_NAMESPACE, &name) as usize;
+        Some((ty_id, tokens, composite_decoder_ident(&name)))
+    } else if is_non_decodable_type(typ) {
+        None
+    } else {
+        Some((typ.type_id, typ.rust_tokens(), typ.decoder_field_ident()))
+    }
+}
 
-            // Parse these as well because we will need to add them to our
-            // mapping of type IDs and `TypeDeclaration`s so we can use them when
-            // we parse log types.
-            let mut log_types = log_types
-                .iter()
-                .filter_map(|log| {
-                    if log.application.type_id == typ.type_id
-                        && log.application.type_arguments.is_some()
-                    {
-                        let args = log
-                            .application
-                            .type_arguments
-                            .as_ref()
-                            .expect("No type args found for log application.");
-                        let inner = args.first().expect("No type args found.");
-                        return Some(abi_types.get(&inner.type_id).unwrap_or_else(
-                            || panic!("Inner type not in ABI: {:?}", inner),
-                        ));
-                    }
-                    None
-                })
-                .collect::<Vec<_>>();
+/// Return the `match` arm for decoding a composite type in the `Decoder`.
+pub fn composite_decode_snippet(
+    ty_id: usize,
+    type_tokens: &TokenStream,
+    field: &Ident,
+) -> TokenStream {
+    let name = resolved_type_name(type_tokens);
 
-            typs.append(&mut output_typs);
-            typs.append(&mut log_types);
-            typs
+    quote! {
+        #ty_id => {
+            let decoded = decoder.decode(&<#type_tokens as Parameterize>::param_type(), &data)
+                .with_context(|| format!("[codegen] Failed decoding {}", #name))?;
+            let obj = <#type_tokens as Tokenizable>::from_token(decoded)
+                .with_context(|| format!("[codegen] Failed detokenizing {}", #name))?;
+            self.#field.push((obj, self.context.clone()));
         }
-        _ => proc_macro_error::abort_call_site!(
-            "Can't derive idents for unsupported generic type: {:?}",
-            t
-        ),
     }
 }
 
@@ -1027,6 +1011,13 @@ pub fn typed_path_name(p: &TypePath) -> String {
     }
 }
 
+/// Extract the full typed path, including any namespace, without whitespace.
+///
+/// E.g., `router::Swap` or `Option<router::Swap>`.
+pub fn qualified_typed_path_name(p: &TypePath) -> String {
+    quote! { #p }.to_string().replace(' ', "")
+}
+
 /// Extract the fully typed path for this generic type.
 ///
 /// When given a generic's `TypedPath` (e.g., `Vec<T>`), we need to extract the struct type (e.g., `Vec`)
@@ -1071,123 +1062,8 @@ fn typed_path_string(p: &TypePath) -> String {
     result
 }
 
-/// Retrieve the inner `T` from the given generic `TypeDefinition`s type field.
-///
-/// E.g., extrac `T` from `Vec<T>`
-pub fn inner_typedef(typ: &TypeDeclaration) -> (String, proc_macro2::TokenStream) {
-    let name = derive_type_name(typ);
-    let gt = GenericType::from(name.clone());
-    match gt {
-        GenericType::Vec => (name[4..name.len() - 1].to_string(), gt.into()),
-        GenericType::Option => (name[7..name.len() - 1].to_string(), gt.into()),
-        _ => proc_macro_error::abort_call_site!("Unsupported generic type: {:?}", gt),
-    }
-}
-
-/// Derive the output type ID for a given ABI function.
-///
-/// For non-generic `TypeDeclaration`s, we can just return the `type_id` associated with
-/// that function output. But for generic `TypeDeclaration`s, we need to derive the `type_id`
-/// using the fully typed path of the generic type (e.g., `Vec<T>`) in order to match
-/// the type ID of the `TypeDefinition` we manually inserted into the `#[indexer]` `abi_types_tyid`
-/// mapping.
-pub fn function_output_type_id(
-    f: &ABIFunction,
-    abi_types: &HashMap<usize, TypeDeclaration>,
-) -> usize {
-    let outer_typ = abi_types.get(&f.output.type_id).unwrap_or_else(|| {
-        panic!(
-            "function_output_type_id: Type with TypeID({}) is missing from the JSON ABI",
-            f.output.type_id
-        )
-    });
-    if is_generic_type(outer_typ) {
-        let name = derive_type_name(outer_typ);
-        let gt = GenericType::from(name);
-        let inner = f
-            .output
-            .type_arguments
-            .as_ref()
-            .expect("Missing type arguments.")
-            .first()
-            .expect("Missing inner type.");
-
-        match gt {
-            GenericType::Option | GenericType::Vec => {
-                let inner_typ = abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                    panic!("function_output_type_id: Generic inner type with TypeID({}) is missing from the JSON ABI", inner.type_id)
-                });
-                let (typ_name, _) =
-                    typed_path_components(outer_typ, inner_typ, abi_types);
-                type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize
-            }
-            _ => proc_macro_error::abort_call_site!(
-                "Unsupported generic type for function outputs: {:?}",
-                gt
-            ),
-        }
-    } else {
-        f.output.type_id
-    }
-}
-
-/// Given two `TypeDeclaration`s for a generic struct (e.g., `Vec`) and an inner type (e.g., `T`),
-/// return the associated fully typed path. (e.g., `Vec<T>`)
-///
-/// We do this by recursively deriving the inner `T` for the provided inner `T`, so long as
-/// the inner `T` is itself, generic.
-pub fn typed_path_components(
-    outer: &TypeDeclaration,
-    inner: &TypeDeclaration,
-    abi_types: &HashMap<usize, TypeDeclaration>,
-) -> (String, TokenStream) {
-    let outer_name = derive_type_name(outer);
-    let outer_ident = format_ident! { "{}", outer_name };
-    let inner_name = derive_type_name(inner);
-    let inner_ident = format_ident! { "{}", inner_name };
-    let mut tokens = quote! { #inner_ident };
-
-    let mut curr = inner;
-    while is_generic_type(curr) {
-        let gt = GenericType::from(curr);
-        match gt {
-            GenericType::Option | GenericType::Vec => {
-                curr = abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                    panic!("typed_path_components: Generic inner type with TypeID({}) is missing from the JSON ABI", inner.type_id)
-                });
-                let name = derive_type_name(curr);
-                let ident = format_ident! { "{}", name };
-                tokens = quote! { #tokens<#ident> }
-            }
-            _ => proc_macro_error::abort_call_site!(
-                "Unsupported generic type for typed path: {:?}",
-                gt
-            ),
-        }
-    }
-
-    tokens = quote! { #outer_ident<#tokens> };
-
-    // Remove white space from path`
-    let name = tokens.to_string().replace(' ', "");
-
-    (name, tokens)
-}
-
 pub fn is_array_type(typ: &TypeDeclaration) -> bool {
     typ.type_field.starts_with('[')
         && typ.type_field.ends_with(']')
         && typ.type_field.contains(';')
 }
-
-/// Determine whether or not the given type name is an unsupported type.
-///
-/// Since we allow unsupported types in the ABI JSON, this check is only
-/// performed on indexer handler function arg typed paths.
-pub fn is_unsupported_type(type_name: &str) -> bool {
-    let gt = GenericType::from(type_name);
-    match gt {
-        GenericType::Vec => UNSUPPORTED_ABI_JSON_TYPES.contains(gt.into()),
-        _ => UNSUPPORTED_ABI_JSON_TYPES.contains(type_name),
-    }
-}
@@ -1,19 +1,26 @@
 use crate::{
-    helpers::*, parse::IndexerConfig, schema::process_graphql_schema,
-    wasm::handler_block_wasm,
+    helpers::*, native::handler_block_native, parse::IndexerConfig,
+    schema::process_graphql_schema, wasm::handler_block_wasm,
 };
-use fuel_abi_types::abi::program::TypeDeclaration;
+use convert_case::{Case, Casing};
+use fuel_abi_types::abi::program::{ProgramABI, TypeDeclaration};
 use fuel_indexer_lib::{
-    constants::*, manifest::ContractIds, manifest::Manifest,
+    constants::*,
+    manifest::{Manifest, Module},
     utils::workspace_manifest_prefix,
 };
 use fuel_indexer_types::{type_id, FUEL_TYPES_NAMESPACE};
-use fuels::{core::codec::resolve_fn_selector, types::param_types::ParamType};
+use fuels::{
+    core::codec::resolve_fn_selector,
+    types::{bech32::Bech32ContractId, param_types::ParamType, ContractId},
+};
 use fuels_code_gen::{Abigen, AbigenTarget, ProgramType};
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use syn::{parse_macro_input, FnArg, Item, ItemMod, PatType, Type};
 
 fn additional_declarations() -> proc_macro2::TokenStream {
@@ -22,14 +29,109 @@ fn additional_declarations() -> proc_macro2::TokenStream {
         type b256 = [u8; 32];
         type Bytes = Vec<u8>;
         type B512 = [u8; 64];
+
+        /// Where the data passed to a handler came from.
+        #[derive(Debug, Clone, Default)]
+        pub struct Context {
+            /// Height of the block.
+            pub block_height: u32,
+
+            /// Time of the block, as a UNIX timestamp.
+            pub block_time: i64,
+
+            /// ID of the transaction, if the data came from a transaction.
+            pub tx_id: Option<TxId>,
+
+            /// Index of the transaction in the block.
+            pub tx_index: Option<usize>,
+
+            /// Index of the receipt in the transaction, if the data came from a receipt.
+            pub receipt_index: Option<usize>,
+
+            /// ID of the contract that produced the receipt. For a `Call`, this is the
+            /// contract being called.
+            pub contract_id: Option<ContractId>,
+
+            /// Owner of the first coin, or sender of the first message, spent by the transaction.
+            pub sender: Option<Address>,
+        }
     }
 }
 
+/// Kind of Sway program described by an `IndexerAbi`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProgramKind {
+    Contract,
+    Script,
+    Predicate,
+}
+
+/// An ABI whose types and receipts are decoded by the indexer.
+struct IndexerAbi {
+    /// Module in which the ABI's types are generated, if they are namespaced.
+    namespace: Option<String>,
+
+    /// The parsed JSON ABI.
+    abi: ProgramABI,
+
+    /// Kind of program described by this ABI.
+    program: ProgramKind,
+
+    /// Contract IDs whose receipts are decoded with this ABI.
+    contract_ids: Vec<String>,
+
+    /// SHA-256 hash of the script or predicate bytecode decoded with this ABI. If
+    /// not set, every script or predicate is decoded with this ABI.
+    bytecode_hash: Option<[u8; 32]>,
+}
+
+/// Offset added to the type IDs of each namespaced ABI, so that type IDs from
+/// different ABIs don't collide.
+const NAMESPACED_ABI_TYPE_ID_OFFSET: usize = 1 << 24;
+
+/// Strip the call path from the types of an ABI and, if the ABI is namespaced,
+/// prefix its custom types with the namespace and offset its type IDs.
+fn prepare_abi(mut abi: ProgramABI, namespace: Option<&str>, index: usize) -> ProgramABI {
+    abi.types = abi
+        .types
+        .into_iter()
+        .map(|typ| match namespace {
+            Some(namespace) => namespace_type_field(typ, namespace),
+            None => strip_callpath_from_type_field(typ),
+        })
+        .collect();
+
+    if namespace.is_some() {
+        offset_type_ids(&mut abi, index * NAMESPACED_ABI_TYPE_ID_OFFSET);
+    }
+
+    abi
+}
+
+/// Parse a manifest contract ID (either `Bech32` or hex) into bytes.
+fn contract_id_bytes(id: &str) -> [u8; 32] {
+    let contract_id = Bech32ContractId::from_str(id)
+        .map(ContractId::from)
+        .or_else(|_| ContractId::from_str(id))
+        .unwrap_or_else(|_| {
+            proc_macro_error::abort_call_site!(
+                "Failed to parse manifest 'contract_id': {:?}.",
+                id
+            )
+        });
+
+    <[u8; 32]>::from(contract_id)
+}
+
 fn process_fn_items(
     manifest: &Manifest,
-    abi_path: Option<String>,
+    abis: Vec<IndexerAbi>,
     indexer_module: ItemMod,
-) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    Vec<proc_macro2::TokenStream>,
+) {
     if indexer_module.content.is_none()
         || indexer_module
             .content
@@ -43,22 +145,21 @@ fn process_fn_items(
         )
     }
 
-    let abi = get_json_abi(abi_path).unwrap_or_default();
+    let abis = abis
+        .into_iter()
+        .enumerate()
+        .map(|(i, indexer_abi)| IndexerAbi {
+            abi: prepare_abi(indexer_abi.abi, indexer_abi.namespace.as_deref(), i),
+            ..indexer_abi
+        })
+        .collect::<Vec<_>>();
 
     let mut decoded_type_snippets = HashSet::new();
     let mut decoded_log_match_arms = HashSet::new();
     let mut decoded_type_fields = HashSet::new();
+    let mut decoded_field_idents = HashSet::new();
     let mut abi_dispatchers = Vec::new();
 
-    let funcs = abi.clone().functions;
-    let abi_types: Vec<TypeDeclaration> = abi
-        .clone()
-        .types
-        .iter()
-        .map(|t| strip_callpath_from_type_field(t.clone()))
-        .collect();
-    let abi_log_types = abi.clone().logged_types.unwrap_or_default();
-    let abi_msg_types = abi.clone().messages_types.unwrap_or_default();
     let fuel_types = FUEL_PRIMITIVES
         .iter()
         .map(|x| {
@@ -73,238 +174,386 @@ fn process_fn_items(
         })
         .collect::<HashMap<usize, TypeDeclaration>>();
 
-    let _tuple_types_tyid = abi_types
-        .iter()
-        .filter_map(|typ| {
-            if is_tuple_type(typ) {
-                return Some((typ.type_id, typ.clone()));
-            }
-
-            None
-        })
-        .collect::<HashMap<usize, TypeDeclaration>>();
-
     // Used to do a reverse lookup of typed path names to ABI type IDs.
     let mut type_ids = RESERVED_TYPEDEF_NAMES
         .iter()
         .map(|x| (x.to_string(), type_id(FUEL_TYPES_NAMESPACE, x) as usize))
         .collect::<HashMap<String, usize>>();
 
-    let mut abi_types_tyid = abi_types
-        .iter()
-        .map(|typ| (typ.type_id, typ.clone()))
-        .collect::<HashMap<usize, TypeDeclaration>>();
+    let mut abi_types_tyid = HashMap::new();
+    let mut abi_type_decoders = Vec::new();
+    let mut abi_struct_fields = Vec::new();
+
+    // Decoder fields of composite types, which have no `TypeDeclaration` of their own.
+    let mut composite_fields = HashMap::new();
+
+    // The following are collected separately for each ABI, since log IDs, message
+    // IDs, and function selectors are only unique within a single ABI. Receipts are
+    // decoded using the ABI of the contract that emitted them.
+    let mut log_type_decoders = Vec::new();
+    let mut decoded_log_ids = Vec::new();
+    let mut message_types_decoders = Vec::new();
+    let mut abi_selectors = Vec::new();
+    let mut abi_selectors_to_fn_names = Vec::new();
+    let mut call_decoders = Vec::new();
+
+    // Call types generated for each ABI, which are placed alongside that ABI's types.
+    let mut call_types = Vec::new();
+
+    for IndexerAbi {
+        abi,
+        namespace,
+        program,
+        ..
+    } in abis.iter()
+    {
+        let funcs = abi.clone().functions;
+        let abi_types = abi.types.clone();
+        let abi_log_types = abi.clone().logged_types.unwrap_or_default();
+        let abi_msg_types = abi.clone().messages_types.unwrap_or_default();
 
-    let message_types_decoders = abi_msg_types
-        .iter()
-        .map(|typ| {
-            let message_type_id = typ.message_id;
-            let ty_id = typ.application.type_id;
+        abi_types_tyid.extend(abi_types.iter().map(|typ| (typ.type_id, typ.clone())));
 
-            quote! {
-                #message_type_id => {
-                    self.decode_type(#ty_id, data)?;
-                }
-            }
-        })
-        .chain(vec![quote! {
-            u64::MAX => {
-                {}
-            }
-        }])
-        .collect::<Vec<proc_macro2::TokenStream>>();
-
-    // Take a second pass over `abi_types` in order to update an `TypeDeclarations` that need to
-    // be updated with more information for the codegen process.
-    let abi_types = abi_types
-        .iter()
-        .map(|typ| {
-            // If this is an array type we have to manually update it's type field to include its inner
-            // type so that the rest of the codegen will work with minimal changes.
-            if is_array_type(typ) {
-                let inner = typ
-                    .components
-                    .as_ref()
-                    .expect("Array type expects inner components")
-                    .first()
-                    .expect("Array type expects at least one inner component")
-                    .type_id;
-                let size = typ
-                    .type_field
-                    .split(' ')
-                    .last()
-                    .expect(
-                        "Array type has unexpected type field format. Expected [u8; 32]",
-                    )
-                    .trim_end_matches(']')
-                    .parse::<usize>()
-                    .expect("Array type size could not be determined.");
-                let inner = abi_types_tyid
-                    .get(&inner)
-                    .expect("Array type inner not found in ABI types.");
-                let name = format!("[{}; {}]", inner.name(), size);
-                let typ = TypeDeclaration {
-                    type_field: name,
-                    ..typ.clone()
-                };
+        message_types_decoders.push(
+            abi_msg_types
+                .iter()
+                .map(|typ| {
+                    let message_type_id = typ.message_id;
+                    let ty_id =
+                        decoded_type_application(&typ.application, &abi_types_tyid)
+                            .map(|(ty_id, ..)| ty_id)
+                            .unwrap_or(typ.application.type_id);
 
-                abi_types_tyid.insert(typ.type_id, typ.clone());
-                typ
-            } else {
-                typ.to_owned()
-            }
-        })
-        .collect::<Vec<TypeDeclaration>>();
+                    quote! {
+                        #message_type_id => {
+                            self.decode_type(#ty_id, data)?;
+                        }
+                    }
+                })
+                .chain(vec![quote! {
+                    u64::MAX => {
+                        {}
+                    }
+                }])
+                .collect::<Vec<proc_macro2::TokenStream>>(),
+        );
 
-    let abi_type_decoders = abi_types
-        .iter()
-        .filter_map(|typ| {
-            if is_non_decodable_type(typ) {
+        abi_type_decoders.extend(abi_types.iter().filter_map(|typ| {
+            if is_non_decodable_type(typ) || is_composite_type(typ) {
                 return None;
             }
 
-            if is_fuel_primitive(typ) {
+            if is_fuel_primitive(typ) || typ.name() == "Context" {
                 proc_macro_error::abort_call_site!(
                     "'{}' is a reserved Fuel type.",
                     typ.name()
                 )
             }
 
-            if is_generic_type(typ) {
-                let gt = GenericType::from(typ);
-                match gt {
-                    GenericType::Vec | GenericType::Option => {
-                        let ab_types = abi_types_tyid.clone();
-                        let inner_typs = derive_generic_inner_typedefs(
-                            typ,
-                            &funcs,
-                            &abi_log_types,
-                            &ab_types,
-                        );
-
-                        return Some(
-                            inner_typs
-                                .iter()
-                                .filter_map(|inner_typ| {
-                                    let (typ_name, type_tokens) = typed_path_components(
-                                        typ,
-                                        inner_typ,
-                                        &abi_types_tyid,
-                                    );
-                                    let ty_id =
-                                        type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize;
-
-                                    let typ = TypeDeclaration {
-                                        type_id: ty_id,
-                                        type_field: typ_name.clone(),
-                                        ..typ.clone()
-                                    };
+            let type_tokens = typ.rust_tokens();
+            type_ids.insert(type_tokens.to_string(), typ.type_id);
+            decoded_type_snippets.insert(typ.type_id);
+            Some(decode_snippet(&type_tokens, typ))
+        }));
 
-                                    if decoded_type_snippets.contains(&ty_id) {
-                                        return None;
-                                    }
+        abi_struct_fields.extend(abi_types.iter().filter_map(|typ| {
+            if is_non_decodable_type(typ) || is_composite_type(typ) {
+                return None;
+            }
 
-                                    abi_types_tyid.insert(ty_id, typ.clone());
-                                    type_ids.insert(typ_name.clone(), ty_id);
+            let ident = typ.decoder_field_ident();
+            let type_tokens = typ.rust_tokens();
+            type_ids.insert(typ.rust_tokens().to_string(), typ.type_id);
+            decoded_type_fields.insert(typ.type_id);
 
-                                    decoded_type_snippets.insert(ty_id);
+            // Types that aren't namespaced (e.g., `u64`) can appear in more
+            // than one ABI, but are decoded into the same field.
+            if !decoded_field_idents.insert(ident.to_string()) {
+                return None;
+            }
 
-                                    Some(decode_snippet(&type_tokens, &typ))
-                                })
-                                .collect::<Vec<proc_macro2::TokenStream>>(),
-                        );
-                    }
-                    _ => unimplemented!("Unsupported decoder generic type: {:?}", gt),
-                }
-            } else {
-                let type_tokens = typ.rust_tokens();
-                type_ids.insert(type_tokens.to_string(), typ.type_id);
-                decoded_type_snippets.insert(typ.type_id);
-                Some(vec![decode_snippet(&type_tokens, typ)])
+            Some(quote! {
+                #ident: Vec<(#type_tokens, Context)>
+            })
+        }));
+
+        // Composite types (e.g., tuples or `Option<T>`) get a decoder and a field for
+        // each of their applications that can be logged, returned, or sent in a message,
+        // as well as for the `main` arguments of scripts and predicates.
+        let main_inputs = funcs
+            .iter()
+            .filter(|_| *program != ProgramKind::Contract)
+            .flat_map(|function| function.inputs.iter());
+        let composite_apps = abi_log_types
+            .iter()
+            .map(|log| &log.application)
+            .chain(funcs.iter().map(|function| &function.output))
+            .chain(abi_msg_types.iter().map(|msg| &msg.application))
+            .chain(main_inputs);
+
+        for app in composite_apps {
+            let typ = abi_types_tyid
+                .get(&app.type_id)
+                .expect("Could not get type reference from ABI types.");
+
+            if !is_composite_type(typ) {
+                continue;
             }
-        })
-        .flatten()
-        .collect::<Vec<proc_macro2::TokenStream>>();
 
-    let fuel_type_decoders = fuel_types
-        .values()
-        .map(|typ| {
-            let type_tokens = typ.rust_tokens();
+            let (ty_id, type_tokens, ident) =
+                match decoded_type_application(app, &abi_types_tyid) {
+                    Some(decoded) => decoded,
+                    None => continue,
+                };
 
-            type_ids.insert(type_tokens.to_string(), typ.type_id);
-            decoded_type_snippets.insert(typ.type_id);
+            if !decoded_type_snippets.insert(ty_id) {
+                continue;
+            }
 
-            decode_snippet(&type_tokens, typ)
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
+            type_ids.insert(resolved_type_name(&type_tokens), ty_id);
+            composite_fields.insert(ty_id, ident.clone());
+            decoded_type_fields.insert(ty_id);
+            decoded_field_idents.insert(ident.to_string());
 
-    let decoders = [fuel_type_decoders, abi_type_decoders].concat();
+            abi_type_decoders.push(composite_decode_snippet(ty_id, &type_tokens, &ident));
+            abi_struct_fields.push(quote! {
+                #ident: Vec<(#type_tokens, Context)>
+            });
+        }
 
-    let abi_struct_fields = abi_types
-        .iter()
-        .filter_map(|typ| {
-            if is_non_decodable_type(typ) {
-                return None;
-            }
+        log_type_decoders.push(
+            abi_log_types
+                .iter()
+                .filter_map(|log| {
+                    let log_id = log.log_id as usize;
+                    let (ty_id, ..) =
+                        decoded_type_application(&log.application, &abi_types_tyid)?;
 
-            if is_fuel_primitive(typ) {
-                proc_macro_error::abort_call_site!(
-                    "'{}' is a reserved Fuel type.",
-                    typ.name()
-                )
-            }
+                    decoded_log_match_arms.insert(log_id);
+
+                    Some(quote! {
+                        #log_id => {
+                            self.decode_type(#ty_id, data)?;
+                        }
+                    })
+                })
+                .collect::<Vec<proc_macro2::TokenStream>>(),
+        );
 
-            if is_generic_type(typ) {
-                let inner_typs = derive_generic_inner_typedefs(
-                    typ,
-                    &funcs,
-                    &abi_log_types,
-                    &abi_types_tyid,
-                );
+        decoded_log_ids.push(
+            abi_log_types
+                .iter()
+                .filter(|log| {
+                    decoded_type_application(&log.application, &abi_types_tyid).is_some()
+                })
+                .map(|log| log.log_id)
+                .collect::<Vec<_>>(),
+        );
 
-                return Some(
-                    inner_typs
+        abi_selectors.push(
+            funcs
+                .iter()
+                .map(|function| {
+                    let params: Vec<ParamType> = function
+                        .inputs
                         .iter()
-                        .filter_map(|inner_typ| {
-                            let (typ_name, type_tokens) =
-                                typed_path_components(typ, inner_typ, &abi_types_tyid);
-                            let ty_id = type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize;
+                        .map(|x| {
+                            ParamType::try_from_type_application(x, &abi_types_tyid)
+                                .expect("Could not derive TypeApplication param types.")
+                        })
+                        .collect();
+                    let sig = resolve_fn_selector(&function.name, &params[..]);
+                    let selector = u64::from_be_bytes(sig);
+                    let ty_id =
+                        decoded_type_application(&function.output, &abi_types_tyid)
+                            .map(|(ty_id, ..)| ty_id)
+                            .unwrap_or(function.output.type_id);
 
-                            if decoded_type_fields.contains(&ty_id) {
-                                return None;
-                            }
+                    quote! {
+                        #selector => #ty_id,
+                    }
+                })
+                .collect::<Vec<proc_macro2::TokenStream>>(),
+        );
 
-                            let typ = TypeDeclaration {
-                                type_id: ty_id,
-                                type_field: typ_name.clone(),
-                                ..typ.clone()
-                            };
+        abi_selectors_to_fn_names.push(
+            funcs
+                .iter()
+                .map(|function| {
+                    let params: Vec<ParamType> = function
+                        .inputs
+                        .iter()
+                        .map(|x| {
+                            ParamType::try_from_type_application(x, &abi_types_tyid)
+                                .expect("Could not derive TypeApplication param types.")
+                        })
+                        .collect();
+                    let sig = resolve_fn_selector(&function.name, &params[..]);
+                    let fn_name = function.name.clone();
+                    let selector = u64::from_be_bytes(sig);
 
-                            let ident = typ.decoder_field_ident();
+                    quote! {
+                       #selector => #fn_name.to_string(),
+                    }
+                })
+                .collect::<Vec<proc_macro2::TokenStream>>(),
+        );
 
-                            type_ids.insert(typ_name.clone(), ty_id);
-                            decoded_type_fields.insert(ty_id);
+        // Each contract function gets a `<Function>Call` type holding its decoded
+        // arguments, so that handlers can subscribe to calls of a specific function.
+        let mut abi_call_types = Vec::new();
+        let mut abi_call_decoders = Vec::new();
 
-                            Some(quote! {
-                                #ident: Vec<#type_tokens>
-                            })
-                        })
-                        .collect::<Vec<proc_macro2::TokenStream>>(),
-                );
-            } else {
-                let ident = typ.decoder_field_ident();
-                let type_tokens = typ.rust_tokens();
-                type_ids.insert(typ.rust_tokens().to_string(), typ.type_id);
-                decoded_type_fields.insert(typ.type_id);
+        for function in funcs.iter().filter(|_| *program == ProgramKind::Contract) {
+            let inputs = function
+                .inputs
+                .iter()
+                .map(|input| {
+                    let typ = abi_types_tyid.get(&input.type_id)?;
+                    if input.name == "call" {
+                        return None;
+                    }
 
-                Some(vec![quote! {
-                    #ident: Vec<#type_tokens>
-                }])
+                    let tokens = resolve_type_application(input, &abi_types_tyid)?;
+
+                    Some((
+                        format_ident! { "{}", input.name },
+                        tokens,
+                        typ.type_field.clone(),
+                    ))
+                })
+                .collect::<Option<Vec<_>>>();
+
+            // Functions with arguments that can't be decoded don't get a call type.
+            let inputs = match inputs {
+                Some(inputs) => inputs,
+                None => continue,
+            };
+
+            let name = format!("{}Call", function.name.to_case(Case::Pascal));
+            let qualified_name = match namespace {
+                Some(namespace) => format!("{namespace}::{name}"),
+                None => name.clone(),
+            };
+
+            if type_ids.contains_key(&qualified_name) {
+                continue;
+            }
+
+            let typ = TypeDeclaration {
+                type_id: type_id(FUEL_TYPES_NAMESPACE, &qualified_name) as usize,
+                type_field: format!("struct {qualified_name}"),
+                components: Some(Vec::new()),
+                type_parameters: None,
+            };
+            let field = typ.decoder_field_ident();
+            let ident = format_ident! { "{}", name };
+            let path = path_tokens(&qualified_name);
+
+            type_ids.insert(qualified_name, typ.type_id);
+            abi_types_tyid.insert(typ.type_id, typ.clone());
+            abi_struct_fields.push(quote! {
+                #field: Vec<(#path, Context)>
+            });
+
+            let names = inputs.iter().map(|(name, ..)| name).collect::<Vec<_>>();
+            let tys = inputs.iter().map(|(_, ty, _)| ty).collect::<Vec<_>>();
+
+            abi_call_types.push(quote! {
+                #[derive(Debug, Clone)]
+                pub struct #ident {
+                    pub call: Call,
+                    #(pub #names: #tys),*
+                }
+            });
+
+            let params: Vec<ParamType> = function
+                .inputs
+                .iter()
+                .map(|x| {
+                    ParamType::try_from_type_application(x, &abi_types_tyid)
+                        .expect("Could not derive TypeApplication param types.")
+                })
+                .collect();
+            let selector =
+                u64::from_be_bytes(resolve_fn_selector(&function.name, &params[..]));
+            let fn_name = function.name.clone();
+
+            if names.is_empty() {
+                abi_call_decoders.push(quote! {
+                    #selector => {
+                        self.#field.push((#path { call }, self.context.clone()));
+                    }
+                });
+                continue;
             }
+
+            // A single argument of a copy type is passed in `param2` itself. Otherwise,
+            // `param2` points to the encoded arguments in the script data.
+            let is_copy_type = match &inputs[..] {
+                [(_, _, type_field)] => {
+                    ["u8", "u16", "u32", "u64", "bool"].contains(&type_field.as_str())
+                }
+                _ => false,
+            };
+
+            let data = if is_copy_type {
+                quote! {
+                    let data = param2.to_be_bytes().to_vec();
+                }
+            } else {
+                quote! {
+                    let offset = call_data_address
+                        .and_then(|address| (param2 as usize).checked_sub(address))
+                        .filter(|offset| *offset < call_data.len());
+
+                    let data = match offset {
+                        Some(offset) => call_data[offset..].to_vec(),
+                        None => anyhow::bail!("Arguments of {} are not in the script data", #fn_name),
+                    };
+                }
+            };
+
+            abi_call_decoders.push(quote! {
+                #selector => {
+                    #data
+
+                    let decoder = ABIDecoder::default();
+                    let param_types = [#(<#tys as Parameterize>::param_type()),*];
+                    let tokens = decoder.decode_multiple(&param_types, &data)
+                        .with_context(|| format!("[codegen] Failed decoding arguments of {}", #fn_name))?;
+                    let mut tokens = tokens.into_iter();
+
+                    let obj = #path {
+                        call,
+                        #(
+                            #names: <#tys as Tokenizable>::from_token(tokens.next().expect("Missing decoded argument."))
+                                .with_context(|| format!("[codegen] Failed detokenizing arguments of {}", #fn_name))?
+                        ),*
+                    };
+                    self.#field.push((obj, self.context.clone()));
+                }
+            });
+        }
+
+        call_types.push(quote! { #(#abi_call_types)* });
+        call_decoders.push(abi_call_decoders);
+    }
+
+    let fuel_type_decoders = fuel_types
+        .values()
+        .map(|typ| {
+            let type_tokens = typ.rust_tokens();
+
+            type_ids.insert(type_tokens.to_string(), typ.type_id);
+            decoded_type_snippets.insert(typ.type_id);
+
+            decode_snippet(&type_tokens, typ)
         })
-        .flatten()
         .collect::<Vec<proc_macro2::TokenStream>>();
 
+    let decoders = [fuel_type_decoders, abi_type_decoders].concat();
+
     let fuel_struct_fields = fuel_types
         .iter()
         .filter_map(|(_ty_id, typ)| {
@@ -323,106 +572,425 @@ fn process_fn_items(
             }
 
             Some(quote! {
-                #name: Vec<#ty>
+                #name: Vec<(#ty, Context)>
             })
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
     let decoder_struct_fields = [abi_struct_fields, fuel_struct_fields].concat();
 
-    // Since log type decoders use `TypeDeclaration`s that were manually created specifically
-    // for generics, we parsed log types after other ABI types.
-    let log_type_decoders = abi_log_types
-        .iter()
-        .filter_map(|log| {
-            let ty_id = log.application.type_id;
-            let log_id = log.log_id as usize;
-            let typ = abi_types_tyid
-                .get(&log.application.type_id)
-                .expect("Could not get log type reference from ABI types.");
+    // Handler arguments are looked up by their typed path, without whitespace
+    // (e.g., `router::Swap`).
+    let type_ids = type_ids
+        .into_iter()
+        .map(|(name, ty_id)| (name.replace(' ', ""), ty_id))
+        .collect::<HashMap<String, usize>>();
 
-            if is_non_decodable_type(typ) {
-                return None;
+    // Index of the ABI used to decode receipts from the given contract. The first ABI is
+    // the manifest's `abi`, which decodes any contract not listed under `contracts`.
+    let contract_abi_checks = abis
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, indexer_abi)| {
+            indexer_abi.program == ProgramKind::Contract
+                && !indexer_abi.contract_ids.is_empty()
+        })
+        .map(|(i, indexer_abi)| {
+            let ids = indexer_abi.contract_ids.iter().map(|id| {
+                let bytes = contract_id_bytes(id);
+                quote! { [#(#bytes),*] }
+            });
+            quote! {
+                if [#(#ids),*].contains(contract_id) {
+                    return #i;
+                }
             }
+        })
+        .collect::<Vec<proc_macro2::TokenStream>>();
 
-            if is_generic_type(typ) {
-                let gt = GenericType::from(typ);
-                match gt {
-                    GenericType::Vec | GenericType::Option => {
-                        let inner_typ =
-                            derive_log_generic_inner_typedefs(log, &abi, &abi_types_tyid);
-
-                        let (typ_name, _) =
-                            typed_path_components(typ, inner_typ, &abi_types_tyid);
-
-                        let ty_id = type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize;
-                        let _typ = abi_types_tyid.get(&ty_id).expect(
-                            "Could not get generic log type reference from ABI types.",
-                        );
-
-                        decoded_log_match_arms.insert(log_id);
-
-                        Some(quote! {
-                            #log_id => {
-                                self.decode_type(#ty_id, data)?;
-                            }
-                        })
+    // A namespaced ABI without contract IDs decodes receipts from contracts that
+    // are watched at runtime (i.e., any contract not listed in the manifest).
+    let watched_contract_abi = abis
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_, indexer_abi)| {
+            indexer_abi.program == ProgramKind::Contract
+                && indexer_abi.contract_ids.is_empty()
+        })
+        .map(|(i, _)| {
+            if abis[0].contract_ids.is_empty() {
+                quote! { return #i; }
+            } else {
+                let ids = abis[0].contract_ids.iter().map(|id| {
+                    let bytes = contract_id_bytes(id);
+                    quote! { [#(#bytes),*] }
+                });
+                quote! {
+                    if ![#(#ids),*].contains(contract_id) {
+                        return #i;
                     }
-                    _ => unimplemented!("Unsupported decoder generic type: {:?}", gt),
                 }
-            } else {
-                decoded_log_match_arms.insert(log_id);
-
-                Some(quote! {
-                    #log_id => {
-                        self.decode_type(#ty_id, data)?;
+            }
+        });
+
+    // Scripts and predicates are matched by the hash of their bytecode, and their
+    // `main` arguments are decoded from the script data or predicate data.
+    let program_abi_checks = |program: ProgramKind| {
+        let programs = abis
+            .iter()
+            .enumerate()
+            .filter(|(_, indexer_abi)| indexer_abi.program == program)
+            .collect::<Vec<_>>();
+
+        let mut checks = programs
+            .iter()
+            .filter_map(|(i, indexer_abi)| {
+                indexer_abi.bytecode_hash.map(|hash| {
+                    quote! {
+                        if hash == [#(#hash),*] {
+                            return Some(#i);
+                        }
                     }
                 })
-            }
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
+            })
+            .collect::<Vec<proc_macro2::TokenStream>>();
+
+        let hash = if checks.is_empty() {
+            quote! { let _ = bytecode; }
+        } else {
+            quote! { let hash: [u8; 32] = Sha256::digest(bytecode).into(); }
+        };
+        checks.insert(0, hash);
+
+        // A program without bytecode matches any bytecode, so it is checked last.
+        if let Some((i, _)) = programs
+            .iter()
+            .find(|(_, indexer_abi)| indexer_abi.bytecode_hash.is_none())
+        {
+            checks.push(quote! { Some(#i) });
+        } else {
+            checks.push(quote! { None });
+        }
+
+        checks
+    };
 
-    let abi_selectors = funcs
+    let script_abi_checks = program_abi_checks(ProgramKind::Script);
+    let predicate_abi_checks = program_abi_checks(ProgramKind::Predicate);
+    let has_scripts = abis
         .iter()
-        .map(|function| {
-            let params: Vec<ParamType> = function
+        .any(|indexer_abi| indexer_abi.program == ProgramKind::Script);
+    let has_predicates = abis
+        .iter()
+        .any(|indexer_abi| indexer_abi.program == ProgramKind::Predicate);
+
+    let program_data_decoders = abis
+        .iter()
+        .enumerate()
+        .filter(|(_, indexer_abi)| indexer_abi.program != ProgramKind::Contract)
+        .map(|(i, indexer_abi)| {
+            let name = indexer_abi.namespace.clone().unwrap_or_default();
+            let main = indexer_abi
+                .abi
+                .functions
+                .iter()
+                .find(|function| function.name == "main")
+                .unwrap_or_else(|| {
+                    proc_macro_error::abort_call_site!(
+                        "ABI for '{}' has no `main` function.",
+                        name
+                    )
+                });
+
+            let (arg_types, arg_fields): (Vec<_>, Vec<_>) = main
                 .inputs
                 .iter()
-                .map(|x| {
-                    ParamType::try_from_type_application(x, &abi_types_tyid)
-                        .expect("Could not derive TypeApplication param types.")
+                .map(|input| {
+                    match decoded_type_application(input, &abi_types_tyid) {
+                        Some((_, type_tokens, ident)) => (type_tokens, ident),
+                        None => proc_macro_error::abort_call_site!(
+                            "Argument '{}' of '{}' has an unsupported type.",
+                            input.name,
+                            name
+                        ),
+                    }
                 })
-                .collect();
-            let sig = resolve_fn_selector(&function.name, &params[..]);
-            let selector = u64::from_be_bytes(sig);
-            let ty_id = function_output_type_id(function, &abi_types_tyid);
+                .unzip();
 
             quote! {
-                #selector => #ty_id,
+                #i => {
+                    let decoder = ABIDecoder::default();
+                    let param_types = [#(<#arg_types as Parameterize>::param_type()),*];
+                    let tokens = decoder.decode_multiple(&param_types, &data)
+                        .with_context(|| format!("[codegen] Failed decoding arguments of {}", #name))?;
+                    let mut tokens = tokens.into_iter();
+                    #(
+                        let token = tokens.next().expect("Missing decoded argument.");
+                        let obj = <#arg_types as Tokenizable>::from_token(token)
+                            .with_context(|| format!("[codegen] Failed detokenizing arguments of {}", #name))?;
+                        self.#arg_fields.push((obj, self.context.clone()));
+                    )*
+                }
             }
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
-    let abi_selectors_to_fn_names = funcs
-        .iter()
-        .map(|function| {
-            let params: Vec<ParamType> = function
-                .inputs
-                .iter()
-                .map(|x| {
-                    ParamType::try_from_type_application(x, &abi_types_tyid)
-                        .expect("Could not derive TypeApplication param types.")
-                })
-                .collect();
-            let sig = resolve_fn_selector(&function.name, &params[..]);
-            let fn_name = function.name.clone();
-            let selector = u64::from_be_bytes(sig);
+    let script_abi_fn = if has_scripts {
+        quote! {
+            fn script_abi(&self, bytecode: &[u8]) -> Option<usize> {
+                #(#script_abi_checks)*
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let predicate_abi_fn = if has_predicates {
+        quote! {
+            fn predicate_abi(&self, bytecode: &[u8]) -> Option<usize> {
+                #(#predicate_abi_checks)*
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let program_decoder_fns = if program_data_decoders.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            #script_abi_fn
+
+            #predicate_abi_fn
 
+            fn decode_program_data(&mut self, abi: usize, data: Vec<u8>) -> anyhow::Result<()> {
+                match abi {
+                    #(#program_data_decoders)*
+                    _ => debug!("Unknown program ABI; check manifest to make sure that scripts and predicates are correct."),
+                }
+                Ok(())
+            }
+        }
+    };
+
+    // Logs emitted by a script have a zeroed contract ID, and are decoded with the
+    // ABI of the script being executed.
+    let script_log_abi = if has_scripts {
+        quote! {
+            if *contract_id == [0u8; 32] {
+                if let Some(abi) = self.script_abi {
+                    return abi;
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let decode_program_inputs = if program_data_decoders.is_empty() {
+        quote! {}
+    } else {
+        let decode_script = if has_scripts {
             quote! {
-               #selector => #fn_name.to_string(),
+                decoder.script_abi = decoder.script_abi(&script.script);
+                if let Some(abi) = decoder.script_abi {
+                    if let Err(e) = decoder.decode_program_data(abi, script.script_data.clone()) {
+                        debug!("Skipping script data that could not be decoded: {e:?}");
+                    }
+                }
             }
-        })
-        .collect::<Vec<proc_macro2::TokenStream>>();
+        } else {
+            quote! {}
+        };
+
+        let decode_predicates = if has_predicates {
+            quote! {
+                for input in script.inputs.iter() {
+                    let (predicate, predicate_data) = match input {
+                        fuel::Input::Coin(coin) => (&coin.predicate, &coin.predicate_data),
+                        fuel::Input::Message(message) => (&message.predicate, &message.predicate_data),
+                        _ => continue,
+                    };
+
+                    if predicate.is_empty() {
+                        continue;
+                    }
+
+                    if let Some(abi) = decoder.predicate_abi(predicate) {
+                        if let Err(e) = decoder.decode_program_data(abi, predicate_data.clone()) {
+                            debug!("Skipping predicate data that could not be decoded: {e:?}");
+                        }
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        let reset_script_abi = if has_scripts {
+            quote! { decoder.script_abi = None; }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #reset_script_abi
+            if let fuel::Transaction::Script(script) = &tx.transaction {
+                #decode_script
+                #decode_predicates
+            }
+        }
+    };
+
+    let mut decoder_struct_fields = decoder_struct_fields;
+
+    // Context of the data being decoded, which is stored along with it.
+    decoder_struct_fields.push(quote! { context: Context });
+
+    if has_scripts {
+        // ABI of the script in the transaction being processed, if any.
+        decoder_struct_fields.push(quote! { script_abi: Option<usize> });
+    }
+
+    // Wrap the given per-ABI match expressions in a match on the ABI of `contract_id`.
+    let dispatch_on_contract_abi = |per_abi: Vec<proc_macro2::TokenStream>| {
+        let mut per_abi = per_abi.into_iter();
+        let default = per_abi.next().unwrap_or_default();
+        let (indices, namespaced): (Vec<usize>, Vec<proc_macro2::TokenStream>) =
+            per_abi.enumerate().map(|(i, arms)| (i + 1, arms)).unzip();
+
+        if indices.is_empty() {
+            return default;
+        }
+
+        quote! {
+            match self.contract_abi(contract_id) {
+                #(#indices => { #namespaced })*
+                _ => { #default }
+            }
+        }
+    };
+
+    let selector_to_type_id = dispatch_on_contract_abi(
+        abi_selectors
+            .into_iter()
+            .map(|arms| {
+                quote! {
+                    match sel {
+                        #(#arms)*
+                        _ => {
+                            debug!("Unknown selector; check ABI to make sure function outputs match to types");
+                            usize::MAX
+                        }
+                    }
+                }
+            })
+            .collect(),
+    );
+
+    let selector_to_fn_name = dispatch_on_contract_abi(
+        abi_selectors_to_fn_names
+            .into_iter()
+            .map(|arms| {
+                quote! {
+                    match sel {
+                        #(#arms)*
+                        _ => {
+                            debug!("Unknown selector; check ABI to make sure function outputs match to types");
+                            "".to_string()
+                        }
+                    }
+                }
+            })
+            .collect(),
+    );
+
+    let decode_logdata = dispatch_on_contract_abi(
+        log_type_decoders
+            .into_iter()
+            .map(|arms| {
+                quote! {
+                    match rb {
+                        #(#arms),*
+                        _ => debug!("Unknown logged type ID; check ABI to make sure that logged types are correct.")
+                    }
+                }
+            })
+            .collect(),
+    );
+
+    let is_decoded_log = dispatch_on_contract_abi(
+        decoded_log_ids
+            .into_iter()
+            .map(|ids| quote! { [#(#ids),*].contains(&rb) })
+            .collect(),
+    );
+
+    let decode_messagedata = dispatch_on_contract_abi(
+        message_types_decoders
+            .into_iter()
+            .map(|arms| {
+                quote! {
+                    match type_id {
+                        #(#arms),*
+                        _ => debug!("Unknown message type ID; check ABI to make sure that message types are correct.")
+                    }
+                }
+            })
+            .collect(),
+    );
+
+    let has_call_types = call_decoders.iter().any(|arms| !arms.is_empty());
+
+    let decode_call = dispatch_on_contract_abi(
+        call_decoders
+            .into_iter()
+            .map(|arms| {
+                quote! {
+                    match selector {
+                        #(#arms)*
+                        _ => debug!("Unknown selector; check ABI to make sure that function inputs are correct."),
+                    }
+                }
+            })
+            .collect(),
+    );
+
+    let decode_call_fn = if has_call_types {
+        quote! {
+            fn decode_call(&mut self, call: Call, selector: u64, param2: u64, call_data: &[u8], call_data_address: Option<usize>) -> anyhow::Result<()> {
+                let contract_id = &<[u8; 32]>::from(call.to);
+                #decode_call
+                Ok(())
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Arguments of calls made by a script are found in its script data.
+    let call_data = if has_call_types {
+        quote! {
+            let (call_data, call_data_address) = match &tx.transaction {
+                fuel::Transaction::Script(script) => (script.script_data.clone(), script.script_data_address()),
+                _ => (Vec::new(), None),
+            };
+        }
+    } else {
+        quote! {}
+    };
+
+    let decode_call_args = if has_call_types {
+        quote! {
+            if let Err(e) = decoder.decode_call(call.clone(), param1, param2, &call_data, call_data_address) {
+                debug!("Skipping call arguments that could not be decoded: {e:?}");
+            }
+        }
+    } else {
+        quote! {}
+    };
 
     let contents = indexer_module
         .content
@@ -431,6 +999,12 @@ fn process_fn_items(
 
     let mut handler_fns = Vec::with_capacity(contents.len());
 
+    // Logs that can't be decoded using the ABI are only kept if a handler asks for them.
+    let mut has_undecoded_log_handler = false;
+
+    // Raw `Log` receipts are handled as they are by `Log` handlers, whatever their `rb`.
+    let mut has_log_handler = false;
+
     let start_block = match manifest.start_block() {
         Some(start_block) => {
             quote! {
@@ -442,54 +1016,45 @@ fn process_fn_items(
         None => quote! {},
     };
 
-    let subscribed_contract_ids = match &manifest.contract_id() {
-        ContractIds::Single(_) => quote! {},
-        ContractIds::Multiple(contract_ids) => {
-            let contract_ids = contract_ids
-                .iter()
-                .map(|id| {
-                    quote! {
-                        Bech32ContractId::from_str(#id).unwrap_or_else(|_| {
-                        let contract_id = ContractId::from_str(&#id).expect("Failed to parse manifest 'contract_id'");
-                        Bech32ContractId::from(contract_id)
-                        })
-                    }
-                })
-                .collect::<Vec<proc_macro2::TokenStream>>();
+    let contract_ids = manifest.subscribed_contract_ids();
 
-            quote! {
-                let contract_ids = HashSet::from([#(#contract_ids),*]);
-            }
+    let subscribed_contract_ids = if contract_ids.is_empty() {
+        quote! {}
+    } else {
+        let contract_ids = contract_ids
+            .iter()
+            .map(|id| {
+                quote! {
+                    Bech32ContractId::from_str(#id).unwrap_or_else(|_| {
+                    let contract_id = ContractId::from_str(&#id).expect("Failed to parse manifest 'contract_id'");
+                    Bech32ContractId::from(contract_id)
+                    })
+                }
+            })
+            .collect::<Vec<proc_macro2::TokenStream>>();
+
+        quote! {
+            let contract_ids = HashSet::from([#(#contract_ids),*]);
         }
     };
 
-    let check_if_subscribed_to_contract = match &manifest.contract_id() {
-        ContractIds::Single(contract_id) => match contract_id {
-            Some(contract_id) => {
-                quote! {
-                    let id_bytes = <[u8; 32]>::try_from(id).expect("Could not convert contract ID into bytes");
-                    let bech32_id = Bech32ContractId::new("fuel", id_bytes);
-                    let manifest_contract_id = Bech32ContractId::from_str(#contract_id).unwrap_or_else(|_| {
-                        let contract_id = ContractId::from_str(&#contract_id).expect("Failed to parse manifest 'contract_id'");
-                        Bech32ContractId::from(contract_id)
-                    });
-                    if bech32_id != manifest_contract_id {
-                        debug!("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
-                        continue;
-                    }
-                }
-            }
-            None => quote! {},
-        },
-        ContractIds::Multiple(_) => {
-            quote! {
-                let id_bytes = <[u8; 32]>::try_from(id).expect("Could not convert contract ID into bytes");
-                let bech32_id = Bech32ContractId::new("fuel", id_bytes);
+    // Receipts from a script that is decoded by the indexer have a zeroed contract ID.
+    let is_script_receipt = if has_scripts {
+        quote! { (id_bytes == [0u8; 32] && decoder.script_abi.is_some()) }
+    } else {
+        quote! { false }
+    };
 
-                if !contract_ids.contains(&bech32_id) {
-                    debug!("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
-                    continue;
-                }
+    let check_if_subscribed_to_contract = if contract_ids.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            let id_bytes = <[u8; 32]>::try_from(id).expect("Could not convert contract ID into bytes");
+            let bech32_id = Bech32ContractId::new("fuel", id_bytes);
+
+            if !contract_ids.contains(&bech32_id) && !is_watched_contract(&id_bytes) && !#is_script_receipt {
+                debug!("Not subscribed to this contract. Will skip this receipt event. <('-'<)");
+                continue;
             }
         }
     };
@@ -499,6 +1064,7 @@ fn process_fn_items(
             Item::Fn(fn_item) => {
                 let mut input_checks = Vec::new();
                 let mut arg_list = Vec::new();
+                let mut dispatcher_names = Vec::new();
 
                 for inp in &fn_item.sig.inputs {
                     match inp {
@@ -508,49 +1074,81 @@ fn process_fn_items(
                             )
                         }
                         FnArg::Typed(PatType { ty, .. }) => {
-                            if let Type::Path(path) = &**ty {
-                                let path_seg = path
-                                    .path
-                                    .segments
-                                    .last()
-                                    .expect("Could not get last path segment.");
-
-                                let path_type_name = typed_path_name(path);
-
-                                if is_unsupported_type(&path_type_name) {
-                                    proc_macro_error::abort_call_site!(
-                                        "Type with ident '{:?}' is not currently supported.",
-                                        path_seg.ident
-                                    )
-                                }
-
-                                if !type_ids.contains_key(&path_type_name) {
-                                    proc_macro_error::abort_call_site!(
-                                        "Type with ident '{:?}' not defined in the ABI.",
-                                        path_seg.ident
-                                    );
-                                };
+                            let path_type_name = match &**ty {
+                                Type::Path(path) => {
+                                    let path_seg = path
+                                        .path
+                                        .segments
+                                        .last()
+                                        .expect("Could not get last path segment.");
+
+                                    // The `Context` is that of the first other argument, and is
+                                    // filled in once all arguments are known.
+                                    if path.path.is_ident("Context") {
+                                        arg_list.push(None);
+                                        continue;
+                                    }
 
-                                let ty_id = type_ids
-                                    .get(&path_type_name)
-                                    .expect("Path type name not found in type IDs.");
-                                let typ = match abi_types_tyid.get(ty_id) {
-                                    Some(typ) => typ,
-                                    None => fuel_types.get(ty_id).unwrap(),
-                                };
+                                    // Prefer the qualified path (e.g., `router::Swap`) so that
+                                    // types from namespaced ABIs can be distinguished.
+                                    let path_type_name =
+                                        Some(qualified_typed_path_name(path))
+                                            .filter(|name| type_ids.contains_key(name))
+                                            .unwrap_or_else(|| typed_path_name(path));
+
+                                    if !type_ids.contains_key(&path_type_name) {
+                                        proc_macro_error::abort_call_site!(
+                                            "Type with ident '{:?}' not defined in the ABI.",
+                                            path_seg.ident
+                                        );
+                                    };
 
-                                let dispatcher_name = typ.decoder_field_ident();
+                                    path_type_name
+                                }
+                                Type::Tuple(_) | Type::Array(_) => {
+                                    let type_name = resolved_type_name(&quote! { #ty });
 
-                                input_checks
-                                    .push(quote! { self.#dispatcher_name.len() > 0 });
+                                    if !type_ids.contains_key(&type_name) {
+                                        proc_macro_error::abort_call_site!(
+                                            "Type '{}' not defined in the ABI.",
+                                            type_name
+                                        );
+                                    };
 
-                                arg_list
-                                    .push(quote! { self.#dispatcher_name[0].clone() });
-                            } else {
-                                proc_macro_error::abort_call_site!(
+                                    type_name
+                                }
+                                _ => proc_macro_error::abort_call_site!(
                                     "Arguments must be types defined in the ABI."
-                                )
+                                ),
+                            };
+
+                            match path_type_name.as_str() {
+                                "UndecodedLog" => has_undecoded_log_handler = true,
+                                "Log" => has_log_handler = true,
+                                _ => {}
                             }
+
+                            let ty_id = type_ids
+                                .get(&path_type_name)
+                                .expect("Path type name not found in type IDs.");
+                            let dispatcher_name = match composite_fields.get(ty_id) {
+                                Some(ident) => ident.clone(),
+                                None => match abi_types_tyid.get(ty_id) {
+                                    Some(typ) => typ.decoder_field_ident(),
+                                    None => fuel_types
+                                        .get(ty_id)
+                                        .unwrap()
+                                        .decoder_field_ident(),
+                                },
+                            };
+
+                            input_checks.push(quote! { self.#dispatcher_name.len() > 0 });
+
+                            arg_list.push(Some(
+                                quote! { self.#dispatcher_name[0].0.clone() },
+                            ));
+
+                            dispatcher_names.push(dispatcher_name);
                         }
                     }
                 }
@@ -565,6 +1163,23 @@ fn process_fn_items(
                     );
                 }
 
+                let context_source = match dispatcher_names.first() {
+                    Some(dispatcher_name) => dispatcher_name,
+                    None => proc_macro_error::abort_call_site!(
+                        "Handler function '{}' must have at least one argument besides `Context`.",
+                        fn_name.to_string(),
+                    ),
+                };
+
+                let arg_list = arg_list
+                    .into_iter()
+                    .map(|arg| {
+                        arg.unwrap_or_else(
+                            || quote! { self.#context_source[0].1.clone() },
+                        )
+                    })
+                    .collect::<Vec<proc_macro2::TokenStream>>();
+
                 let fn_call = if fn_item.sig.output == syn::ReturnType::Default {
                     quote! {
                         #fn_name(#(#arg_list),*)
@@ -586,6 +1201,7 @@ fn process_fn_items(
 
                 abi_dispatchers.push(quote! {
                     if ( #(#input_checks)&&* ) {
+                        set_handler_name(#fn_name_string);
                         #fn_call
                     }
                 });
@@ -601,6 +1217,32 @@ fn process_fn_items(
         }
     }
 
+    let decode_undecoded_log = if has_undecoded_log_handler {
+        let field = fuel_types
+            .get(&(type_id(FUEL_TYPES_NAMESPACE, "UndecodedLog") as usize))
+            .expect("Could not get `UndecodedLog` type reference.")
+            .decoder_field_ident();
+
+        quote! {
+            self.#field.push((log, self.context.clone()));
+        }
+    } else {
+        let is_dropped = if has_log_handler {
+            quote! { matches!(log, UndecodedLog::LogData(_)) }
+        } else {
+            quote! { true }
+        };
+
+        quote! {
+            if #is_dropped {
+                debug!("Dropped log {} of contract {}, which could not be decoded using the ABI.", log.log_id(), log.contract_id());
+                unsafe {
+                    DROPPED_LOGS += 1;
+                }
+            }
+        }
+    };
+
     let decoder_struct = quote! {
         #[derive(Default)]
         struct Decoders {
@@ -608,24 +1250,26 @@ fn process_fn_items(
         }
 
         impl Decoders {
-            fn selector_to_type_id(&self, sel: u64) -> usize {
-                match sel {
-                    #(#abi_selectors)*
-                    _ => {
-                        debug!("Unknown selector; check ABI to make sure function outputs match to types");
-                        usize::MAX
-                    }
-                }
+            fn contract_abi(&self, contract_id: &[u8; 32]) -> usize {
+                #script_log_abi
+
+                #(#contract_abi_checks)*
+
+                #watched_contract_abi
+
+                0
             }
 
-            pub fn selector_to_fn_name(&self, sel: u64) -> String {
-                match sel {
-                    #(#abi_selectors_to_fn_names)*
-                    _ => {
-                        debug!("Unknown selector; check ABI to make sure function outputs match to types");
-                        "".to_string()
-                    }
-                }
+            #program_decoder_fns
+
+            #decode_call_fn
+
+            fn selector_to_type_id(&self, contract_id: &[u8; 32], sel: u64) -> usize {
+                #selector_to_type_id
+            }
+
+            pub fn selector_to_fn_name(&self, contract_id: &[u8; 32], sel: u64) -> String {
+                #selector_to_fn_name
             }
 
             fn compute_message_id(&self, sender: &Address, recipient: &Address, nonce: Nonce, amount: Word, data: Option<Vec<u8>>) -> MessageId {
@@ -661,28 +1305,30 @@ fn process_fn_items(
             }
 
             pub fn decode_block(&mut self, data: BlockData) {
-                self.blockdata_decoded.push(data);
+                self.blockdata_decoded.push((data, self.context.clone()));
             }
 
-            pub fn decode_return_type(&mut self, sel: u64, data: Vec<u8>) -> anyhow::Result<()> {
-                let ty_id = self.selector_to_type_id(sel);
+            pub fn decode_return_type(&mut self, contract_id: &[u8; 32], sel: u64, data: Vec<u8>) -> anyhow::Result<()> {
+                let ty_id = self.selector_to_type_id(contract_id, sel);
                 self.decode_type(ty_id, data)?;
                 Ok(())
             }
 
-            pub fn decode_logdata(&mut self, rb: usize, data: Vec<u8>) -> anyhow::Result<()> {
-                match rb {
-                    #(#log_type_decoders),*
-                    _ => debug!("Unknown logged type ID; check ABI to make sure that logged types are correct.")
-                }
+            pub fn decode_logdata(&mut self, contract_id: &[u8; 32], rb: usize, data: Vec<u8>) -> anyhow::Result<()> {
+                #decode_logdata
                 Ok(())
             }
 
-            pub fn decode_messagedata(&mut self, type_id: u64, data: Vec<u8>) -> anyhow::Result<()> {
-                match type_id {
-                    #(#message_types_decoders),*
-                    _ => debug!("Unknown message type ID; check ABI to make sure that message types are correct.")
-                }
+            fn is_decoded_log(&self, contract_id: &[u8; 32], rb: u64) -> bool {
+                #is_decoded_log
+            }
+
+            pub fn decode_undecoded_log(&mut self, log: UndecodedLog) {
+                #decode_undecoded_log
+            }
+
+            pub fn decode_messagedata(&mut self, contract_id: &[u8; 32], type_id: u64, data: Vec<u8>) -> anyhow::Result<()> {
+                #decode_messagedata
                 Ok(())
             }
 
@@ -701,52 +1347,99 @@ fn process_fn_items(
     };
     (
         quote! {
+            // Contracts watched by a previous, failed call may not have been persisted,
+            // and its last handler is not the one that a failure of this call is in.
+            reset_watched_contracts();
+            reset_handler_name();
+
             #subscribed_contract_ids
 
-            use anyhow::Context;
+            use anyhow::Context as _;
             use fuel::TransactionData;
 
-            let mut process_transaction = |decoder: &mut Decoders, tx: TransactionData| -> anyhow::Result<()> {
+            let mut process_transaction = |decoder: &mut Decoders, tx_index: usize, tx: TransactionData| -> anyhow::Result<()> {
                 let mut return_types = Vec::new();
                 let mut callees = HashSet::new();
 
-                for receipt in tx.receipts {
+                let inputs = match &tx.transaction {
+                    fuel::Transaction::Script(script) => &script.inputs[..],
+                    fuel::Transaction::Create(create) => &create.inputs[..],
+                    fuel::Transaction::Mint(_) => &[],
+                };
+
+                decoder.context.tx_id = Some(tx.id);
+                decoder.context.tx_index = Some(tx_index);
+                decoder.context.receipt_index = None;
+                decoder.context.contract_id = None;
+                decoder.context.sender = inputs.iter().find_map(|input| match input {
+                    fuel::Input::Coin(coin) => Some(coin.owner),
+                    fuel::Input::Message(message) => Some(message.sender),
+                    _ => None,
+                });
+
+                #decode_program_inputs
+
+                #call_data
+
+                for (receipt_index, receipt) in tx.receipts.into_iter().enumerate() {
+                    decoder.context.receipt_index = Some(receipt_index);
+                    decoder.context.contract_id = match &receipt {
+                        fuel::Receipt::Call { to, .. } => Some(ContractId::from(<[u8; 32]>::from(*to))),
+                        _ => receipt.contract_id().map(|id| ContractId::from(<[u8; 32]>::from(*id))),
+                    };
+
                     match receipt {
-                        fuel::Receipt::Call { id: contract_id, amount, asset_id, gas, param1, to: id, .. } => {
+                        fuel::Receipt::Call { id: contract_id, amount, asset_id, gas, param1, param2, to: id, .. } => {
                             #check_if_subscribed_to_contract
 
-                            let fn_name = decoder.selector_to_fn_name(param1);
+                            let fn_name = decoder.selector_to_fn_name(&<[u8; 32]>::from(id), param1);
                             return_types.push(param1);
                             callees.insert(id);
 
-                            let data = serialize(
-                                &Call {
-                                    contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
-                                    to: ContractId::from(<[u8; 32]>::from(id)),
-                                    amount,
-                                    asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
-                                    gas,
-                                    fn_name
-                                }
-                            );
+                            let call = Call {
+                                contract_id: ContractId::from(<[u8; 32]>::from(contract_id)),
+                                to: ContractId::from(<[u8; 32]>::from(id)),
+                                amount,
+                                asset_id: AssetId::from(<[u8; 32]>::from(asset_id)),
+                                gas,
+                                fn_name
+                            };
+
+                            #decode_call_args
+
+                            let data = serialize(&call);
                             let ty_id = Call::type_id();
                             decoder.decode_type(ty_id, data)?;
                         }
                         fuel::Receipt::Log { id, ra, rb, .. } => {
                             #check_if_subscribed_to_contract
                             let ty_id = Log::type_id();
-                            let data = serialize(
-                                &Log {
-                                    contract_id: ContractId::from(<[u8; 32]>::from(id)),
-                                    ra,
-                                    rb
-                                }
-                            );
+                            let log = Log {
+                                contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                                ra,
+                                rb
+                            };
+                            let data = serialize(&log);
                             decoder.decode_type(ty_id, data)?;
+
+                            if !decoder.is_decoded_log(&<[u8; 32]>::from(id), rb) {
+                                decoder.decode_undecoded_log(UndecodedLog::Log(log));
+                            }
                         }
                         fuel::Receipt::LogData { rb, data, ptr, len, id, .. } => {
                             #check_if_subscribed_to_contract
-                            decoder.decode_logdata(rb as usize, data.unwrap_or(Vec::<u8>::new()))?;
+                            let data = data.unwrap_or(Vec::<u8>::new());
+                            if decoder.is_decoded_log(&<[u8; 32]>::from(id), rb) {
+                                decoder.decode_logdata(&<[u8; 32]>::from(id), rb as usize, data)?;
+                            } else {
+                                decoder.decode_undecoded_log(UndecodedLog::LogData(LogData {
+                                    contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                                    data,
+                                    rb,
+                                    len,
+                                    ptr
+                                }));
+                            }
                         }
                         fuel::Receipt::Return { id, val, pc, is } => {
                             #check_if_subscribed_to_contract
@@ -767,7 +1460,7 @@ fn process_fn_items(
                             #check_if_subscribed_to_contract
                             if callees.contains(&id) {
                                 let selector = return_types.pop().expect("No return type available. <('-'<)");
-                                decoder.decode_return_type(selector, data.unwrap_or(Vec::<u8>::new()))?;
+                                decoder.decode_return_type(&<[u8; 32]>::from(id), selector, data.unwrap_or(Vec::<u8>::new()))?;
                             }
                         }
                         fuel::Receipt::MessageOut { sender, recipient, amount, nonce, len, digest, data, .. } => {
@@ -795,7 +1488,7 @@ fn process_fn_items(
                                 });
 
 
-                            decoder.decode_messagedata(type_id, data.clone())?;
+                            decoder.decode_messagedata(&<[u8; 32]>::from(sender), type_id, data.clone())?;
 
                             let ty_id = MessageOut::type_id();
                             let data = serialize(
@@ -908,14 +1601,19 @@ fn process_fn_items(
                 #start_block
 
                 let mut decoder = Decoders::default();
+                decoder.context = Context {
+                    block_height: block.height,
+                    block_time: block.time,
+                    ..Context::default()
+                };
 
                 let ty_id = BlockData::type_id();
                 let data = serialize(&block);
                 decoder.decode_type(ty_id, data)?;
 
-                for tx in block.transactions {
+                for (tx_index, tx) in block.transactions.into_iter().enumerate() {
                     let tx_id = tx.id;
-                    process_transaction(&mut decoder, tx).with_context(|| format!("Failed processing Transaction {:?}", tx_id))?
+                    process_transaction(&mut decoder, tx_index, tx).with_context(|| format!("Failed processing Transaction {:?}", tx_id))?
                 }
 
                 decoder.dispatch()?;
@@ -927,6 +1625,7 @@ fn process_fn_items(
 
             for block in blocks {
                 let block_height = block.header.height;
+                set_block_height(block_height);
                 process_block(block).with_context(|| format!("Failed processing Block #{}", block_height))?;
             }
         },
@@ -935,6 +1634,7 @@ fn process_fn_items(
 
             #(#handler_fns)*
         },
+        call_types,
     )
 }
 
@@ -969,6 +1669,20 @@ pub fn prefix_abi_and_schema_paths(
     (None, schema.to_string())
 }
 
+/// Prefix the path of an ABI listed under the manifest's `contracts`, in the same
+/// way as `prefix_abi_and_schema_paths`.
+pub fn prefix_abi_path(abi: &str) -> String {
+    match std::env::var("COMPILE_TEST_PREFIX") {
+        Ok(prefix) => std::path::Path::new(&prefix)
+            .join(abi)
+            .into_os_string()
+            .to_str()
+            .expect("Could not parse prefixed ABI path.")
+            .to_string(),
+        Err(_) => abi.to_string(),
+    }
+}
+
 pub fn get_abi_tokens(namespace: &str, abi: &str) -> proc_macro2::TokenStream {
     match Abigen::generate(
         vec![AbigenTarget {
@@ -1004,11 +1718,79 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
     let (abi, schema_string) =
         prefix_abi_and_schema_paths(manifest.abi(), manifest.graphql_schema());
 
-    let abi_tokens = match abi {
+    let mut abi_tokens = match abi {
         Some(ref abi_path) => get_abi_tokens(manifest.namespace(), abi_path),
         None => proc_macro2::TokenStream::new(),
     };
 
+    let mut abis = vec![IndexerAbi {
+        namespace: None,
+        abi: get_json_abi(abi).unwrap_or_default(),
+        program: ProgramKind::Contract,
+        contract_ids: manifest.contract_id().ids(),
+        bytecode_hash: None,
+    }];
+
+    // Types from each additional contract ABI are generated in a module named
+    // after the contract, so that they don't collide with each other.
+    let mut abi_modules = Vec::new();
+    for contract in manifest.contracts() {
+        let abi_path = prefix_abi_path(contract.abi());
+        let name = format_ident! { "{}", contract.name() };
+        let tokens = get_abi_tokens(contract.name(), &abi_path);
+
+        abi_modules.push((name, tokens));
+
+        abis.push(IndexerAbi {
+            namespace: Some(contract.name().to_string()),
+            abi: get_json_abi(Some(abi_path)).unwrap_or_default(),
+            program: ProgramKind::Contract,
+            contract_ids: contract.contract_id().ids(),
+            bytecode_hash: None,
+        });
+    }
+
+    // Scripts and predicates are namespaced in the same way. Their types are generated
+    // as for a contract, since only the types (not the program bindings) are used.
+    let programs = manifest
+        .scripts()
+        .iter()
+        .map(|script| (script, ProgramKind::Script))
+        .chain(
+            manifest
+                .predicates()
+                .iter()
+                .map(|predicate| (predicate, ProgramKind::Predicate)),
+        );
+
+    for (program, kind) in programs {
+        let abi_path = prefix_abi_path(program.abi());
+        let name = format_ident! { "{}", program.name() };
+        let tokens = get_abi_tokens(program.name(), &abi_path);
+
+        abi_modules.push((name, tokens));
+
+        let bytecode_hash = program.bytecode().map(|bytecode| {
+            let path = prefix_abi_path(bytecode);
+            let bytes = std::fs::read(&path).unwrap_or_else(|e| {
+                proc_macro_error::abort_call_site!(
+                    "Could not read bytecode at {:?}: {:?}.",
+                    path,
+                    e
+                )
+            });
+            <[u8; 32]>::from(Sha256::digest(bytes))
+        });
+
+        abis.push(IndexerAbi {
+            namespace: Some(program.name().to_string()),
+            abi: get_json_abi(Some(abi_path)).unwrap_or_default(),
+            program: kind,
+            contract_ids: Vec::new(),
+            bytecode_hash,
+        });
+    }
+
     // NOTE: https://nickb.dev/blog/cargo-workspace-and-the-feature-unification-pitfall/
     let graphql_tokens = process_graphql_schema(
         manifest.namespace(),
@@ -1018,8 +1800,40 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
 
     let decl_tokens = additional_declarations();
 
-    let (handler_block, fn_items) = process_fn_items(&manifest, abi, indexer_module);
-    let handler_block = handler_block_wasm(handler_block);
+    let (handler_block, fn_items, call_types) =
+        process_fn_items(&manifest, abis, indexer_module);
+
+    // Call types are placed alongside the other types of their ABI.
+    let mut call_types = call_types.into_iter();
+    abi_tokens.extend(call_types.next().unwrap_or_default());
+
+    for ((name, tokens), calls) in abi_modules.into_iter().zip(call_types) {
+        let calls = if calls.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                pub use calls::*;
+
+                mod calls {
+                    use super::super::*;
+
+                    #calls
+                }
+            }
+        };
+
+        abi_tokens.extend(quote! {
+            pub mod #name {
+                #tokens
+
+                #calls
+            }
+        });
+    }
+    let handler_block = match manifest.module() {
+        Module::Wasm(_) => handler_block_wasm(handler_block),
+        Module::Native => handler_block_native(handler_block),
+    };
     let output = quote! {
 
         #decl_tokens
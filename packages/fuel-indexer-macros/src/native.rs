@@ -0,0 +1,57 @@
+use quote::quote;
+
+/// Generate the handler block for the native execution environment.
+pub fn handler_block_native(
+    handler_block: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let native_prelude = native_prelude();
+
+    quote! {
+
+        #native_prelude
+
+        static mut ERROR_MESSAGE: String = String::new();
+
+        static mut DROPPED_LOGS: u64 = 0;
+
+        /// Handle the given blocks.
+        ///
+        /// This function is registered into a `NativeIndexExecutor` by the indexer
+        /// service binary that this indexer is linked into.
+        pub fn handle_events(blocks: Vec<BlockData>) -> anyhow::Result<()> {
+            unsafe {
+                ERROR_MESSAGE.clear();
+            }
+
+            #handler_block
+
+            let dropped_logs = unsafe { core::mem::take(&mut DROPPED_LOGS) };
+            if dropped_logs > 0 {
+                report_dropped_logs(dropped_logs);
+            }
+
+            Ok(())
+        }
+    }
+}
+
+/// Prelude imports for the _indexer_ module.
+///
+/// These are the same as those of the WASM execution environment, except that the
+/// entity API is that of native indexers.
+fn native_prelude() -> proc_macro2::TokenStream {
+    quote! {
+        use fuel_indexer_utils::plugin::anyhow::{self, Context as _};
+        use alloc::{format, vec, vec::Vec};
+        use std::str::FromStr;
+
+        use fuel_indexer_utils::plugin::types::*;
+        use fuel_indexer_utils::plugin::native::*;
+        use fuel_indexer_utils::plugin::{serde_json, serialize, deserialize, bincode};
+        use fuel_indexer_utils::plugin::serde::{Deserialize, Serialize};
+        use fuels::{
+            core::{codec::{ABIDecoder}, Configurables, traits::{Parameterize, Tokenizable}},
+            types::{param_types::ParamType},
+        };
+    }
+}
@@ -21,6 +21,8 @@ fn process_type_def(
         TypeKind::Object(_o) => ObjectDecoder::from_typedef(typ, parsed).into(),
         TypeKind::Enum(_e) => EnumDecoder::from_typedef(typ, parsed).into(),
         TypeKind::Union(_u) => ObjectDecoder::from_typedef(typ, parsed).into(),
+        // Interfaces are only used for querying the objects that implement them.
+        TypeKind::Interface(_i) => return None,
         _ => proc_macro_error::abort_call_site!(
             "Unrecognized TypeKind in GraphQL schema: {:?}",
             typ.kind
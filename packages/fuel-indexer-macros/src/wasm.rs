@@ -8,12 +8,16 @@ pub fn handler_block_wasm(
 
     let panic_hook = panic_hook();
 
+    let dropped_logs = dropped_logs();
+
     quote! {
 
         #wasm_prelude
 
         #panic_hook
 
+        #dropped_logs
+
         #[no_mangle]
         fn handle_events(blob: *mut u8, len: usize) {
             if let Err(e) = handle_events_internal(blob, len) {
@@ -26,7 +30,10 @@ pub fn handler_block_wasm(
 
         #[no_mangle]
         fn handle_events_internal(blob: *mut u8, len: usize) -> anyhow::Result<()> {
+            // Indexers compiled for the host, e.g. for unit tests, unwind on panic.
+            #[cfg(target_arch = "wasm32")]
             register_panic_hook();
+            init_logger();
 
             use fuel_indexer_utils::plugin::deserialize;
             let bytes = unsafe { Vec::from_raw_parts(blob, len, len) };
@@ -49,9 +56,10 @@ pub fn handler_block_wasm(
 
 /// Panic hook for the indexer.
 ///
-/// When a panic occurs, the message is stored in a `static mut` `String` and a
-/// `WasmIndexerError::Panic` error code is returned. The message is then
-/// retrieved by the indexer service and logged.
+/// When a panic occurs, the message and its location are stored in a `static mut`
+/// `String` and a `WasmIndexerError::Panic` error code is returned. The message is
+/// then retrieved by the indexer service, and reported along with the name of the
+/// handler that panicked.
 fn panic_hook() -> proc_macro2::TokenStream {
     quote! {
         static mut ERROR_MESSAGE: String = String::new();
@@ -75,7 +83,7 @@ fn panic_hook() -> proc_macro2::TokenStream {
             SET_HOOK.call_once(|| {
                 panic::set_hook(Box::new(|info| {
                     unsafe {
-                        ERROR_MESSAGE = info.to_string();
+                        ERROR_MESSAGE = panic_message(info);
                     }
                     early_exit(WasmIndexerError::Panic);
                 }));
@@ -84,6 +92,25 @@ fn panic_hook() -> proc_macro2::TokenStream {
     }
 }
 
+/// Counter of logs that could not be decoded and had no handler.
+///
+/// The indexer service takes the count after each batch of blocks, and reports
+/// it as a metric.
+fn dropped_logs() -> proc_macro2::TokenStream {
+    quote! {
+        static mut DROPPED_LOGS: u64 = 0;
+
+        #[no_mangle]
+        fn take_dropped_logs() -> u64 {
+            unsafe {
+                let count = DROPPED_LOGS;
+                DROPPED_LOGS = 0;
+                count
+            }
+        }
+    }
+}
+
 /// Prelude imports for the _indexer_ module.
 ///
 /// These imports are placed below the top-level lib imports, so any
@@ -91,7 +118,7 @@ fn panic_hook() -> proc_macro2::TokenStream {
 /// indexer module, not within the scope of the entire lib module.
 fn wasm_prelude() -> proc_macro2::TokenStream {
     quote! {
-        use fuel_indexer_utils::plugin::anyhow::{self, Context};
+        use fuel_indexer_utils::plugin::anyhow::{self, Context as _};
         use alloc::{format, vec, vec::Vec};
         use std::str::FromStr;
 
@@ -2,7 +2,7 @@ use lazy_static::lazy_static;
 use prometheus::{self, register_int_counter, IntCounter};
 use prometheus_client::{
     encoding::EncodeLabelSet,
-    metrics::{family::Family, histogram::Histogram},
+    metrics::{counter::Counter, family::Family, histogram::Histogram},
     registry::Registry,
 };
 
@@ -98,9 +98,96 @@ impl ExecutorWeb {
     }
 }
 
+pub struct ExecutorDroppedLogs {
+    pub registry: Registry,
+    logs: Family<Label, Counter>,
+}
+
+impl Metric for ExecutorDroppedLogs {
+    fn init() -> Self {
+        let mut registry = Registry::default();
+        let logs = Family::<Label, Counter>::default();
+        registry.register(
+            "executor_dropped_logs",
+            "Count of logs that could not be decoded and were dropped.",
+            logs.clone(),
+        );
+
+        Self { registry, logs }
+    }
+}
+
+impl ExecutorDroppedLogs {
+    pub fn record(&self, indexer_uid: &str, count: u64) {
+        self.logs
+            .get_or_create(&Label {
+                path: indexer_uid.to_string(),
+            })
+            .inc_by(count);
+    }
+}
+
+pub struct ExecutorPrunedRows {
+    pub registry: Registry,
+    rows: Family<Label, Counter>,
+}
+
+impl Metric for ExecutorPrunedRows {
+    fn init() -> Self {
+        let mut registry = Registry::default();
+        let rows = Family::<Label, Counter>::default();
+        registry.register(
+            "executor_pruned_rows",
+            "Count of rows deleted from tables with a retention policy.",
+            rows.clone(),
+        );
+
+        Self { registry, rows }
+    }
+}
+
+impl ExecutorPrunedRows {
+    pub fn record(&self, indexer_uid: &str, count: u64) {
+        self.rows
+            .get_or_create(&Label {
+                path: indexer_uid.to_string(),
+            })
+            .inc_by(count);
+    }
+}
+
+pub struct ExecutorPrune {
+    pub registry: Registry,
+    requests: Family<Label, Histogram>,
+}
+
+impl Metric for ExecutorPrune {
+    fn init() -> Self {
+        let mut registry = Registry::default();
+        let requests = Family::<Label, Histogram>::new_with_constructor(|| {
+            Histogram::new(TIMING_HISTOGRAM_BUCKETS.iter().cloned())
+        });
+        registry.register("executor_prune_duration", "", requests.clone());
+
+        Self { registry, requests }
+    }
+}
+
+impl ExecutorPrune {
+    pub fn record(&self, indexer_uid: &str, time: f64) {
+        let histogram = self.requests.get_or_create(&Label {
+            path: indexer_uid.to_string(),
+        });
+        histogram.observe(time);
+    }
+}
+
 pub struct Executor {
     pub handler: ExecutorHandler,
     pub web: ExecutorWeb,
+    pub dropped_logs: ExecutorDroppedLogs,
+    pub prune: ExecutorPrune,
+    pub pruned_rows: ExecutorPrunedRows,
 }
 
 impl Metric for Executor {
@@ -108,6 +195,9 @@ impl Metric for Executor {
         Self {
             handler: ExecutorHandler::init(),
             web: ExecutorWeb::init(),
+            dropped_logs: ExecutorDroppedLogs::init(),
+            prune: ExecutorPrune::init(),
+            pruned_rows: ExecutorPrunedRows::init(),
         }
     }
 }
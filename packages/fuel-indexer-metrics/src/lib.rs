@@ -34,6 +34,18 @@ pub fn encode_metrics_response() -> impl IntoResponse {
         return error_body();
     }
 
+    if encode(&mut encoded, &METRICS.exec.dropped_logs.registry).is_err() {
+        return error_body();
+    }
+
+    if encode(&mut encoded, &METRICS.exec.prune.registry).is_err() {
+        return error_body();
+    }
+
+    if encode(&mut encoded, &METRICS.exec.pruned_rows.registry).is_err() {
+        return error_body();
+    }
+
     Response::builder()
         .status(StatusCode::OK)
         .body(Body::from(encoded))
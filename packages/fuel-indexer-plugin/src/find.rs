@@ -1,4 +1,4 @@
-use fuel_indexer_types::scalar::{BlockHeight, Boolean, UID};
+use fuel_indexer_types::scalar::{BlockHeight, Boolean, Timestamp, UID};
 use sqlparser::ast as sql;
 
 /// Represents a filter that returns a single results.
@@ -304,6 +304,12 @@ impl ToSQLValue for UID {
     }
 }
 
+impl ToSQLValue for Timestamp {
+    fn to_sql_value(self) -> sql::Value {
+        sql::Value::SingleQuotedString(self.to_string())
+    }
+}
+
 impl ToSQLValue for BlockHeight {
     fn to_sql_value(self) -> sql::Value {
         sqlparser::test_utils::number(
@@ -340,16 +346,15 @@ macro_rules! impl_number_to_sql_value {
     ($T:ident) => {
         impl ToSQLValue for fuel_indexer_types::scalar::$T {
             fn to_sql_value(self) -> sql::Value {
-                sqlparser::test_utils::number(
-                    &usize::try_from(self)
-                        .expect("Could not convert scalar into usize")
-                        .to_string(),
-                )
+                sqlparser::test_utils::number(&self.to_string())
             }
         }
     };
 }
 
+impl_number_to_sql_value!(I256);
+impl_number_to_sql_value!(U256);
+
 impl_number_to_sql_value!(I128);
 impl_number_to_sql_value!(U128);
 
@@ -498,7 +503,9 @@ mod tests {
 
     #[test]
     fn test_find_query_generation() {
-        use fuel_indexer_types::scalar::{Address, BlockHeight, Bytes8, I32};
+        use fuel_indexer_types::scalar::{
+            Address, BlockHeight, Bytes8, Timestamp, I256, I32, I64, U128, U256,
+        };
 
         struct MyStruct {}
 
@@ -518,6 +525,26 @@ mod tests {
             Field::new("my_blockheight_field".to_string())
         }
 
+        fn my_timestamp_field() -> Field<MyStruct, Timestamp> {
+            Field::new("my_timestamp_field".to_string())
+        }
+
+        fn my_i64_field() -> Field<MyStruct, I64> {
+            Field::new("my_i64_field".to_string())
+        }
+
+        fn my_u128_field() -> Field<MyStruct, U128> {
+            Field::new("my_u128_field".to_string())
+        }
+
+        fn my_u256_field() -> Field<MyStruct, U256> {
+            Field::new("my_u256_field".to_string())
+        }
+
+        fn my_i256_field() -> OptionField<MyStruct, I256> {
+            OptionField::new("my_i256_field".to_string())
+        }
+
         let f: Filter<MyStruct> = my_field().gt(7);
         assert_eq!(&f.to_string(), "my_field > 7");
 
@@ -588,5 +615,27 @@ mod tests {
 
         let word: Filter<MyStruct> = my_blockheight_field().eq(BlockHeight::new(123));
         assert_eq!(&word.to_string(), "my_blockheight_field = 123");
+
+        let negative: Filter<MyStruct> = my_i64_field().lt(i64::MIN);
+        assert_eq!(&negative.to_string(), "my_i64_field < -9223372036854775808");
+
+        let big: Filter<MyStruct> = my_u128_field().gt(u128::MAX);
+        assert_eq!(
+            &big.to_string(),
+            "my_u128_field > 340282366920938463463374607431768211455"
+        );
+
+        let big: Filter<MyStruct> = my_u256_field().gt(U256::MAX);
+        assert_eq!(&big.to_string(), "my_u256_field > 115792089237316195423570985008687907853269984665640564039457584007913129639935");
+
+        let big: Filter<MyStruct> = my_i256_field().lt(I256::from(-1));
+        assert_eq!(&big.to_string(), "my_i256_field < -1");
+
+        let time: Filter<MyStruct> =
+            my_timestamp_field().ge(Timestamp::from_unix(1_700_000_000));
+        assert_eq!(
+            &time.to_string(),
+            "my_timestamp_field >= '2023-11-14T22:13:20Z'"
+        );
     }
 }
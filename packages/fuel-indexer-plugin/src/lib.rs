@@ -1,5 +1,9 @@
 mod find;
 
+#[cfg(feature = "native")]
+pub mod native;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod wasm;
 
 pub mod types {
@@ -34,6 +38,9 @@ pub use fuel_indexer_lib::{
     utils::{deserialize, serialize},
 };
 
+// Handlers can log through the `log` facade, which is hooked up to `wasm::Logger`.
+pub use log;
+
 // Specifically we import `serde` here for the `Serialize` and `Deserialize` traits
 // else the user would have to explicity import these in their indexer modules.
 pub use serde;
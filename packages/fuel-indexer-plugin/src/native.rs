@@ -0,0 +1,156 @@
+//! Native counterparts of the functions that a WASM indexer imports from the indexer
+//! service.
+//!
+//! Native indexers are linked into the indexer service binary, so rather than
+//! copying data in and out of WASM memory, these functions read their arguments
+//! from, and hand their results over in, the memory of the process, and call the
+//! indexer service directly.
+use fuel_indexer::native;
+use fuel_indexer_lib::{utils::serialize, WasmIndexerError};
+use fuel_indexer_types::scalar::ContractId;
+
+pub use crate::wasm::*;
+
+/// Terminate handler execution with the given error.
+///
+/// The `NativeIndexExecutor` catches the panic, and handles the error just like the
+/// error code of a WASM indexer that exited early.
+fn exit(err: WasmIndexerError) -> ! {
+    std::panic::panic_any(err)
+}
+
+/// Read the length at the given pointer.
+unsafe fn read_len(len: *mut u8) -> usize {
+    u32::from_le_bytes(*(len as *const [u8; 4])) as usize
+}
+
+/// Read the bytes at the given pointer.
+unsafe fn read_bytes(ptr: *const u8, len: usize) -> Vec<u8> {
+    std::slice::from_raw_parts(ptr, len).to_vec()
+}
+
+/// Read the bincode-serialized string at the given pointer.
+unsafe fn read_string(ptr: *const u8, len: *mut u8) -> String {
+    let bytes = read_bytes(ptr, read_len(len));
+    match bincode::deserialize(&bytes) {
+        Ok(s) => s,
+        Err(_) => exit(WasmIndexerError::DeserializationError),
+    }
+}
+
+/// Hand the given bytes over to the caller, which takes ownership of them using
+/// `Vec::from_raw_parts`, and write their length at the given pointer.
+unsafe fn into_raw(bytes: Vec<u8>, len: *mut u8) -> *mut u8 {
+    if bytes.is_empty() {
+        return std::ptr::null_mut();
+    }
+
+    // A boxed slice has a capacity equal to its length.
+    let bytes = bytes.into_boxed_slice();
+    let size = (bytes.len() as u32).to_le_bytes();
+    std::ptr::copy_nonoverlapping(size.as_ptr(), len, size.len());
+
+    Box::into_raw(bytes) as *mut u8
+}
+
+pub(crate) unsafe fn ff_get_object(
+    type_id: i64,
+    ptr: *const u8,
+    len: *mut u8,
+) -> *mut u8 {
+    let id = read_string(ptr, len);
+    match native::get_object(type_id, id) {
+        Ok(Some(bytes)) => into_raw(bytes, len),
+        Ok(None) => std::ptr::null_mut(),
+        Err(e) => exit(e),
+    }
+}
+
+pub(crate) unsafe fn ff_find_many(type_id: i64, ptr: *const u8, len: *mut u8) -> *mut u8 {
+    let constraints = read_string(ptr, len);
+    match native::find_many(type_id, constraints) {
+        Ok(objects) if objects.is_empty() => std::ptr::null_mut(),
+        Ok(objects) => into_raw(serialize(&objects), len),
+        Err(e) => exit(e),
+    }
+}
+
+pub(crate) unsafe fn ff_delete_many(type_id: i64, ptr: *const u8, len: *mut u8) -> u64 {
+    let constraints = read_string(ptr, len);
+    match native::delete_many(type_id, constraints) {
+        Ok(count) => count as u64,
+        Err(e) => exit(e),
+    }
+}
+
+pub(crate) unsafe fn ff_log_data(ptr: *const u8, len: u32, log_level: u32) {
+    let log_string =
+        String::from_utf8_lossy(std::slice::from_raw_parts(ptr, len as usize));
+    if let Err(e) = native::log_data(&log_string, log_level) {
+        exit(e)
+    }
+}
+
+pub(crate) unsafe fn ff_log_record(ptr: *const u8, len: u32) {
+    let bytes = read_bytes(ptr, len as usize);
+    let record = match bincode::deserialize(&bytes) {
+        Ok(record) => record,
+        Err(_) => exit(WasmIndexerError::DeserializationError),
+    };
+
+    if let Err(e) = native::log_record(record) {
+        exit(e)
+    }
+}
+
+pub(crate) unsafe fn ff_set_block_height(height: u32) {
+    if let Err(e) = native::set_block_height(height) {
+        exit(e)
+    }
+}
+
+pub(crate) unsafe fn ff_put_object(type_id: i64, ptr: *const u8, len: u32) {
+    if let Err(e) = native::put_object(type_id, read_bytes(ptr, len as usize)) {
+        exit(e)
+    }
+}
+
+pub(crate) unsafe fn ff_put_many_to_many_record(ptr: *const u8, len: u32) {
+    if let Err(e) = native::put_many_to_many_record(read_bytes(ptr, len as usize)) {
+        exit(e)
+    }
+}
+
+pub(crate) unsafe fn ff_early_exit(err_code: u32) {
+    exit(WasmIndexerError::from(err_code))
+}
+
+pub(crate) unsafe fn ff_watch_contract(ptr: *const u8, len: u32) {
+    let contract_id =
+        match ContractId::try_from(std::slice::from_raw_parts(ptr, len as usize)) {
+            Ok(contract_id) => contract_id,
+            Err(_) => exit(WasmIndexerError::DeserializationError),
+        };
+
+    if let Err(e) = native::watch_contract(&contract_id) {
+        exit(e)
+    }
+}
+
+pub(crate) unsafe fn ff_get_watched_contracts(len: *mut u8) -> *mut u8 {
+    match native::get_watched_contracts() {
+        Ok(contract_ids) if contract_ids.is_empty() => std::ptr::null_mut(),
+        Ok(contract_ids) => into_raw(serialize(&contract_ids), len),
+        Err(e) => exit(e),
+    }
+}
+
+pub(crate) fn report_handler_name(name: &'static str) {
+    native::set_handler_name(name)
+}
+
+/// Report the number of logs that the indexer dropped because they could not be
+/// decoded, which the `NativeIndexExecutor` reports once the blocks are handled.
+pub fn report_dropped_logs(count: u64) {
+    native::record_dropped_logs(count)
+}
@@ -0,0 +1,1133 @@
+//! In-memory counterparts of the functions that a WASM indexer imports from the
+//! indexer service, for unit-testing handlers with `cargo test`.
+//!
+//! With the `testing` feature enabled, the `#[indexer]` module compiles for the
+//! host, and entities are saved to, loaded from, and found in a thread-local store
+//! instead of the database. The contracts watched by handlers are kept per thread
+//! as well, and `cargo test` runs each test on its own thread, so tests don't share
+//! state.
+//!
+//! ```ignore
+//! use fuel_indexer_utils::testing::{self, BlockBuilder, TransactionBuilder};
+//!
+//! #[test]
+//! fn test_handles_transfer() {
+//!     let block = BlockBuilder::new(1)
+//!         .transaction(
+//!             TransactionBuilder::new().receipt(testing::transfer(
+//!                 ContractId::zeroed(),
+//!                 ContractId::from([1; 32]),
+//!                 100,
+//!                 AssetId::zeroed(),
+//!             )),
+//!         )
+//!         .build();
+//!
+//!     testing::run_handlers(handle_events_internal, vec![block]).unwrap();
+//!
+//!     let transfer = Transfer::load(uid([1; 32])).unwrap();
+//!     assert_eq!(transfer.amount, 100);
+//! }
+//! ```
+use std::{
+    cell::RefCell,
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+};
+
+use fuel_indexer_lib::{
+    utils::{deserialize, serialize},
+    WasmIndexerError,
+};
+use fuel_indexer_schema::FtColumn;
+use fuel_indexer_types::{
+    ffi::LogRecord,
+    fuel::{
+        BlockData, Consensus, Header, Receipt, Transaction, TransactionData,
+        TransactionStatus, TxId,
+    },
+    scalar::{AssetId, Bytes32, ContractId},
+};
+use sqlparser::{
+    ast::{self as sql, BinaryOperator, Expr, SetExpr, Statement, UnaryOperator},
+    dialect::GenericDialect,
+    parser::Parser,
+};
+
+use crate::wasm::{reset_watched_contracts, FromHex};
+
+/// The in-memory store of a test.
+#[derive(Default)]
+struct Store {
+    /// Rows of each entity type, by ID.
+    objects: HashMap<i64, BTreeMap<String, Vec<FtColumn>>>,
+
+    /// Column names of each entity type, in the order of their rows.
+    columns: HashMap<i64, &'static [&'static str]>,
+
+    /// Log records emitted by the handlers.
+    logs: Vec<LogRecord>,
+
+    /// Contracts watched by the handlers.
+    watched_contracts: Vec<String>,
+}
+
+thread_local! {
+    static STORE: RefCell<Store> = RefCell::new(Store::default());
+}
+
+/// Terminate handler execution with the given error.
+///
+/// Handlers are run on the host, so this simply panics, failing the test unless
+/// it expects the panic.
+fn exit(err: WasmIndexerError) -> ! {
+    panic!("Indexer exited early: {err}")
+}
+
+/// Read the length at the given pointer.
+unsafe fn read_len(len: *mut u8) -> usize {
+    u32::from_le_bytes(*(len as *const [u8; 4])) as usize
+}
+
+/// Read the bytes at the given pointer.
+unsafe fn read_bytes(ptr: *const u8, len: usize) -> Vec<u8> {
+    std::slice::from_raw_parts(ptr, len).to_vec()
+}
+
+/// Read the bincode-serialized string at the given pointer.
+unsafe fn read_string(ptr: *const u8, len: *mut u8) -> String {
+    let bytes = read_bytes(ptr, read_len(len));
+    match bincode::deserialize(&bytes) {
+        Ok(s) => s,
+        Err(_) => exit(WasmIndexerError::DeserializationError),
+    }
+}
+
+/// Hand the given bytes over to the caller, which takes ownership of them using
+/// `Vec::from_raw_parts`, and write their length at the given pointer.
+unsafe fn into_raw(bytes: Vec<u8>, len: *mut u8) -> *mut u8 {
+    if bytes.is_empty() {
+        return std::ptr::null_mut();
+    }
+
+    // A boxed slice has a capacity equal to its length.
+    let bytes = bytes.into_boxed_slice();
+    let size = (bytes.len() as u32).to_le_bytes();
+    std::ptr::copy_nonoverlapping(size.as_ptr(), len, size.len());
+
+    Box::into_raw(bytes) as *mut u8
+}
+
+pub(crate) unsafe fn ff_get_object(
+    type_id: i64,
+    ptr: *const u8,
+    len: *mut u8,
+) -> *mut u8 {
+    let id = read_string(ptr, len);
+    let row = STORE.with(|store| {
+        store
+            .borrow()
+            .objects
+            .get(&type_id)
+            .and_then(|rows| rows.get(&id).cloned())
+    });
+
+    match row {
+        Some(row) => into_raw(serialize(&row), len),
+        None => std::ptr::null_mut(),
+    }
+}
+
+pub(crate) unsafe fn ff_find_many(type_id: i64, ptr: *const u8, len: *mut u8) -> *mut u8 {
+    let constraints = read_string(ptr, len);
+    let rows = STORE.with(|store| store.borrow().find_many(type_id, &constraints));
+
+    if rows.is_empty() {
+        return std::ptr::null_mut();
+    }
+
+    let objects = rows.iter().map(serialize).collect::<Vec<_>>();
+    into_raw(serialize(&objects), len)
+}
+
+pub(crate) unsafe fn ff_delete_many(type_id: i64, ptr: *const u8, len: *mut u8) -> u64 {
+    let constraints = read_string(ptr, len);
+    STORE.with(|store| store.borrow_mut().delete_many(type_id, &constraints)) as u64
+}
+
+pub(crate) unsafe fn ff_log_data(ptr: *const u8, len: u32, log_level: u32) {
+    let message = String::from_utf8_lossy(std::slice::from_raw_parts(ptr, len as usize));
+    record_log(LogRecord {
+        level: log_level,
+        target: String::new(),
+        message: message.to_string(),
+        fields: Vec::new(),
+    });
+}
+
+pub(crate) unsafe fn ff_log_record(ptr: *const u8, len: u32) {
+    let bytes = read_bytes(ptr, len as usize);
+    match bincode::deserialize(&bytes) {
+        Ok(record) => record_log(record),
+        Err(_) => exit(WasmIndexerError::DeserializationError),
+    }
+}
+
+pub(crate) unsafe fn ff_set_block_height(_height: u32) {}
+
+pub(crate) unsafe fn ff_put_object(type_id: i64, ptr: *const u8, len: u32) {
+    let bytes = read_bytes(ptr, len as usize);
+    let row: Vec<FtColumn> = match deserialize(&bytes) {
+        Ok(row) => row,
+        Err(_) => exit(WasmIndexerError::DeserializationError),
+    };
+
+    let id = row.iter().find_map(|column| match column {
+        FtColumn::ID(Some(id)) => Some(id.to_string()),
+        _ => None,
+    });
+
+    match id {
+        Some(id) => STORE.with(|store| {
+            store
+                .borrow_mut()
+                .objects
+                .entry(type_id)
+                .or_default()
+                .insert(id, row);
+        }),
+        None => exit(WasmIndexerError::PutObjectError),
+    }
+}
+
+// List fields are part of the row of an entity, so the join table records are
+// not needed to load or find it.
+pub(crate) unsafe fn ff_put_many_to_many_record(_ptr: *const u8, _len: u32) {}
+
+pub(crate) unsafe fn ff_early_exit(err_code: u32) {
+    exit(WasmIndexerError::from(err_code))
+}
+
+pub(crate) unsafe fn ff_watch_contract(ptr: *const u8, len: u32) {
+    let contract_id =
+        match ContractId::try_from(std::slice::from_raw_parts(ptr, len as usize)) {
+            Ok(contract_id) => contract_id,
+            Err(_) => exit(WasmIndexerError::DeserializationError),
+        };
+
+    STORE.with(|store| {
+        let mut store = store.borrow_mut();
+        let contract_id = hex::encode(contract_id);
+        if !store.watched_contracts.contains(&contract_id) {
+            store.watched_contracts.push(contract_id);
+        }
+    });
+}
+
+pub(crate) unsafe fn ff_get_watched_contracts(len: *mut u8) -> *mut u8 {
+    let contract_ids = STORE.with(|store| store.borrow().watched_contracts.clone());
+    if contract_ids.is_empty() {
+        return std::ptr::null_mut();
+    }
+
+    into_raw(serialize(&contract_ids), len)
+}
+
+fn record_log(record: LogRecord) {
+    STORE.with(|store| store.borrow_mut().logs.push(record));
+}
+
+/// Register the column names of an entity type, which are needed to evaluate
+/// the constraints of `find` and `delete_many`.
+pub(crate) fn register_columns(type_id: i64, columns: &'static [&'static str]) {
+    STORE.with(|store| {
+        store.borrow_mut().columns.insert(type_id, columns);
+    });
+}
+
+/// Run the handlers of an indexer against the given blocks.
+///
+/// `handle_events` is the `handle_events_internal` function generated by the
+/// `#[indexer]` attribute.
+pub fn run_handlers(
+    handle_events: fn(*mut u8, usize) -> anyhow::Result<()>,
+    blocks: Vec<BlockData>,
+) -> anyhow::Result<()> {
+    // Like the indexer service, forget the contracts watched by a previous batch,
+    // so that they're loaded from the store again.
+    reset_watched_contracts();
+
+    // `handle_events` takes ownership of the bytes using `Vec::from_raw_parts`.
+    let bytes = serialize(&blocks).into_boxed_slice();
+    let len = bytes.len();
+    let ptr = Box::into_raw(bytes) as *mut u8;
+
+    handle_events(ptr, len)
+}
+
+/// Clear the store of the current test.
+pub fn reset() {
+    STORE.with(|store| *store.borrow_mut() = Store::default());
+    reset_watched_contracts();
+}
+
+/// Log records emitted by the handlers in the current test.
+pub fn logs() -> Vec<LogRecord> {
+    STORE.with(|store| store.borrow().logs.clone())
+}
+
+/// Contracts watched by the handlers in the current test.
+pub fn watched_contracts() -> Vec<ContractId> {
+    STORE.with(|store| {
+        store
+            .borrow()
+            .watched_contracts
+            .iter()
+            .filter_map(|id| <[u8; 32]>::from_hex(id).ok().map(ContractId::from))
+            .collect()
+    })
+}
+
+/// Builder for a `BlockData`.
+#[derive(Debug, Clone)]
+pub struct BlockBuilder {
+    block: BlockData,
+}
+
+impl BlockBuilder {
+    /// Create a builder for an empty block at the given height.
+    pub fn new(height: u32) -> Self {
+        let id = Bytes32::from(height_bytes(height));
+        Self {
+            block: BlockData {
+                height,
+                id,
+                header: Header {
+                    id,
+                    da_height: 0,
+                    transactions_count: 0,
+                    message_receipt_count: 0,
+                    transactions_root: Bytes32::zeroed(),
+                    message_receipt_root: Bytes32::zeroed(),
+                    height,
+                    prev_root: Bytes32::zeroed(),
+                    time: 0,
+                    application_hash: Bytes32::zeroed(),
+                },
+                producer: None,
+                time: 0,
+                consensus: Consensus::default(),
+                transactions: Vec::new(),
+            },
+        }
+    }
+
+    /// Set the ID of the block.
+    pub fn id(mut self, id: Bytes32) -> Self {
+        self.block.id = id;
+        self.block.header.id = id;
+        self
+    }
+
+    /// Set the time of the block, as a UNIX timestamp.
+    pub fn time(mut self, time: i64) -> Self {
+        self.block.time = time;
+        self.block.header.time = time;
+        self
+    }
+
+    /// Set the producer of the block.
+    pub fn producer(mut self, producer: [u8; 32]) -> Self {
+        self.block.producer = Some(producer.into());
+        self
+    }
+
+    /// Add a transaction to the block.
+    pub fn transaction(mut self, transaction: impl Into<TransactionData>) -> Self {
+        self.block.transactions.push(transaction.into());
+        self.block.header.transactions_count = self.block.transactions.len() as u64;
+        self
+    }
+
+    pub fn build(self) -> BlockData {
+        self.block
+    }
+}
+
+impl From<BlockBuilder> for BlockData {
+    fn from(builder: BlockBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Builder for a `TransactionData`.
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    transaction: TransactionData,
+}
+
+impl Default for TransactionBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransactionBuilder {
+    /// Create a builder for a transaction without receipts.
+    pub fn new() -> Self {
+        Self {
+            transaction: TransactionData {
+                transaction: Transaction::default(),
+                status: TransactionStatus::default(),
+                receipts: Vec::new(),
+                id: TxId::zeroed(),
+            },
+        }
+    }
+
+    /// Set the ID of the transaction.
+    pub fn id(mut self, id: TxId) -> Self {
+        self.transaction.id = id;
+        self
+    }
+
+    /// Set the transaction.
+    pub fn transaction(mut self, transaction: Transaction) -> Self {
+        self.transaction.transaction = transaction;
+        self
+    }
+
+    /// Set the status of the transaction.
+    pub fn status(mut self, status: TransactionStatus) -> Self {
+        self.transaction.status = status;
+        self
+    }
+
+    /// Add a receipt to the transaction.
+    pub fn receipt(mut self, receipt: Receipt) -> Self {
+        self.transaction.receipts.push(receipt);
+        self
+    }
+
+    /// Add receipts to the transaction.
+    pub fn receipts(mut self, receipts: impl IntoIterator<Item = Receipt>) -> Self {
+        self.transaction.receipts.extend(receipts);
+        self
+    }
+
+    pub fn build(self) -> TransactionData {
+        self.transaction
+    }
+}
+
+impl From<TransactionBuilder> for TransactionData {
+    fn from(builder: TransactionBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// A `Call` receipt of a call from `id` to the function of contract `to` with the
+/// given selector.
+pub fn call(id: ContractId, to: ContractId, selector: u64) -> Receipt {
+    Receipt::Call {
+        id: <[u8; 32]>::from(id).into(),
+        to: <[u8; 32]>::from(to).into(),
+        amount: 0,
+        asset_id: Default::default(),
+        gas: 0,
+        param1: selector,
+        param2: 0,
+        pc: 0,
+        is: 0,
+    }
+}
+
+/// A `ReturnData` receipt of the given ABI-encoded data, returned by contract `id`.
+pub fn return_data(id: ContractId, data: Vec<u8>) -> Receipt {
+    Receipt::ReturnData {
+        id: <[u8; 32]>::from(id).into(),
+        ptr: 0,
+        len: data.len() as u64,
+        digest: Default::default(),
+        data: Some(data),
+        pc: 0,
+        is: 0,
+    }
+}
+
+/// A `LogData` receipt of the given ABI-encoded data, logged by contract `id` with
+/// the log ID `rb`.
+pub fn log_data(id: ContractId, rb: u64, data: Vec<u8>) -> Receipt {
+    Receipt::LogData {
+        id: <[u8; 32]>::from(id).into(),
+        ra: 0,
+        rb,
+        ptr: 0,
+        len: data.len() as u64,
+        digest: Default::default(),
+        data: Some(data),
+        pc: 0,
+        is: 0,
+    }
+}
+
+/// A `Transfer` receipt of `amount` of `asset_id` from contract `id` to contract
+/// `to`.
+pub fn transfer(
+    id: ContractId,
+    to: ContractId,
+    amount: u64,
+    asset_id: AssetId,
+) -> Receipt {
+    Receipt::Transfer {
+        id: <[u8; 32]>::from(id).into(),
+        to: <[u8; 32]>::from(to).into(),
+        amount,
+        asset_id: <[u8; 32]>::from(asset_id).into(),
+        pc: 0,
+        is: 0,
+    }
+}
+
+fn height_bytes(height: u32) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[28..].copy_from_slice(&height.to_be_bytes());
+    bytes
+}
+
+impl Store {
+    /// The rows of the given type that satisfy the given constraints, in order.
+    fn find_many(&self, type_id: i64, constraints: &str) -> Vec<Vec<FtColumn>> {
+        let Some(rows) = self.objects.get(&type_id) else {
+            return Vec::new();
+        };
+
+        let query = Query::parse(constraints);
+        let columns = self.columns.get(&type_id).copied().unwrap_or_default();
+
+        let mut rows = rows
+            .values()
+            .filter(|row| query.matches(columns, row))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for (expr, asc) in query.order_by.iter().rev() {
+            rows.sort_by(|a, b| {
+                let ordering =
+                    eval(expr, columns, a).cmp_nulls_last(&eval(expr, columns, b));
+                if *asc {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+
+        if let Some(limit) = query.limit {
+            rows.truncate(limit);
+        }
+
+        rows
+    }
+
+    /// Delete the rows of the given type that satisfy the given constraints.
+    fn delete_many(&mut self, type_id: i64, constraints: &str) -> usize {
+        let ids = self
+            .find_many(type_id, constraints)
+            .iter()
+            .filter_map(|row| {
+                row.iter().find_map(|column| match column {
+                    FtColumn::ID(Some(id)) => Some(id.to_string()),
+                    _ => None,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if let Some(rows) = self.objects.get_mut(&type_id) {
+            for id in ids.iter() {
+                rows.remove(id);
+            }
+        }
+
+        ids.len()
+    }
+}
+
+/// Constraints of a `find` or `delete_many`, e.g.
+/// `amount > 100 ORDER BY amount DESC LIMIT 1`.
+struct Query {
+    selection: Option<Expr>,
+    order_by: Vec<(Expr, bool)>,
+    limit: Option<usize>,
+}
+
+impl Query {
+    fn parse(constraints: &str) -> Self {
+        let sql = format!("SELECT * FROM t WHERE {constraints}");
+        let statement = Parser::parse_sql(&GenericDialect {}, &sql)
+            .ok()
+            .and_then(|mut statements| statements.pop());
+
+        let query = match statement {
+            Some(Statement::Query(query)) => *query,
+            _ => panic!("Could not parse constraints: {constraints}"),
+        };
+
+        let selection = match *query.body {
+            SetExpr::Select(select) => select.selection,
+            _ => None,
+        };
+
+        let order_by = query
+            .order_by
+            .into_iter()
+            .map(|o| (o.expr, o.asc.unwrap_or(true)))
+            .collect();
+
+        let limit = query.limit.map(|limit| match eval(&limit, &[], &[]) {
+            Datum::Number(n) => n.parse().unwrap_or(usize::MAX),
+            _ => panic!("Unsupported LIMIT in constraints: {constraints}"),
+        });
+
+        Self {
+            selection,
+            order_by,
+            limit,
+        }
+    }
+
+    fn matches(&self, columns: &[&str], row: &[FtColumn]) -> bool {
+        match &self.selection {
+            Some(expr) => eval(expr, columns, row) == Datum::Bool(true),
+            None => true,
+        }
+    }
+}
+
+/// The value of an expression, or of a column.
+#[derive(Debug, Clone, PartialEq)]
+enum Datum {
+    Null,
+    Bool(bool),
+    Number(String),
+    Text(String),
+}
+
+impl Datum {
+    /// Compare two values like SQL does, where any comparison with `NULL` is `NULL`.
+    fn compare(&self, other: &Datum) -> Option<Ordering> {
+        match (self, other) {
+            (Datum::Null, _) | (_, Datum::Null) => None,
+            (Datum::Bool(a), Datum::Bool(b)) => Some(a.cmp(b)),
+            (Datum::Number(a), Datum::Number(b)) => compare_integers(a, b)
+                .or_else(|| a.parse::<f64>().ok()?.partial_cmp(&b.parse::<f64>().ok()?)),
+            (Datum::Text(a), Datum::Text(b)) => Some(a.cmp(b)),
+            // Hex-encoded values are compared with numbers as text, like IDs.
+            (Datum::Number(a), Datum::Text(b)) | (Datum::Text(a), Datum::Number(b)) => {
+                Some(a.cmp(b))
+            }
+            _ => None,
+        }
+    }
+
+    /// Compare two values for `ORDER BY`, where `NULL`s come last like in Postgres.
+    fn cmp_nulls_last(&self, other: &Datum) -> Ordering {
+        match (self, other) {
+            (Datum::Null, Datum::Null) => Ordering::Equal,
+            (Datum::Null, _) => Ordering::Greater,
+            (_, Datum::Null) => Ordering::Less,
+            _ => self.compare(other).unwrap_or(Ordering::Equal),
+        }
+    }
+
+    fn from_bool(value: Option<bool>) -> Self {
+        value.map(Datum::Bool).unwrap_or(Datum::Null)
+    }
+}
+
+/// Compare two decimal integers of any size, such as 256-bit integers, which
+/// do not fit into primitive types.
+fn compare_integers(a: &str, b: &str) -> Option<Ordering> {
+    let parse = |s: &str| -> Option<(bool, String)> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let digits = digits.trim_start_matches('0');
+        Some((negative && !digits.is_empty(), digits.to_string()))
+    };
+
+    let (a_negative, a) = parse(a)?;
+    let (b_negative, b) = parse(b)?;
+    let magnitude = a.len().cmp(&b.len()).then_with(|| a.cmp(&b));
+
+    Some(match (a_negative, b_negative) {
+        (false, false) => magnitude,
+        (true, true) => magnitude.reverse(),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+    })
+}
+
+impl From<&sql::Value> for Datum {
+    fn from(value: &sql::Value) -> Self {
+        match value {
+            sql::Value::Null => Datum::Null,
+            sql::Value::Boolean(b) => Datum::Bool(*b),
+            sql::Value::Number(n, _) => Datum::Number(n.to_string()),
+            sql::Value::SingleQuotedString(s) | sql::Value::DoubleQuotedString(s) => {
+                Datum::Text(s.clone())
+            }
+            other => Datum::Text(other.to_string()),
+        }
+    }
+}
+
+impl From<&FtColumn> for Datum {
+    fn from(column: &FtColumn) -> Self {
+        // Columns are converted into values the same way as they are inserted into
+        // the database.
+        let fragment = column.query_fragment();
+        match Parser::new(&GenericDialect {})
+            .try_with_sql(&fragment)
+            .and_then(|mut parser| parser.parse_expr())
+        {
+            Ok(expr) => eval(&expr, &[], &[]),
+            Err(_) => Datum::Text(fragment),
+        }
+    }
+}
+
+/// Evaluate an expression against the given row.
+fn eval(expr: &Expr, columns: &[&str], row: &[FtColumn]) -> Datum {
+    match expr {
+        Expr::Identifier(ident) => column(&ident.value, columns, row),
+        Expr::CompoundIdentifier(idents) => match idents.last() {
+            Some(ident) => column(&ident.value, columns, row),
+            None => Datum::Null,
+        },
+        Expr::Value(value) => Datum::from(value),
+        Expr::Nested(expr) | Expr::Cast { expr, .. } => eval(expr, columns, row),
+        Expr::IsNull(expr) => Datum::Bool(eval(expr, columns, row) == Datum::Null),
+        Expr::IsNotNull(expr) => Datum::Bool(eval(expr, columns, row) != Datum::Null),
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr,
+        } => match eval(expr, columns, row) {
+            Datum::Bool(b) => Datum::Bool(!b),
+            _ => Datum::Null,
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => match eval(expr, columns, row) {
+            Datum::Number(n) => Datum::Number(format!("-{n}")),
+            _ => Datum::Null,
+        },
+        Expr::BinaryOp { left, op, right } => {
+            let left = eval(left, columns, row);
+            let right = eval(right, columns, row);
+            match op {
+                BinaryOperator::And => match (left, right) {
+                    (Datum::Bool(false), _) | (_, Datum::Bool(false)) => {
+                        Datum::Bool(false)
+                    }
+                    (Datum::Bool(true), Datum::Bool(true)) => Datum::Bool(true),
+                    _ => Datum::Null,
+                },
+                BinaryOperator::Or => match (left, right) {
+                    (Datum::Bool(true), _) | (_, Datum::Bool(true)) => Datum::Bool(true),
+                    (Datum::Bool(false), Datum::Bool(false)) => Datum::Bool(false),
+                    _ => Datum::Null,
+                },
+                BinaryOperator::Eq => {
+                    Datum::from_bool(left.compare(&right).map(Ordering::is_eq))
+                }
+                BinaryOperator::NotEq => {
+                    Datum::from_bool(left.compare(&right).map(Ordering::is_ne))
+                }
+                BinaryOperator::Lt => {
+                    Datum::from_bool(left.compare(&right).map(Ordering::is_lt))
+                }
+                BinaryOperator::LtEq => {
+                    Datum::from_bool(left.compare(&right).map(Ordering::is_le))
+                }
+                BinaryOperator::Gt => {
+                    Datum::from_bool(left.compare(&right).map(Ordering::is_gt))
+                }
+                BinaryOperator::GtEq => {
+                    Datum::from_bool(left.compare(&right).map(Ordering::is_ge))
+                }
+                op => panic!("Unsupported operator in constraints: {op}"),
+            }
+        }
+        Expr::InList {
+            expr,
+            list,
+            negated,
+        } => {
+            let value = eval(expr, columns, row);
+            let found = list.iter().any(|item| {
+                value.compare(&eval(item, columns, row)) == Some(Ordering::Equal)
+            });
+            match value {
+                Datum::Null => Datum::Null,
+                _ => Datum::Bool(found != *negated),
+            }
+        }
+        Expr::Between {
+            expr,
+            negated,
+            low,
+            high,
+        } => {
+            let value = eval(expr, columns, row);
+            let above = value.compare(&eval(low, columns, row)).map(Ordering::is_ge);
+            let below = value
+                .compare(&eval(high, columns, row))
+                .map(Ordering::is_le);
+            match (above, below) {
+                (Some(above), Some(below)) => Datum::Bool((above && below) != *negated),
+                _ => Datum::Null,
+            }
+        }
+        expr => panic!("Unsupported expression in constraints: {expr}"),
+    }
+}
+
+/// The value of the named column of the given row.
+fn column(name: &str, columns: &[&str], row: &[FtColumn]) -> Datum {
+    match columns.iter().position(|column| *column == name) {
+        Some(index) => row.get(index).map(Datum::from).unwrap_or(Datum::Null),
+        None => panic!("Unknown column in constraints: {name}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        find::{Field, OptionField},
+        wasm::{is_watched_contract, watch_contract, Entity, Logger},
+    };
+    use fuel_indexer_lib::graphql::MAX_FOREIGN_KEY_LIST_FIELDS;
+    use fuel_indexer_schema::join::JoinMetadata;
+    use fuel_indexer_types::{ffi::LOG_LEVEL_INFO, scalar::UID};
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Account {
+        id: UID,
+        balance: u64,
+        label: Option<String>,
+    }
+
+    impl<'a> Entity<'a> for Account {
+        const TYPE_ID: i64 = 1;
+        const JOIN_METADATA: Option<
+            [Option<JoinMetadata<'a>>; MAX_FOREIGN_KEY_LIST_FIELDS],
+        > = None;
+        const COLUMNS: &'static [&'static str] = &["id", "balance", "label"];
+
+        fn from_row(row: Vec<FtColumn>) -> Self {
+            match &row[..] {
+                [FtColumn::ID(Some(id)), FtColumn::U64(Some(balance)), FtColumn::String(label)] => {
+                    Self {
+                        id: id.clone(),
+                        balance: *balance,
+                        label: label.clone(),
+                    }
+                }
+                _ => panic!("Unexpected row: {row:?}"),
+            }
+        }
+
+        fn to_row(&self) -> Vec<FtColumn> {
+            vec![
+                FtColumn::ID(Some(self.id.clone())),
+                FtColumn::U64(Some(self.balance)),
+                FtColumn::String(self.label.clone()),
+            ]
+        }
+    }
+
+    impl Account {
+        fn new(n: u8, balance: u64, label: Option<&str>) -> Self {
+            Self {
+                id: id(n),
+                balance,
+                label: label.map(str::to_string),
+            }
+        }
+
+        fn id() -> Field<Account, UID> {
+            Field::new("id".to_string())
+        }
+
+        fn balance() -> Field<Account, u64> {
+            Field::new("balance".to_string())
+        }
+
+        fn label() -> OptionField<Account, String> {
+            OptionField::new("label".to_string())
+        }
+    }
+
+    fn id(n: u8) -> UID {
+        UID::new(hex::encode([n; 32])).unwrap()
+    }
+
+    fn balances(accounts: Vec<Account>) -> Vec<u64> {
+        accounts.iter().map(|account| account.balance).collect()
+    }
+
+    #[test]
+    fn test_entities_are_saved_and_loaded() {
+        let account = Account::new(1, 100, Some("savings"));
+        account.save();
+
+        assert_eq!(Account::load(id(1)), Some(account.clone()));
+        assert_eq!(Account::load(id(2)), None);
+
+        // Saving an entity with the same ID replaces it.
+        let updated = Account::new(1, 50, None);
+        updated.save();
+        assert_eq!(Account::load(id(1)), Some(updated));
+
+        reset();
+        assert_eq!(Account::load(id(1)), None);
+    }
+
+    #[test]
+    fn test_entities_are_found_by_their_fields() {
+        Account::new(1, 10, Some("a")).save();
+        Account::new(2, 200, None).save();
+        Account::new(3, 30, Some("c")).save();
+
+        assert_eq!(
+            Account::find(Account::id().eq(id(2))),
+            Some(Account::new(2, 200, None))
+        );
+        assert_eq!(Account::find(Account::balance().gt(1000)), None);
+
+        let found = Account::find_many(
+            Account::balance()
+                .gt(20)
+                .order_by(Account::balance().desc())
+                .limit(10),
+        );
+        assert_eq!(balances(found), vec![200, 30]);
+
+        let found = Account::find_many(
+            Account::balance()
+                .lt(100)
+                .or(Account::label().is_null())
+                .order_by(Account::balance().asc())
+                .limit(2),
+        );
+        assert_eq!(balances(found), vec![10, 30]);
+
+        // Comparisons with NULL are never satisfied.
+        let found = Account::find_many(Account::label().ne("a".to_string()));
+        assert_eq!(balances(found), vec![30]);
+    }
+
+    #[test]
+    fn test_entities_are_deleted_by_their_fields() {
+        Account::new(1, 10, None).save();
+        Account::new(2, 200, None).save();
+        Account::new(3, 30, None).save();
+
+        assert_eq!(Account::delete_many(Account::balance().lt(100)), 2);
+        assert_eq!(Account::delete_many(Account::balance().lt(100)), 0);
+
+        assert_eq!(Account::load(id(1)), None);
+        assert_eq!(Account::load(id(2)), Some(Account::new(2, 200, None)));
+    }
+
+    #[test]
+    fn test_constraints_are_evaluated_like_sql() {
+        let row = Account::new(1, 100, None).to_row();
+        let matches =
+            |constraints: &str| Query::parse(constraints).matches(Account::COLUMNS, &row);
+
+        assert!(matches("balance = 100"));
+        assert!(matches("balance >= 100 AND balance <= 100"));
+        assert!(matches("balance BETWEEN 50 AND 150"));
+        assert!(!matches("balance NOT BETWEEN 50 AND 150"));
+        assert!(matches("balance IN (1, 100)"));
+        assert!(!matches("balance NOT IN (1, 100)"));
+        assert!(matches("label IS NULL"));
+        assert!(!matches("label IS NOT NULL"));
+
+        // Comparisons with NULL are neither true nor false.
+        assert!(!matches("label = 'a'"));
+        assert!(!matches("NOT (label = 'a')"));
+        assert!(!matches("label = 'a' AND balance = 100"));
+        assert!(matches("label = 'a' OR balance = 100"));
+        assert!(matches(
+            "(label = 'a' OR balance > 10) AND NOT balance > 1000"
+        ));
+
+        let query = Query::parse("balance > 1 ORDER BY balance DESC, id LIMIT 2");
+        assert_eq!(query.order_by.len(), 2);
+        assert!(!query.order_by[0].1);
+        assert!(query.order_by[1].1);
+        assert_eq!(query.limit, Some(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown column in constraints: owner")]
+    fn test_constraints_on_unknown_columns_fail() {
+        let row = Account::new(1, 100, None).to_row();
+        Query::parse("owner = 'abc'").matches(Account::COLUMNS, &row);
+    }
+
+    #[test]
+    fn test_integers_of_any_size_are_compared_as_numbers() {
+        assert_eq!(compare_integers("10", "9"), Some(Ordering::Greater));
+        assert_eq!(compare_integers("-10", "9"), Some(Ordering::Less));
+        assert_eq!(compare_integers("-10", "-9"), Some(Ordering::Less));
+        assert_eq!(compare_integers("007", "7"), Some(Ordering::Equal));
+        assert_eq!(compare_integers("-0", "0"), Some(Ordering::Equal));
+        assert_eq!(compare_integers("1.5", "2"), None);
+
+        let max_u256 = "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+        assert_eq!(
+            compare_integers(max_u256, "18446744073709551615"),
+            Some(Ordering::Greater)
+        );
+
+        let number = |n: &str| Datum::Number(n.to_string());
+        assert_eq!(number("1.5").compare(&number("2")), Some(Ordering::Less));
+        assert_eq!(number("1").compare(&Datum::Null), None);
+        assert_eq!(Datum::Null.cmp_nulls_last(&number("1")), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_blocks_and_transactions_are_built() {
+        let contract = ContractId::from([1; 32]);
+        let transaction = TransactionBuilder::new()
+            .id(TxId::from([7; 32]))
+            .receipt(transfer(
+                contract,
+                ContractId::zeroed(),
+                100,
+                AssetId::zeroed(),
+            ))
+            .receipts(vec![
+                call(ContractId::zeroed(), contract, 42),
+                return_data(contract, vec![1, 2, 3]),
+                log_data(contract, 9, vec![4, 5]),
+            ])
+            .build();
+
+        assert_eq!(transaction.id, TxId::from([7; 32]));
+        assert_eq!(transaction.receipts.len(), 4);
+        assert!(matches!(
+            transaction.receipts[0],
+            Receipt::Transfer { amount: 100, .. }
+        ));
+        assert!(matches!(
+            transaction.receipts[1],
+            Receipt::Call { param1: 42, .. }
+        ));
+        assert!(matches!(
+            &transaction.receipts[2],
+            Receipt::ReturnData { len: 3, data: Some(data), .. } if data[..] == [1, 2, 3]
+        ));
+        assert!(matches!(
+            transaction.receipts[3],
+            Receipt::LogData { rb: 9, len: 2, .. }
+        ));
+
+        let block = BlockBuilder::new(42)
+            .time(1_700_000_000)
+            .producer([3; 32])
+            .transaction(transaction)
+            .transaction(TransactionBuilder::new())
+            .build();
+
+        assert_eq!(block.height, 42);
+        assert_eq!(block.header.height, 42);
+        assert_eq!(block.id, block.header.id);
+        assert_eq!(block.time, 1_700_000_000);
+        assert_eq!(block.header.time, 1_700_000_000);
+        assert!(block.producer.is_some());
+        assert_eq!(block.transactions.len(), 2);
+        assert_eq!(block.header.transactions_count, 2);
+
+        // Blocks at different heights have different IDs, unless set explicitly.
+        assert_ne!(
+            BlockBuilder::new(1).build().id,
+            BlockBuilder::new(2).build().id
+        );
+        let id = Bytes32::from([5; 32]);
+        assert_eq!(BlockBuilder::new(1).id(id).build().header.id, id);
+    }
+
+    fn save_block_accounts(blob: *mut u8, len: usize) -> anyhow::Result<()> {
+        let bytes = unsafe { Vec::from_raw_parts(blob, len, len) };
+        let blocks: Vec<BlockData> = deserialize(&bytes).map_err(anyhow::Error::msg)?;
+
+        for block in blocks {
+            Account::new(block.height as u8, block.transactions.len() as u64, None)
+                .save();
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_handlers_are_run_against_blocks() {
+        let blocks = vec![
+            BlockBuilder::new(1).build(),
+            BlockBuilder::new(2)
+                .transaction(TransactionBuilder::new())
+                .build(),
+        ];
+
+        run_handlers(save_block_accounts, blocks).unwrap();
+
+        assert_eq!(Account::load(id(1)), Some(Account::new(1, 0, None)));
+        assert_eq!(Account::load(id(2)), Some(Account::new(2, 1, None)));
+    }
+
+    #[test]
+    fn test_logs_and_watched_contracts_are_recorded() {
+        Logger::info("Handled block");
+        assert_eq!(logs().len(), 1);
+        assert_eq!(logs()[0].level, LOG_LEVEL_INFO);
+        assert_eq!(logs()[0].message, "Handled block");
+
+        let contract = ContractId::from([1; 32]);
+        watch_contract(&contract);
+        assert!(is_watched_contract(&[1; 32]));
+        assert_eq!(watched_contracts(), vec![contract]);
+
+        // Watched contracts are reloaded from the store before each batch.
+        run_handlers(save_block_accounts, Vec::new()).unwrap();
+        assert!(is_watched_contract(&[1; 32]));
+
+        reset();
+        assert!(!is_watched_contract(&[1; 32]));
+        assert!(logs().is_empty());
+    }
+
+    #[test]
+    fn test_threads_do_not_share_state() {
+        std::thread::spawn(|| {
+            Account::new(1, 100, None).save();
+            watch_contract(&ContractId::from([1; 32]));
+            assert!(is_watched_contract(&[1; 32]));
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(Account::load(id(1)), None);
+        assert!(!is_watched_contract(&[1; 32]));
+        assert!(watched_contracts().is_empty());
+    }
+}
@@ -10,31 +10,209 @@ use fuel_indexer_schema::{
     join::{JoinMetadata, RawQuery},
     FtColumn,
 };
-use fuel_indexer_types::{ffi::*, scalar::UID};
+use fuel_indexer_types::{
+    ffi::*,
+    scalar::{ContractId, UID},
+};
 
 pub use bincode;
 pub use hex::FromHex;
 pub use sha2::{Digest, Sha256};
 pub use std::collections::{HashMap, HashSet};
 
+use std::cell::{Cell, RefCell};
+
 pub use crate::find::{Field, Filter, ManyFilter, OptionField, SingleFilter};
 
 // These are instantiated with functions which return
 // `Result<T, WasmIndexerError>`. `wasmer` unwraps the `Result` and uses the
 // `Err` variant for early exit.
+#[cfg(not(any(feature = "native", feature = "testing")))]
 extern "C" {
     fn ff_get_object(type_id: i64, ptr: *const u8, len: *mut u8) -> *mut u8;
     fn ff_find_many(type_id: i64, ptr: *const u8, len: *mut u8) -> *mut u8;
     fn ff_delete_many(type_id: i64, ptr: *const u8, len: *mut u8) -> u64;
     fn ff_log_data(ptr: *const u8, len: u32, log_level: u32);
+    fn ff_log_record(ptr: *const u8, len: u32);
+    fn ff_set_block_height(height: u32);
     fn ff_put_object(type_id: i64, ptr: *const u8, len: u32);
     fn ff_put_many_to_many_record(ptr: *const u8, len: u32);
     fn ff_early_exit(err_code: u32);
+    fn ff_watch_contract(ptr: *const u8, len: u32);
+    fn ff_get_watched_contracts(len: *mut u8) -> *mut u8;
+}
+
+// Native indexers call the indexer service directly.
+#[cfg(all(feature = "native", not(feature = "testing")))]
+use crate::native::{
+    ff_delete_many, ff_early_exit, ff_find_many, ff_get_object, ff_get_watched_contracts,
+    ff_log_data, ff_log_record, ff_put_many_to_many_record, ff_put_object,
+    ff_set_block_height, ff_watch_contract,
+};
+
+// Indexers compiled for unit tests use an in-memory store.
+#[cfg(feature = "testing")]
+use crate::testing::{
+    ff_delete_many, ff_early_exit, ff_find_many, ff_get_object, ff_get_watched_contracts,
+    ff_log_data, ff_log_record, ff_put_many_to_many_record, ff_put_object,
+    ff_set_block_height, ff_watch_contract,
+};
+
+thread_local! {
+    /// Contract IDs that the indexer has started watching at runtime.
+    ///
+    /// Loaded from the indexer service on first use, and reset before each batch of
+    /// blocks, since a failed batch discards the contracts it started watching.
+    ///
+    /// Native indexers and unit tests run handlers on several threads of the same
+    /// process, so this is kept per thread rather than in a global.
+    static WATCHED_CONTRACTS: RefCell<Option<HashSet<[u8; 32]>>> = RefCell::new(None);
+
+    /// Name of the handler being run.
+    static HANDLER_NAME: Cell<&'static str> = Cell::new("");
 }
 
-// TODO: more to do here, hook up to 'impl log::Log for Logger'
+/// Start watching the given contract.
+///
+/// Receipts from this contract will be dispatched to handlers in addition to
+/// those from the contracts in the manifest's `contract_id`, starting with the
+/// next transaction processed. The contract ID is persisted along with the rest
+/// of the block's data, so it remains watched when the indexer is restarted.
+pub fn watch_contract(contract_id: &ContractId) {
+    let id = <[u8; 32]>::from(*contract_id);
+
+    unsafe {
+        ff_watch_contract(id.as_ptr(), id.len() as u32);
+    }
+
+    with_watched_contracts(|contracts| contracts.insert(id));
+}
+
+/// Returns `true` if the given contract was watched at runtime via `watch_contract`.
+pub fn is_watched_contract(contract_id: &[u8; 32]) -> bool {
+    with_watched_contracts(|contracts| contracts.contains(contract_id))
+}
+
+/// Forget the cached set of watched contracts so that it is reloaded from the
+/// indexer service on next use.
+pub fn reset_watched_contracts() {
+    WATCHED_CONTRACTS.with(|contracts| contracts.borrow_mut().take());
+}
+
+fn with_watched_contracts<T>(f: impl FnOnce(&mut HashSet<[u8; 32]>) -> T) -> T {
+    let mut contracts = WATCHED_CONTRACTS
+        .with(|contracts| contracts.borrow_mut().take())
+        .unwrap_or_else(load_watched_contracts);
+
+    let result = f(&mut contracts);
+    WATCHED_CONTRACTS.with(|c| *c.borrow_mut() = Some(contracts));
+
+    result
+}
+
+fn load_watched_contracts() -> HashSet<[u8; 32]> {
+    unsafe {
+        let mut bufflen = 0u32.to_le_bytes();
+        let ptr = ff_get_watched_contracts(bufflen.as_mut_ptr());
+
+        if ptr.is_null() {
+            return HashSet::new();
+        }
+
+        let len = u32::from_le_bytes(bufflen) as usize;
+        let bytes = Vec::from_raw_parts(ptr, len, len);
+        let ids: Vec<String> = match deserialize(&bytes) {
+            Ok(ids) => ids,
+            Err(_) => early_exit(WasmIndexerError::DeserializationError),
+        };
+
+        ids.iter()
+            .map(|id| match <[u8; 32]>::from_hex(id) {
+                Ok(id) => id,
+                Err(_) => early_exit(WasmIndexerError::DeserializationError),
+            })
+            .collect()
+    }
+}
+
+/// Set the name of the handler being run, which the indexer service reports
+/// when the handler fails.
+pub fn set_handler_name(name: &'static str) {
+    HANDLER_NAME.with(|handler_name| handler_name.set(name));
+
+    // Native indexers report the name to the indexer service directly.
+    #[cfg(all(feature = "native", not(feature = "testing")))]
+    crate::native::report_handler_name(name);
+}
+
+/// Forget the name of the handler that was last run, so that a failure outside
+/// of any handler is not attributed to it.
+pub fn reset_handler_name() {
+    set_handler_name("");
+}
+
+#[no_mangle]
+/// Get a pointer to the name of the handler being run.
+fn get_handler_name_ptr() -> *const u8 {
+    HANDLER_NAME.with(|handler_name| handler_name.get().as_ptr())
+}
+
+#[no_mangle]
+/// Get the length of the name of the handler being run.
+fn get_handler_name_len() -> u32 {
+    HANDLER_NAME.with(|handler_name| handler_name.get().len() as u32)
+}
+
+/// Format the message of a panic, along with the location in the source code at
+/// which it occurred.
+pub fn panic_message(info: &std::panic::PanicInfo) -> String {
+    let payload = info.payload();
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    };
+
+    match info.location() {
+        Some(location) => format!(
+            "{message} at {}:{}:{}",
+            location.file(),
+            location.line(),
+            location.column()
+        ),
+        None => message,
+    }
+}
+
+/// Logger that forwards log messages to the indexer service.
+///
+/// `Logger` also implements `log::Log`, so handlers can use the macros of the `log`
+/// crate, including key/value fields (e.g., `log::info!(owner = id; "Minted")`).
+/// The indexer service attaches the UID of the indexer, and the height of the block
+/// being processed, to each message.
 pub struct Logger;
 
+static LOGGER: Logger = Logger;
+
+/// Install `Logger` as the logger of the `log` facade.
+///
+/// Called by WASM indexers before their handlers run. Native indexers share the
+/// `log` facade with the indexer service they are linked into, so they must not
+/// call this.
+pub fn init_logger() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(log::LevelFilter::Trace);
+    }
+}
+
+/// Set the height of the block being processed, which the indexer service
+/// attaches to the messages logged by handlers.
+pub fn set_block_height(height: u32) {
+    unsafe { ff_set_block_height(height) }
+}
+
 impl Logger {
     pub fn error(log: &str) {
         unsafe { ff_log_data(log.as_ptr(), log.len() as u32, LOG_LEVEL_ERROR) }
@@ -57,6 +235,52 @@ impl Logger {
     }
 }
 
+impl log::Log for Logger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        let level = match record.level() {
+            log::Level::Error => LOG_LEVEL_ERROR,
+            log::Level::Warn => LOG_LEVEL_WARN,
+            log::Level::Info => LOG_LEVEL_INFO,
+            log::Level::Debug => LOG_LEVEL_DEBUG,
+            log::Level::Trace => LOG_LEVEL_TRACE,
+        };
+
+        let mut fields = LogFields(Vec::new());
+        // `LogFields` never fails to visit a pair.
+        let _ = record.key_values().visit(&mut fields);
+
+        let record = LogRecord {
+            level,
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            fields: fields.0,
+        };
+
+        let bytes = serialize(&record);
+        unsafe { ff_log_record(bytes.as_ptr(), bytes.len() as u32) }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Collects the key/value fields of a log record.
+struct LogFields(Vec<(String, String)>);
+
+impl<'kvs> log::kv::Visitor<'kvs> for LogFields {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
+
 /// Trait for a type entity which supports the `delete()` operation.
 pub trait EntityDelete<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
     /// Deletes the entity with the corresponding `ID` from the database.
@@ -75,6 +299,9 @@ pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
     /// Necessary metadata for saving an entity's list type fields.
     const JOIN_METADATA: Option<[Option<JoinMetadata<'a>>; MAX_FOREIGN_KEY_LIST_FIELDS]>;
 
+    /// Names of the columns of an entity's row, in the order of `to_row`.
+    const COLUMNS: &'static [&'static str] = &[];
+
     /// Convert database row representation into an instance of an entity.
     fn from_row(vec: Vec<FtColumn>) -> Self;
 
@@ -121,18 +348,16 @@ pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
 
             let ptr = ff_get_object(Self::TYPE_ID, buff.as_ptr(), bufflen.as_mut_ptr());
 
-            if !ptr.is_null() {
-                let len = u32::from_le_bytes(bufflen) as usize;
-                let bytes = Vec::from_raw_parts(ptr, len, len);
-                match deserialize(&bytes) {
-                    Ok(vec) => Some(Self::from_row(vec)),
-                    Err(_) => {
-                        early_exit(WasmIndexerError::DeserializationError);
-                    }
-                };
+            if ptr.is_null() {
+                return None;
             }
 
-            None
+            let len = u32::from_le_bytes(bufflen) as usize;
+            let bytes = Vec::from_raw_parts(ptr, len, len);
+            match deserialize(&bytes) {
+                Ok(vec) => Some(Self::from_row(vec)),
+                Err(_) => early_exit(WasmIndexerError::DeserializationError),
+            }
         }
     }
 
@@ -144,6 +369,9 @@ pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
 
     /// Finds the entities that satisfy the given constraints.
     fn find_many(filter: impl Into<ManyFilter<Self>>) -> Vec<Self> {
+        #[cfg(feature = "testing")]
+        crate::testing::register_columns(Self::TYPE_ID, Self::COLUMNS);
+
         unsafe {
             let filter: ManyFilter<Self> = filter.into();
             let buff = bincode::serialize(&filter.to_string())
@@ -172,6 +400,9 @@ pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
 
     /// Delete the entities that satisfy the given constraints.
     fn delete_many(filter: impl Into<ManyFilter<Self>>) -> usize {
+        #[cfg(feature = "testing")]
+        crate::testing::register_columns(Self::TYPE_ID, Self::COLUMNS);
+
         let filter: ManyFilter<Self> = filter.into();
         let buff =
             bincode::serialize(&filter.to_string()).expect("Failed to serialize query");
@@ -222,3 +453,55 @@ pub fn early_exit(err_code: WasmIndexerError) -> ! {
     unsafe { ff_early_exit(err_code as u32) }
     unreachable!("Expected termination of WASM exetution after a call to ff_early_exit.")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, panic};
+
+    thread_local! {
+        static PANIC_MESSAGE: RefCell<Option<String>> = RefCell::new(None);
+    }
+
+    // Run `f`, and return the message of its panic, as formatted by `panic_message`.
+    fn message_of_panic(f: fn()) -> String {
+        assert!(panic::catch_unwind(f).is_err());
+        PANIC_MESSAGE
+            .with(|message| message.borrow_mut().take())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_panic_message_includes_the_payload_and_location() {
+        // The panic hook is global, so all cases are run by a single test.
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(|info| {
+            PANIC_MESSAGE
+                .with(|message| *message.borrow_mut() = Some(panic_message(info)))
+        }));
+
+        let messages = [
+            message_of_panic(|| panic!("Transfer has no recipient")),
+            message_of_panic(|| panic!("Transfer {} has no recipient", 42)),
+            message_of_panic(|| panic::panic_any(42)),
+        ];
+
+        panic::set_hook(hook);
+
+        let location = format!(" at {}:", file!());
+        assert!(messages[0].starts_with(&format!("Transfer has no recipient{location}")));
+        assert!(
+            messages[1].starts_with(&format!("Transfer 42 has no recipient{location}"))
+        );
+        assert!(messages[2].starts_with(&format!("Box<dyn Any>{location}")));
+
+        // The location ends with the line and column of the panic.
+        let position = messages[0].rsplit(&location).next().unwrap();
+        let position = position
+            .split(':')
+            .map(|n| n.parse::<u32>())
+            .collect::<Vec<_>>();
+        assert_eq!(position.len(), 2);
+        assert!(position.iter().all(Result::is_ok));
+    }
+}
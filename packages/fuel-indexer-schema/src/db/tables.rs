@@ -172,6 +172,22 @@ impl IndexerSchema {
 
         statements.extend(constraint_stmnts);
 
+        // History tables aren't mapped to a `TypeId`, so their columns aren't
+        // registered; they're written to alongside their tables.
+        let history_tables = tables
+            .iter()
+            .filter_map(|t| t.history_table())
+            .collect::<Vec<Table>>();
+
+        for table in history_tables.iter() {
+            statements.push(table.create());
+            statements.extend(table.constraints().iter().map(|c| c.create()));
+        }
+
+        // Likewise, the column recording the height at which the rows of a table
+        // with a retention policy were written isn't registered.
+        statements.extend(tables.iter().flat_map(|t| t.retention_stmnts()));
+
         for stmnt in statements.iter() {
             queries::execute_query(conn, stmnt.to_owned()).await?;
         }
@@ -24,6 +24,10 @@ pub struct JoinMetadata<'a> {
 
     /// Position in the parent's set of `FtColumn`s, at which the many-to-many child column is found.
     pub child_position: usize,
+
+    /// Whether the parent is immutable, in which case its records are inserted only
+    /// once, and the join table has no primary key to resolve conflicts on.
+    pub immutable: bool,
 }
 
 /// A raw SQL query.
@@ -54,6 +58,7 @@ impl RawQuery {
             parent_column_name,
             child_column_name,
             child_position,
+            immutable,
         } = metadata;
 
         let (parent_typedef_name, child_typedef_name) =
@@ -76,11 +81,18 @@ impl RawQuery {
         match list_type_field {
             FtColumn::Array(list) => {
                 if let Some(list) = list {
+                    let mut children = Vec::new();
                     list.iter().for_each(|item| {
+                        // Without conflict resolution, a child listed twice must
+                        // only be inserted once.
+                        let child = item.query_fragment();
+                        if children.contains(&child) {
+                            return;
+                        }
+
                         // We could also use id.query_fragment() here
-                        query.push_str(
-                            format!(" ('{}', {}),", id, item.query_fragment()).as_str(),
-                        );
+                        query.push_str(format!(" ('{}', {}),", id, child).as_str());
+                        children.push(child);
                     });
                 }
             }
@@ -95,9 +107,13 @@ impl RawQuery {
         if !query.is_empty() {
             // Trim the trailing comma
             query.pop();
-            query.push_str(&format!(
+            if *immutable {
+                query.push(';');
+            } else {
+                query.push_str(&format!(
                     " ON CONFLICT({parent_typedef_name}_{parent_column_name}, {child_typedef_name}_{child_column_name}) DO NOTHING;"
                 ));
+            }
         }
 
         Self(query)
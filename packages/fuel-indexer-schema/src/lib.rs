@@ -63,6 +63,7 @@ pub enum FtColumn {
     Enum(Option<String>),
     I128(Option<I128>),
     I16(Option<I16>),
+    I256(Option<I256>),
     I32(Option<I32>),
     I64(Option<I64>),
     I8(Option<I8>),
@@ -70,8 +71,10 @@ pub enum FtColumn {
     Identity(Option<Identity>),
     Json(Option<Json>),
     String(Option<String>),
+    Timestamp(Option<Timestamp>),
     U128(Option<U128>),
     U16(Option<U16>),
+    U256(Option<U256>),
     U32(Option<U32>),
     U64(Option<U64>),
     U8(Option<U8>),
@@ -164,6 +167,14 @@ impl FtColumn {
                 Some(val) => format!("{val}"),
                 None => String::from(NULL_VALUE),
             },
+            FtColumn::I256(value) => match value {
+                Some(val) => format!("{val}"),
+                None => String::from(NULL_VALUE),
+            },
+            FtColumn::U256(value) => match value {
+                Some(val) => format!("{val}"),
+                None => String::from(NULL_VALUE),
+            },
             FtColumn::Json(value) => match value {
                 Some(val) => {
                     let x = &val.0;
@@ -175,6 +186,10 @@ impl FtColumn {
                 Some(val) => format!("'{val}'"),
                 None => String::from(NULL_VALUE),
             },
+            FtColumn::Timestamp(value) => match value {
+                Some(val) => format!("'{val}'"),
+                None => String::from(NULL_VALUE),
+            },
             FtColumn::Identity(value) => match value {
                 Some(val) => match val {
                     Identity::Address(v) => format!("'{v:x}'",),
@@ -310,6 +325,41 @@ mod tests {
         insta::assert_yaml_snapshot!(int2.query_fragment());
     }
 
+    #[test]
+    fn test_fragments_256_bit_integers() {
+        use super::*;
+
+        assert_eq!(
+            FtColumn::U256(Some(U256::MAX)).query_fragment(),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        assert_eq!(
+            FtColumn::I256(Some(I256::MIN)).query_fragment(),
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819968"
+        );
+        assert_eq!(
+            FtColumn::I256(Some(I256::from(-42))).query_fragment(),
+            "-42"
+        );
+        assert_eq!(FtColumn::U256(None).query_fragment(), "NULL");
+    }
+
+    #[test]
+    fn test_fragments_timestamp() {
+        use super::*;
+
+        assert_eq!(
+            FtColumn::Timestamp(Some(Timestamp::from_unix(1_700_000_000)))
+                .query_fragment(),
+            "'2023-11-14T22:13:20Z'"
+        );
+        assert_eq!(
+            FtColumn::Timestamp(Some(Timestamp::from_unix(-1))).query_fragment(),
+            "'1969-12-31T23:59:59Z'"
+        );
+        assert_eq!(FtColumn::Timestamp(None).query_fragment(), "NULL");
+    }
+
     #[test]
     fn test_fragments_none_types() {
         use super::*;
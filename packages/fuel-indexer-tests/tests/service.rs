@@ -1,9 +1,21 @@
 extern crate alloc;
+use axum::response::IntoResponse;
 use fuel_indexer::prelude::fuel::{BlockData, Consensus, Header};
-use fuel_indexer::{Executor, IndexerConfig, WasmIndexExecutor};
+use fuel_indexer::{
+    native, Database, Executor, FtColumn, IndexerConfig, IndexerError,
+    NativeIndexExecutor, WasmIndexExecutor,
+};
+use fuel_indexer_database::{queries, DbType, IndexerConnectionPool};
 use fuel_indexer_lib::WasmIndexerError;
-use fuel_indexer_lib::{config::DatabaseConfig, manifest::Manifest};
+use fuel_indexer_lib::{
+    config::DatabaseConfig,
+    graphql::GraphQLSchema,
+    manifest::Manifest,
+    utils::{serialize, EntityChangeKind},
+};
+use fuel_indexer_schema::db::tables::IndexerSchema;
 use fuel_indexer_tests::fixtures::TestPostgresDb;
+use fuel_indexer_types::scalar::UID;
 use fuel_types::Bytes32;
 use std::str::FromStr;
 
@@ -83,6 +95,9 @@ async fn test_wasm_executor_can_meter_execution() {
                 }
             }
         }
+        fuel_indexer_lib::manifest::Module::Native => {
+            unreachable!("The test indexer uses a WASM module.")
+        }
     }
 }
 
@@ -202,5 +217,569 @@ async fn test_wasm_executor_error_codes() {
                 }
             }
         }
+        fuel_indexer_lib::manifest::Module::Native => {
+            unreachable!("The test indexer uses a WASM module.")
+        }
+    }
+}
+
+fn native_test_manifest() -> Manifest {
+    if let Ok(mut current_dir) = std::env::current_dir() {
+        if current_dir.ends_with("fuel-indexer-tests") {
+            current_dir.pop();
+            current_dir.pop();
+        }
+
+        if let Err(e) = std::env::set_current_dir(current_dir) {
+            eprintln!("Failed to change directory: {}", e);
+        }
+    }
+
+    Manifest::from_file(
+        "packages/fuel-indexer-tests/indexers/fuel-indexer-test/fuel_indexer_test.yaml",
+    )
+    .unwrap()
+}
+
+fn native_test_block(height: u32) -> BlockData {
+    BlockData {
+        height,
+        id: Bytes32::zeroed(),
+        header: Header {
+            id: Bytes32::zeroed(),
+            da_height: 1,
+            transactions_count: 0,
+            message_receipt_count: 0,
+            transactions_root: Bytes32::zeroed(),
+            message_receipt_root: Bytes32::zeroed(),
+            height,
+            prev_root: Bytes32::zeroed(),
+            time: 0,
+            application_hash: Bytes32::zeroed(),
+        },
+        producer: None,
+        time: 0,
+        consensus: Consensus::Unknown,
+        transactions: vec![],
     }
 }
+
+async fn native_test_executor(
+    test_db: &TestPostgresDb,
+    handle_events_fn: fuel_indexer::NativeHandlerFn,
+) -> NativeIndexExecutor {
+    let manifest = native_test_manifest();
+    let pool =
+        fuel_indexer_database::IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let schema_version = manifest
+        .graphql_schema_content()
+        .unwrap()
+        .version()
+        .to_string();
+
+    NativeIndexExecutor::new(
+        &IndexerConfig::default(),
+        &manifest,
+        pool,
+        schema_version,
+        handle_events_fn,
+    )
+    .await
+    .unwrap()
+}
+
+// Native handlers call the host functions that the entity API of
+// `fuel-indexer-plugin` calls when it is built with the `native` feature.
+
+fn handle_blocks(blocks: Vec<BlockData>) -> anyhow::Result<()> {
+    for block in blocks {
+        native::set_block_height(block.height).map_err(|e| anyhow::anyhow!("{e}"))?;
+        native::set_handler_name("handle_block");
+        native::log_data("Handled block", fuel_indexer_types::ffi::LOG_LEVEL_INFO)
+            .map_err(|e| anyhow::anyhow!("{e}"))?;
+    }
+
+    Ok(())
+}
+
+fn panic_in_handler(blocks: Vec<BlockData>) -> anyhow::Result<()> {
+    let height = blocks.first().map(|block| block.height).unwrap_or_default();
+    native::set_block_height(height).map_err(|e| anyhow::anyhow!("{e}"))?;
+    native::set_handler_name("handle_transfer");
+    panic!("Transfer has no recipient");
+}
+
+fn fail_outside_handlers(_blocks: Vec<BlockData>) -> anyhow::Result<()> {
+    anyhow::bail!("Failed to decode block")
+}
+
+fn load_object(_blocks: Vec<BlockData>) -> anyhow::Result<()> {
+    // Like the entity API, exit with the error of a failed call.
+    if let Err(e) = native::get_object(1, "1".to_string()) {
+        std::panic::panic_any(e);
+    }
+
+    Ok(())
+}
+
+fn drop_logs(_blocks: Vec<BlockData>) -> anyhow::Result<()> {
+    native::record_dropped_logs(2);
+    native::record_dropped_logs(1);
+    Ok(())
+}
+
+/// Return the `executor_dropped_logs` metric of the indexer with the given UID.
+async fn dropped_logs_metric(uid: &str) -> u64 {
+    let response = fuel_indexer_metrics::encode_metrics_response().into_response();
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let prefix = format!("executor_dropped_logs_total{{path=\"{uid}\"}} ");
+
+    String::from_utf8(body.to_vec())
+        .unwrap()
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix))
+        .map_or(0, |count| count.parse().unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_native_executor_runs_handlers() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let mut executor = native_test_executor(&test_db, handle_blocks).await;
+
+    executor
+        .handle_events(vec![native_test_block(1), native_test_block(2)])
+        .await
+        .unwrap();
+
+    // Nothing is reported for an execution that succeeded.
+    assert!(executor.get_handler_failure().await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_native_executor_records_dropped_logs() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let mut executor = native_test_executor(&test_db, drop_logs).await;
+    let uid = native_test_manifest().uid();
+
+    // Other tests may drop logs of the same indexer concurrently.
+    let before = dropped_logs_metric(&uid).await;
+    executor
+        .handle_events(vec![native_test_block(1)])
+        .await
+        .unwrap();
+    assert!(dropped_logs_metric(&uid).await >= before + 3);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_native_executor_reports_handler_failures() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let mut executor = native_test_executor(&test_db, panic_in_handler).await;
+
+    let result = executor.handle_events(vec![native_test_block(7)]).await;
+    assert!(matches!(
+        result,
+        Err(IndexerError::NativeExecutionRuntimeError)
+    ));
+
+    let failure = executor.get_handler_failure().await.unwrap();
+    assert_eq!(failure.message, "Transfer has no recipient");
+    assert_eq!(failure.handler.as_deref(), Some("handle_transfer"));
+    assert_eq!(failure.block_height, Some(7));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_native_executors_do_not_share_handler_state() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let mut failing_handler = native_test_executor(&test_db, panic_in_handler).await;
+    let mut failing_outside_handlers =
+        native_test_executor(&test_db, fail_outside_handlers).await;
+
+    for _ in 0..10 {
+        let (first, second) = tokio::join!(
+            failing_handler.handle_events(vec![native_test_block(1)]),
+            failing_outside_handlers.handle_events(vec![native_test_block(1)]),
+        );
+        assert!(first.is_err());
+        assert!(second.is_err());
+
+        let failure = failing_handler.get_handler_failure().await.unwrap();
+        assert_eq!(failure.handler.as_deref(), Some("handle_transfer"));
+
+        // The name of the handler of the other indexer is not reported.
+        let failure = failing_outside_handlers
+            .get_handler_failure()
+            .await
+            .unwrap();
+        assert!(failure.message.contains("Failed to decode block"));
+        assert_eq!(failure.handler, None);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_native_executor_kill_switch() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let mut executor = native_test_executor(&test_db, load_object).await;
+
+    executor
+        .kill_switch()
+        .store(true, std::sync::atomic::Ordering::SeqCst);
+
+    let result = executor.handle_events(vec![native_test_block(1)]).await;
+    assert!(matches!(result, Err(IndexerError::KillSwitch)));
+}
+
+const IMMUTABLE_TEST_SCHEMA: &str = r#"
+type Transfer @entity @immutable {
+    id: ID!
+    amount: U64!
+}
+"#;
+
+// Deploy a schema with an immutable type, and return the manifest of its indexer.
+async fn immutable_test_manifest(test_db: &TestPostgresDb) -> (Manifest, String) {
+    let schema = GraphQLSchema::new(IMMUTABLE_TEST_SCHEMA.to_string());
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+
+    IndexerSchema::new("test", "immutable", &schema, DbType::Postgres)
+        .unwrap()
+        .commit(&schema, &mut conn)
+        .await
+        .unwrap();
+
+    let mut manifest = native_test_manifest();
+    manifest.set_namespace("test".to_string());
+    manifest.set_identifier("immutable".to_string());
+
+    (manifest, schema.version().to_string())
+}
+
+fn transfer_columns(amount: u64) -> Vec<FtColumn> {
+    vec![
+        FtColumn::ID(Some(UID::new(hex::encode([1u8; 32])).unwrap())),
+        FtColumn::U64(Some(amount)),
+    ]
+}
+
+fn save_transfer_twice(_blocks: Vec<BlockData>) -> anyhow::Result<()> {
+    let type_id = fuel_indexer_types::type_id("test_immutable", "Transfer");
+    for amount in [1, 2] {
+        if let Err(e) = native::put_object(type_id, serialize(&transfer_columns(amount)))
+        {
+            std::panic::panic_any(e);
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_native_executor_rejects_updates_of_immutable_entities() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (manifest, version) = immutable_test_manifest(&test_db).await;
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+
+    let mut executor = NativeIndexExecutor::new(
+        &IndexerConfig::default(),
+        &manifest,
+        pool,
+        version,
+        save_transfer_twice,
+    )
+    .await
+    .unwrap();
+
+    // The error is returned as is, rather than as a failure to retry.
+    let result = executor.handle_events(vec![native_test_block(1)]).await;
+    match result {
+        Err(IndexerError::ImmutableEntityError(entity, operation)) => {
+            assert!(entity.starts_with("test_immutable.transfer"));
+            assert_eq!(operation, "updated");
+        }
+        _ => panic!("Expected an ImmutableEntityError, got {result:?}"),
+    }
+}
+
+#[tokio::test]
+async fn test_database_rejects_deletes_of_immutable_entities() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (manifest, version) = immutable_test_manifest(&test_db).await;
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+
+    let mut db = Database::new(pool, &manifest, &IndexerConfig::default()).await;
+    db.load_schema(version).await.unwrap();
+
+    let type_id = fuel_indexer_types::type_id("test_immutable", "Transfer");
+    let columns = transfer_columns(1);
+
+    db.start_transaction().await.unwrap();
+    db.put_object(type_id, columns.clone(), serialize(&columns))
+        .await
+        .unwrap();
+
+    let result = db.delete_many(type_id, "amount = 1".to_string()).await;
+    assert!(matches!(
+        result,
+        Err(IndexerError::ImmutableEntityError(_, ref operation)) if operation == "deleted"
+    ));
+
+    // The rejection is kept for the executor to report.
+    assert!(db.take_immutable_entity_error().is_some());
+    assert!(db.take_immutable_entity_error().is_none());
+
+    db.revert_transaction().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_watched_contracts_are_persisted_with_their_block() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (manifest, _) = immutable_test_manifest(&test_db).await;
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+
+    let mut db = Database::new(pool.clone(), &manifest, &IndexerConfig::default()).await;
+    let contract_id = hex::encode([1u8; 32]);
+
+    // Watching a contract outside of a transaction is an error.
+    assert!(matches!(
+        db.watch_contract(contract_id.clone()).await,
+        Err(IndexerError::NoTransactionError(_))
+    ));
+
+    // A contract watched by a block that is reverted is not persisted.
+    db.start_transaction().await.unwrap();
+    db.watch_contract(contract_id.clone()).await.unwrap();
+    assert_eq!(
+        db.watched_contracts().await.unwrap(),
+        vec![contract_id.clone()]
+    );
+    db.revert_transaction().await.unwrap();
+
+    db.start_transaction().await.unwrap();
+    assert!(db.watched_contracts().await.unwrap().is_empty());
+
+    // Watching a contract twice is not an error.
+    db.watch_contract(contract_id.clone()).await.unwrap();
+    db.watch_contract(contract_id.clone()).await.unwrap();
+    db.commit_transaction().await.unwrap();
+
+    db.start_transaction().await.unwrap();
+    assert_eq!(
+        db.watched_contracts().await.unwrap(),
+        vec![contract_id.clone()]
+    );
+    db.revert_transaction().await.unwrap();
+
+    // Watched contracts are kept per indexer.
+    let mut other_manifest = manifest.clone();
+    other_manifest.set_identifier("other".to_string());
+    let mut other_db =
+        Database::new(pool.clone(), &other_manifest, &IndexerConfig::default()).await;
+    other_db.start_transaction().await.unwrap();
+    assert!(other_db.watched_contracts().await.unwrap().is_empty());
+    other_db.revert_transaction().await.unwrap();
+
+    // Removing the indexer and its data removes its watched contracts.
+    let mut conn = pool.acquire().await.unwrap();
+    queries::remove_indexer(&mut conn, "test", "immutable", true)
+        .await
+        .unwrap();
+    assert!(queries::watched_contracts(&mut conn, "test", "immutable")
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+fn metadata_columns(block_height: u32) -> Vec<FtColumn> {
+    vec![
+        FtColumn::ID(Some(
+            UID::new(hex::encode([block_height as u8; 32])).unwrap(),
+        )),
+        FtColumn::U64(Some(0)),
+        FtColumn::U32(Some(block_height)),
+        FtColumn::Bytes32(Some(Bytes32::zeroed())),
+    ]
+}
+
+async fn has_block_height_trigger(test_db: &TestPostgresDb, schema: &str) -> bool {
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let count: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM pg_trigger
+        WHERE tgname = 'trigger_ensure_block_height_consecutive'
+        AND tgrelid = $1::regclass;",
+    )
+    .bind(format!("{schema}.indexmetadataentity"))
+    .fetch_one(&mut conn)
+    .await
+    .unwrap();
+
+    count > 0
+}
+
+#[tokio::test]
+async fn test_debug_transaction_records_changes() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (manifest, version) = immutable_test_manifest(&test_db).await;
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+
+    let mut db = Database::new(pool, &manifest, &IndexerConfig::default()).await;
+    db.load_schema(version).await.unwrap();
+
+    let type_id = fuel_indexer_types::type_id("test_immutable", "IndexMetadataEntity");
+
+    // Changes are only recorded in debug transactions.
+    db.start_transaction().await.unwrap();
+    let columns = metadata_columns(1);
+    db.put_object(type_id, columns.clone(), serialize(&columns))
+        .await
+        .unwrap();
+    assert!(db.take_changes().is_empty());
+    db.revert_transaction().await.unwrap();
+
+    db.start_debug_transaction().await.unwrap();
+    for columns in [metadata_columns(1), metadata_columns(1)] {
+        db.put_object(type_id, columns.clone(), serialize(&columns))
+            .await
+            .unwrap();
+    }
+
+    let changes = db.take_changes();
+    let kinds = changes.iter().map(|change| change.kind).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![EntityChangeKind::Insert, EntityChangeKind::Update]
+    );
+    assert_eq!(changes[0].entity, "indexmetadataentity");
+    assert_eq!(changes[0].id, hex::encode([1u8; 32]));
+    assert!(changes[0].before.is_none());
+    assert_eq!(changes[1].before, changes[0].after);
+    assert_eq!(changes[1].after.as_ref().unwrap()["block_height"], 1);
+
+    // Taking the changes stops recording them.
+    let columns = metadata_columns(2);
+    db.put_object(type_id, columns.clone(), serialize(&columns))
+        .await
+        .unwrap();
+    assert!(db.take_changes().is_empty());
+
+    db.revert_transaction().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_debug_transaction_skips_the_block_height_check_without_dropping_it() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let (manifest, version) = immutable_test_manifest(&test_db).await;
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+
+    let mut conn = pool.acquire().await.unwrap();
+    queries::create_ensure_block_height_consecutive_trigger(
+        &mut conn,
+        "test",
+        "immutable",
+    )
+    .await
+    .unwrap();
+
+    let mut db = Database::new(pool, &manifest, &IndexerConfig::default()).await;
+    db.load_schema(version).await.unwrap();
+
+    let type_id = fuel_indexer_types::type_id("test_immutable", "IndexMetadataEntity");
+    let (first, later) = (metadata_columns(1), metadata_columns(5));
+
+    db.start_transaction().await.unwrap();
+    db.put_object(type_id, first.clone(), serialize(&first))
+        .await
+        .unwrap();
+    db.commit_transaction().await.unwrap();
+
+    // Blocks can be skipped in a debug transaction.
+    db.start_debug_transaction().await.unwrap();
+    db.put_object(type_id, later.clone(), serialize(&later))
+        .await
+        .unwrap();
+    db.revert_transaction().await.unwrap();
+    assert!(has_block_height_trigger(&test_db, "test_immutable").await);
+
+    // The check is only skipped within the debug transaction.
+    db.start_transaction().await.unwrap();
+    assert!(db
+        .put_object(type_id, later.clone(), serialize(&later))
+        .await
+        .is_err());
+    db.revert_transaction().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_wasm_executor_debugs_blocks_without_persisting_them() {
+    use async_std::{fs::File, io::ReadExt};
+
+    let manifest = native_test_manifest();
+    let fuel_indexer_lib::manifest::Module::Wasm(module) = manifest.module() else {
+        unreachable!("The test indexer uses a WASM module.")
+    };
+    let mut bytes = Vec::<u8>::new();
+    let mut file = File::open(module).await.unwrap();
+    file.read_to_end(&mut bytes).await.unwrap();
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let schema = manifest.graphql_schema_content().unwrap();
+
+    let mut conn = pool.acquire().await.unwrap();
+    IndexerSchema::new(
+        manifest.namespace(),
+        manifest.identifier(),
+        &schema,
+        DbType::Postgres,
+    )
+    .unwrap()
+    .commit(&schema, &mut conn)
+    .await
+    .unwrap();
+    queries::create_ensure_block_height_consecutive_trigger(
+        &mut conn,
+        manifest.namespace(),
+        manifest.identifier(),
+    )
+    .await
+    .unwrap();
+
+    let mut executor = WasmIndexExecutor::new(
+        &IndexerConfig::default(),
+        &manifest,
+        bytes,
+        pool,
+        schema.version().to_string(),
+    )
+    .await
+    .unwrap();
+
+    executor
+        .handle_events(vec![native_test_block(1)])
+        .await
+        .unwrap();
+
+    let report = executor.debug_block(native_test_block(5)).await.unwrap();
+    assert_eq!(report.block_height, 5);
+    assert_eq!(report.error, None);
+    assert!(report.changes.iter().any(|change| {
+        change.entity == "indexmetadataentity"
+            && change.kind == EntityChangeKind::Insert
+            && change.after.as_ref().unwrap()["block_height"] == 5
+    }));
+
+    let last_block_height = queries::last_block_height_for_indexer(
+        &mut conn,
+        manifest.namespace(),
+        manifest.identifier(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(last_block_height, 1);
+    assert!(
+        has_block_height_trigger(
+            &test_db,
+            &format!("{}_{}", manifest.namespace(), manifest.identifier())
+        )
+        .await
+    );
+}
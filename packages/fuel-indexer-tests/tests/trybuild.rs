@@ -45,6 +45,64 @@ module:
     )
 }
 
+fn manifest_with_multiple_contract_abis() -> String {
+    let (abi_root_str, tests_root_str, project_root_str, _) = test_dirs();
+    format!(
+        r#"
+namespace: test_namespace
+identifier: simple_wasm_executor
+abi: {tests_root_str}/contracts/simple-wasm/out/debug/contracts-abi.json
+graphql_schema: {tests_root_str}/indexers/simple-wasm/schema/simple_wasm.graphql
+contract_id: ~
+contracts:
+  - name: amm
+    abi: {abi_root_str}/AMM-contract-abi.json
+    contract_id: "0x39150017c9e38e5e280432d546fae345d6ce6d8fe4710162c2e3a95a6faff051"
+  - name: exchange
+    abi: {abi_root_str}/exchange-contract-abi.json
+module:
+    wasm: {project_root_str}/target/wasm32-unknown-unknown/release/simple_wasm.wasm"#
+    )
+}
+
+fn manifest_with_contract_abi_dispatch() -> String {
+    let (abi_root_str, tests_root_str, project_root_str, _) = test_dirs();
+    format!(
+        r#"
+namespace: test_namespace
+identifier: simple_wasm_executor
+abi: {tests_root_str}/contracts/simple-wasm/out/debug/contracts-abi.json
+graphql_schema: {tests_root_str}/indexers/simple-wasm/schema/simple_wasm.graphql
+contract_id: ~
+contracts:
+  - name: amm
+    abi: {abi_root_str}/AMM-contract-abi.json
+    contract_id: "0x1111111111111111111111111111111111111111111111111111111111111111"
+  - name: exchange
+    abi: {abi_root_str}/exchange-contract-abi.json
+    contract_id: "0x2222222222222222222222222222222222222222222222222222222222222222"
+module:
+    wasm: {project_root_str}/target/wasm32-unknown-unknown/release/simple_wasm.wasm"#
+    )
+}
+
+fn manifest_with_predicates() -> String {
+    let (abi_root_str, tests_root_str, project_root_str, _) = test_dirs();
+    format!(
+        r#"
+namespace: test_namespace
+identifier: simple_wasm_executor
+abi: {tests_root_str}/contracts/simple-wasm/out/debug/contracts-abi.json
+graphql_schema: {tests_root_str}/indexers/simple-wasm/schema/simple_wasm.graphql
+contract_id: ~
+predicates:
+  - name: swap
+    abi: {abi_root_str}/swap-predicate-abi.json
+module:
+    wasm: {project_root_str}/target/wasm32-unknown-unknown/release/simple_wasm.wasm"#
+    )
+}
+
 #[test]
 fn test_success_and_failure_macros() {
     let t = trybuild::TestCases::new();
@@ -113,6 +171,12 @@ module:
             TestKind::Fail,
             manifest_content.clone(),
         ),
+        (
+            "fail_if_unsupported_type_used_in_handler_args.rs",
+            "simple_wasm.yaml",
+            TestKind::Fail,
+            manifest_content.clone(),
+        ),
         (
             "pass_if_indexer_is_valid_single_type.rs",
             "simple_wasm_single.yaml",
@@ -131,6 +195,12 @@ module:
             TestKind::Pass,
             manifest_content.clone(),
         ),
+        (
+            "pass_if_raw_logs_with_a_log_handler_are_not_dropped.rs",
+            "simple_wasm_raw_logs.yaml",
+            TestKind::Pass,
+            manifest_content.clone(),
+        ),
         (
             "fail_if_abi_contains_reserved_fuel_type.rs",
             "invalid_abi_type_simple_wasm.yaml",
@@ -149,12 +219,6 @@ module:
             TestKind::Fail,
             manifest_content.clone(),
         ),
-        (
-            "fail_if_unsupported_type_used_in_handler_args.rs",
-            "simple_wasm.yaml",
-            TestKind::Fail,
-            manifest_content.clone(),
-        ),
         (
             "pass_if_using_sway_amm_abi.rs",
             "sway_amm.yaml",
@@ -222,6 +286,30 @@ module:
             TestKind::Pass,
             manifest_with_contract_abi("timelock-contract-abi.json"),
         ),
+        (
+            "pass_if_using_complex_abi_types.rs",
+            "complex_abi_types.yaml",
+            TestKind::Pass,
+            manifest_with_contract_abi("complex-types-contract-abi.json"),
+        ),
+        (
+            "pass_if_using_multiple_contract_abis.rs",
+            "multiple_contract_abis.yaml",
+            TestKind::Pass,
+            manifest_with_multiple_contract_abis(),
+        ),
+        (
+            "pass_if_receipts_are_decoded_with_the_abi_of_their_contract.rs",
+            "contract_abi_dispatch.yaml",
+            TestKind::Pass,
+            manifest_with_contract_abi_dispatch(),
+        ),
+        (
+            "pass_if_using_predicates.rs",
+            "predicates.yaml",
+            TestKind::Pass,
+            manifest_with_predicates(),
+        ),
     ];
 
     for (name, manifest_name, kind, manifest_content) in tests {
@@ -219,6 +219,21 @@ async fn test_querying_sql_endpoint_when_sql_not_enabled_returns_404() {
     assert_eq!(resp.status(), 404);
 }
 
+#[actix_web::test]
+async fn test_logs_endpoint_rejects_negative_limits() {
+    let WebTestComponents { server, client, .. } = setup_web_test_components(None).await;
+
+    let resp = client
+        .get("http://127.0.0.1:29987/api/logs/fuel_indexer_test/index1?limit=-1")
+        .send()
+        .await
+        .unwrap();
+
+    server.abort();
+
+    assert_eq!(resp.status(), 400);
+}
+
 #[actix_web::test]
 async fn test_querying_sql_endpoint_when_sql_is_enabled_returns_actual_query_response() {
     let config = IndexerConfig {
@@ -0,0 +1,45 @@
+//! Stand-ins for the functions that a WASM indexer imports from the indexer
+//! service, so that the trybuild tests link and run on the host.
+
+#[no_mangle]
+extern "C" fn ff_get_object(_type_id: i64, _ptr: *const u8, _len: *mut u8) -> *mut u8 {
+    std::ptr::null_mut()
+}
+
+#[no_mangle]
+extern "C" fn ff_find_many(_type_id: i64, _ptr: *const u8, _len: *mut u8) -> *mut u8 {
+    std::ptr::null_mut()
+}
+
+#[no_mangle]
+extern "C" fn ff_delete_many(_type_id: i64, _ptr: *const u8, _len: *mut u8) -> u64 {
+    0
+}
+
+#[no_mangle]
+extern "C" fn ff_log_data(_ptr: *const u8, _len: u32, _log_level: u32) {}
+
+#[no_mangle]
+extern "C" fn ff_log_record(_ptr: *const u8, _len: u32) {}
+
+#[no_mangle]
+extern "C" fn ff_set_block_height(_height: u32) {}
+
+#[no_mangle]
+extern "C" fn ff_put_object(_type_id: i64, _ptr: *const u8, _len: u32) {}
+
+#[no_mangle]
+extern "C" fn ff_put_many_to_many_record(_ptr: *const u8, _len: u32) {}
+
+#[no_mangle]
+extern "C" fn ff_early_exit(err_code: u32) {
+    panic!("Indexer exited early with error code {err_code}.");
+}
+
+#[no_mangle]
+extern "C" fn ff_watch_contract(_ptr: *const u8, _len: u32) {}
+
+#[no_mangle]
+extern "C" fn ff_get_watched_contracts(_len: *mut u8) -> *mut u8 {
+    std::ptr::null_mut()
+}
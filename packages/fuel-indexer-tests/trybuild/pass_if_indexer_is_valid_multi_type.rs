@@ -1,17 +1,7 @@
 extern crate alloc;
 use fuel_indexer_utils::prelude::*;
 
-#[no_mangle]
-fn ff_log_data(_inp: ()) {}
-
-#[no_mangle]
-fn ff_put_object(_inp: ()) {}
-
-#[no_mangle]
-fn ff_put_many_to_many_record(_inp: ()) {}
-
-#[no_mangle]
-fn ff_early_exit(_inp: ()) {}
+mod ffi_stubs;
 
 #[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm_multi.yaml")]
 mod indexer {
@@ -29,6 +19,14 @@ mod indexer {
         assert_eq!(account, Bits256([48u8; 32]));
         assert_eq!(hash, Bits256([56u8; 32]));
     }
+
+    fn function_three(_event: AnotherEvent, ctx: Context) {
+        assert_eq!(ctx.block_height, 0);
+        assert_eq!(ctx.tx_index, Some(0));
+        assert_eq!(ctx.receipt_index, Some(3));
+    }
+
+    fn function_four(_log: UndecodedLog) {}
 }
 
 fn main() {
@@ -0,0 +1,77 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod ffi_stubs;
+
+static LOGS: AtomicUsize = AtomicUsize::new(0);
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm_raw_logs.yaml")]
+mod indexer {
+    fn function_one(log: Log) {
+        assert_eq!(log.rb, 424242);
+        crate::LOGS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    let data: Vec<BlockData> = vec![BlockData {
+        id: [0u8; 32].into(),
+        time: 1,
+        producer: None,
+        height: 0,
+        consensus: fuel::Consensus::default(),
+        header: fuel::Header {
+            id: [0u8; 32].into(),
+            da_height: 1,
+            transactions_count: 1,
+            message_receipt_count: 1,
+            transactions_root: [0u8; 32].into(),
+            height: 1,
+            prev_root: [0u8; 32].into(),
+            time: 1,
+            application_hash: [0u8; 32].into(),
+            message_receipt_root: [0u8; 32].into(),
+        },
+        transactions: vec![fuel::TransactionData {
+            status: fuel::TransactionStatus::default(),
+            id: [0u8; 32].into(),
+            receipts: vec![
+                // A raw log is handled as a `Log`, although its `rb` is not a log ID
+                // of the ABI.
+                fuel::Receipt::Log {
+                    id: [0u8; 32].into(),
+                    ra: 1,
+                    rb: 424242,
+                    rc: 0,
+                    rd: 0,
+                    pc: 0,
+                    is: 0,
+                },
+                // A `LogData` with such an `rb` can't be decoded, and is dropped.
+                fuel::Receipt::LogData {
+                    id: [0u8; 32].into(),
+                    ra: 0,
+                    rb: 424242,
+                    ptr: 0,
+                    len: 1,
+                    digest: [0u8; 32].into(),
+                    data: Some(vec![1]),
+                    pc: 0,
+                    is: 0,
+                },
+            ],
+            transaction: fuel::Transaction::default(),
+        }],
+    }];
+
+    let mut bytes = serialize(&data);
+
+    let ptr = bytes.as_mut_ptr();
+    let len = bytes.len();
+
+    handle_events(ptr, len);
+
+    assert_eq!(LOGS.load(Ordering::SeqCst), 1);
+    assert_eq!(take_dropped_logs(), 1);
+}
@@ -0,0 +1,91 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+mod ffi_stubs;
+
+static AMM_ERRORS: AtomicUsize = AtomicUsize::new(0);
+static EXCHANGE_ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+// Both ABIs log an `InitError` with log ID 0 and type ID 3, but with different
+// variants, so each log only decodes correctly with the ABI of its own contract.
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/contract_abi_dispatch.yaml")]
+mod indexer {
+    fn function_one(error: amm::InitError) {
+        assert!(matches!(error, amm::InitError::PairDoesNotDefinePool));
+        crate::AMM_ERRORS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    fn function_two(error: exchange::InitError) {
+        assert!(matches!(error, exchange::InitError::IdenticalAssets));
+        crate::EXCHANGE_ERRORS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+fn log_data(id: [u8; 32], rb: u64, data: Vec<u8>) -> fuel::Receipt {
+    fuel::Receipt::LogData {
+        id: id.into(),
+        ra: 0,
+        rb,
+        ptr: 0,
+        len: data.len() as u64,
+        digest: [0u8; 32].into(),
+        data: Some(data),
+        pc: 0,
+        is: 0,
+    }
+}
+
+fn main() {
+    use fuels::core::codec::ABIEncoder;
+
+    // `PairDoesNotDefinePool` is the fourth variant of the AMM's `InitError`, which
+    // the exchange's `InitError` doesn't have.
+    let amm_error =
+        ABIEncoder::encode(&[amm::InitError::PairDoesNotDefinePool.into_token()])
+            .expect("Failed compile test")
+            .resolve(0);
+    let exchange_error =
+        ABIEncoder::encode(&[exchange::InitError::IdenticalAssets.into_token()])
+            .expect("Failed compile test")
+            .resolve(0);
+
+    let data: Vec<BlockData> = vec![BlockData {
+        id: [0u8; 32].into(),
+        time: 1,
+        producer: None,
+        height: 0,
+        consensus: fuel::Consensus::default(),
+        header: fuel::Header {
+            id: [0u8; 32].into(),
+            da_height: 1,
+            transactions_count: 1,
+            message_receipt_count: 1,
+            transactions_root: [0u8; 32].into(),
+            height: 1,
+            prev_root: [0u8; 32].into(),
+            time: 1,
+            application_hash: [0u8; 32].into(),
+            message_receipt_root: [0u8; 32].into(),
+        },
+        transactions: vec![fuel::TransactionData {
+            status: fuel::TransactionStatus::default(),
+            id: [0u8; 32].into(),
+            receipts: vec![
+                log_data([0x11; 32], 0, amm_error),
+                log_data([0x22; 32], 0, exchange_error),
+            ],
+            transaction: fuel::Transaction::default(),
+        }],
+    }];
+
+    let mut bytes = serialize(&data);
+
+    let ptr = bytes.as_mut_ptr();
+    let len = bytes.len();
+
+    handle_events(ptr, len);
+
+    assert_eq!(AMM_ERRORS.load(Ordering::SeqCst), 1);
+    assert_eq!(EXCHANGE_ERRORS.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,101 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+
+mod ffi_stubs;
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/complex_abi_types.yaml")]
+mod indexer {
+    fn function_one(_quote: (u64, bool), _trade: (SizedAsciiString<5>, Bits256, Order)) {}
+
+    fn function_two(_pair: Pair<u64, bool>, _either: Either<Side, Pair<u8, Bits256>>) {}
+
+    fn function_three(_pair: Option<Pair<u64, bool>>, _order: Option<Order>) {}
+
+    fn function_four(
+        _outcome: Result<u64, TradeError>,
+        _result: Result<Order, TradeError>,
+    ) {
+    }
+
+    fn function_five(_orders: Vec<Order>, _amounts: Vec<u64>) {}
+
+    fn function_six(_payload: fuels::types::Bytes, _name: String) {}
+
+    fn function_seven(_code: SizedAsciiString<5>, _message: fuels::types::AsciiString) {}
+
+    fn function_eight(_grid: [[u8; 3]; 2]) {}
+
+    fn function_nine(_status: Status, _either: Either<u64, Status>, _ctx: Context) {}
+
+    fn function_ten(call: TradeCall) {
+        let TradeCall { order, quote, .. } = call;
+        let _price: Option<u64> = order.price;
+        let _tags: Vec<u8> = order.tags;
+        let _quote: (u64, bool) = quote;
+    }
+
+    fn function_eleven(quote: (u64, bool), grid: [[u8; 3]; 2]) {
+        assert_eq!(quote, (7, true));
+        assert_eq!(grid, [[1, 2, 3], [4, 5, 6]]);
+    }
+}
+
+fn log_data(rb: u64, data: Vec<u8>) -> fuel::Receipt {
+    fuel::Receipt::LogData {
+        id: [0u8; 32].into(),
+        ra: 0,
+        rb,
+        ptr: 0,
+        len: data.len() as u64,
+        digest: [0u8; 32].into(),
+        data: Some(data),
+        pc: 0,
+        is: 0,
+    }
+}
+
+fn main() {
+    use fuels::core::codec::ABIEncoder;
+
+    // The `(u64, bool)` tuple is logged with ID 0 and the `[[u8; 3]; 2]` array
+    // with ID 8, and both are decoded for `function_eleven`.
+    let quote = ABIEncoder::encode(&[(7u64, true).into_token()])
+        .expect("Failed compile test")
+        .resolve(0);
+    let grid = ABIEncoder::encode(&[[[1u8, 2, 3], [4, 5, 6]].into_token()])
+        .expect("Failed compile test")
+        .resolve(0);
+
+    let data: Vec<BlockData> = vec![BlockData {
+        id: [0u8; 32].into(),
+        time: 1,
+        producer: None,
+        height: 0,
+        consensus: fuel::Consensus::default(),
+        header: fuel::Header {
+            id: [0u8; 32].into(),
+            da_height: 1,
+            transactions_count: 1,
+            message_receipt_count: 1,
+            transactions_root: [0u8; 32].into(),
+            height: 1,
+            prev_root: [0u8; 32].into(),
+            time: 1,
+            application_hash: [0u8; 32].into(),
+            message_receipt_root: [0u8; 32].into(),
+        },
+        transactions: vec![fuel::TransactionData {
+            status: fuel::TransactionStatus::default(),
+            id: [0u8; 32].into(),
+            receipts: vec![log_data(0, quote), log_data(8, grid)],
+            transaction: fuel::Transaction::default(),
+        }],
+    }];
+
+    let mut bytes = serialize(&data);
+
+    let ptr = bytes.as_mut_ptr();
+    let len = bytes.len();
+
+    handle_events(ptr, len);
+}
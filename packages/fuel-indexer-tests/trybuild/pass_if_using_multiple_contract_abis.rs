@@ -0,0 +1,18 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+
+mod ffi_stubs;
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/multiple_contract_abis.yaml")]
+mod indexer {
+    fn function_one(_event: amm::RegisterPoolEvent) {}
+
+    fn function_two(_event: exchange::AddLiquidityEvent) {}
+
+    fn function_three(_call: exchange::AddLiquidityCall) {}
+}
+
+fn main() {
+    // We're not actually testing the serialization of the events from the ABI JSON here,
+    // we're just testing that this compiles.
+}
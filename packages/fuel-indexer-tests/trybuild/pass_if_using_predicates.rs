@@ -0,0 +1,14 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+
+mod ffi_stubs;
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/predicates.yaml")]
+mod indexer {
+    fn function_one(_event: BlockData) {}
+}
+
+fn main() {
+    // We're not actually testing the serialization of the events from the ABI JSON here,
+    // we're just testing that this compiles.
+}
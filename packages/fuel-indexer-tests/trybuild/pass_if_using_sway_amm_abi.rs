@@ -1,21 +1,13 @@
 extern crate alloc;
 use fuel_indexer_utils::prelude::*;
 
-#[no_mangle]
-fn ff_log_data(_inp: ()) {}
-
-#[no_mangle]
-fn ff_put_object(_inp: ()) {}
-
-#[no_mangle]
-fn ff_put_many_to_many_record(_inp: ()) {}
-
-#[no_mangle]
-fn ff_early_exit(_inp: ()) {}
+mod ffi_stubs;
 
 #[indexer(manifest = "packages/fuel-indexer-tests/trybuild/sway_amm.yaml")]
 mod indexer {
     fn function_one(_event: RegisterPoolEvent) {}
+
+    fn function_two(_call: InitializeCall) {}
 }
 
 fn main() {
@@ -1,5 +1,26 @@
+use serde::{Deserialize, Serialize};
+
 pub const LOG_LEVEL_ERROR: u32 = 0;
 pub const LOG_LEVEL_WARN: u32 = 1;
 pub const LOG_LEVEL_INFO: u32 = 2;
 pub const LOG_LEVEL_DEBUG: u32 = 3;
 pub const LOG_LEVEL_TRACE: u32 = 4;
+
+/// A log record emitted through the `log` facade by an indexer's handlers.
+///
+/// The record is serialized by the indexer, and passed to the indexer service,
+/// which attaches the indexer's UID and the current block height to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// Level of the record, one of the `LOG_LEVEL_*` constants.
+    pub level: u32,
+
+    /// Target of the record, usually the module path of the handler.
+    pub target: String,
+
+    /// Formatted message of the record.
+    pub message: String,
+
+    /// Key/value fields attached to the record.
+    pub fields: Vec<(String, String)>,
+}
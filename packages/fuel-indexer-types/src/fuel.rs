@@ -95,10 +95,26 @@ pub struct Script {
     pub metadata: Option<ScriptMetadata>,
 }
 
+impl Script {
+    /// Address of the script data in VM memory, if the transaction's metadata is known.
+    ///
+    /// Pointers found in receipts (e.g., the arguments of a contract call made by
+    /// the script) can be resolved against the script data using this address.
+    pub fn script_data_address(&self) -> Option<usize> {
+        self.metadata
+            .as_ref()
+            .map(|metadata| metadata.tx_offset + metadata.script_data_offset)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ScriptMetadata {
     pub common: CommonMetadata,
     pub script_data_offset: usize,
+
+    /// Address of the transaction in VM memory, which depends on the transaction
+    /// parameters of the chain.
+    pub tx_offset: usize,
 }
 
 impl From<ScriptMetadata> for Json {
@@ -48,6 +48,38 @@ impl TypeId for LogData {
     }
 }
 
+/// A `Log` or `LogData` receipt that could not be decoded using the ABI of the
+/// contract that emitted it (e.g., because its log ID is not in the ABI's `logged_types`).
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub enum UndecodedLog {
+    Log(Log),
+    LogData(LogData),
+}
+
+impl UndecodedLog {
+    /// The contract that emitted the log.
+    pub fn contract_id(&self) -> &ContractId {
+        match self {
+            UndecodedLog::Log(log) => &log.contract_id,
+            UndecodedLog::LogData(log) => &log.contract_id,
+        }
+    }
+
+    /// The log ID (i.e., the `rb` register) of the log.
+    pub fn log_id(&self) -> u64 {
+        match self {
+            UndecodedLog::Log(log) => log.rb,
+            UndecodedLog::LogData(log) => log.rb,
+        }
+    }
+}
+
+impl TypeId for UndecodedLog {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "UndecodedLog") as usize
+    }
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct ScriptResult {
     pub result: u64,
@@ -3,7 +3,8 @@ pub use fuel_types::{
     MessageId, Nonce, Salt, Word,
 };
 use fuels::types::SizedAsciiString;
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
 
 /// Scalar for 256-bit value.
 pub type B256 = [u8; 32];
@@ -32,6 +33,314 @@ pub type U64 = u64;
 /// Scalar for 128-bit unsigned integers.
 pub type U128 = u128;
 
+uint::construct_uint! {
+    /// Scalar for 256-bit unsigned integers.
+    pub struct U256(4);
+}
+
+impl U256 {
+    /// Return the memory representation of this integer in little-endian byte order.
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        self.to_little_endian(&mut bytes);
+        bytes
+    }
+}
+
+impl Serialize for U256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // 256-bit integers do not fit into JSON numbers, so they are encoded as
+        // decimal strings.
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for U256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        U256::from_dec_str(&value).map_err(de::Error::custom)
+    }
+}
+
+/// Scalar for 256-bit signed integers, stored in two's complement.
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash, Default)]
+pub struct I256(pub U256);
+
+impl I256 {
+    /// The largest value of a 256-bit signed integer.
+    pub const MAX: I256 = I256(U256([u64::MAX, u64::MAX, u64::MAX, i64::MAX as u64]));
+
+    /// The smallest value of a 256-bit signed integer.
+    pub const MIN: I256 = I256(U256([0, 0, 0, 1 << 63]));
+
+    /// Whether this value is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.0.bit(255)
+    }
+
+    /// Return the memory representation of this integer in little-endian byte order.
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        self.0.to_le_bytes()
+    }
+
+    /// Absolute value of this integer, as an unsigned integer.
+    pub fn unsigned_abs(&self) -> U256 {
+        if self.is_negative() {
+            (!self.0).overflowing_add(U256::one()).0
+        } else {
+            self.0
+        }
+    }
+}
+
+impl From<i128> for I256 {
+    fn from(value: i128) -> Self {
+        if value < 0 {
+            I256(!U256::from(!(value as u128)))
+        } else {
+            I256(U256::from(value as u128))
+        }
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Flipping the sign bit maps two's complement onto unsigned order.
+        (self.0 ^ I256::MIN.0).cmp(&(other.0 ^ I256::MIN.0))
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for I256 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_negative() {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.unsigned_abs())
+    }
+}
+
+impl FromStr for I256 {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        if digits.is_empty() {
+            return Err(format!("Could not parse I256 from {s:?}."));
+        }
+        let abs = U256::from_dec_str(digits)
+            .map_err(|e| format!("Could not parse I256 from {s:?}: {e:?}"))?;
+
+        if negative && abs <= I256::MIN.0 {
+            Ok(I256((!abs).overflowing_add(U256::one()).0))
+        } else if !negative && abs <= I256::MAX.0 {
+            Ok(I256(abs))
+        } else {
+            Err(format!("Value {s:?} is out of range for I256."))
+        }
+    }
+}
+
+impl Serialize for I256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for I256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Scalar for points in time, in seconds since the Unix epoch.
+///
+/// Timestamps are formatted as RFC 3339 date-times in UTC, e.g. `2023-11-14T22:13:20Z`.
+#[derive(
+    Deserialize,
+    Serialize,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Debug,
+    Hash,
+    Default,
+)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    const SECONDS_PER_DAY: i64 = 86_400;
+
+    /// Create a `Timestamp` from a number of seconds since the Unix epoch.
+    pub fn from_unix(seconds: i64) -> Self {
+        Timestamp(seconds)
+    }
+
+    /// Number of seconds since the Unix epoch.
+    pub fn to_unix(&self) -> i64 {
+        self.0
+    }
+
+    /// Return the memory representation of this timestamp in little-endian byte order.
+    pub fn to_le_bytes(&self) -> [u8; 8] {
+        self.0.to_le_bytes()
+    }
+}
+
+/// Number of days since the Unix epoch for the given proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era =
+        year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date for the given number of days since the Unix epoch.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = self.0.div_euclid(Self::SECONDS_PER_DAY);
+        let seconds = self.0.rem_euclid(Self::SECONDS_PER_DAY);
+        let (year, month, day) = civil_from_days(days);
+        write!(
+            f,
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        )
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = String;
+
+    /// Parse an RFC 3339 date-time, e.g. `2023-11-14T22:13:20Z` or
+    /// `2023-11-14T23:13:20.5+01:00`, or a date, e.g. `2023-11-14`, which is
+    /// taken as midnight UTC. Fractions of a second are truncated.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || {
+            format!("Could not parse Timestamp from {s:?}. Expected an RFC 3339 date-time, e.g. \"2023-11-14T22:13:20Z\".")
+        };
+        let number = |part: &str| -> Result<i64, String> {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(err());
+            }
+            part.parse().map_err(|_| err())
+        };
+        let separator = |part: &str, i: usize, c: u8| -> Result<(), String> {
+            match part.as_bytes().get(i) {
+                Some(b) if *b == c => Ok(()),
+                _ => Err(err()),
+            }
+        };
+
+        if !s.is_ascii() {
+            return Err(err());
+        }
+
+        let (date, time) = match s.find(|c| matches!(c, 'T' | 't' | ' ')) {
+            Some(i) => (&s[..i], Some(&s[i + 1..])),
+            None => (s, None),
+        };
+
+        if date.len() != 10 {
+            return Err(err());
+        }
+        separator(date, 4, b'-')?;
+        separator(date, 7, b'-')?;
+        let (year, month, day) = (
+            number(&date[..4])?,
+            number(&date[5..7])?,
+            number(&date[8..])?,
+        );
+        let days = days_from_civil(year, month, day);
+        if civil_from_days(days) != (year, month, day) {
+            return Err(err());
+        }
+
+        let seconds = match time {
+            Some(time) => {
+                if time.len() < 9 {
+                    return Err(err());
+                }
+                separator(time, 2, b':')?;
+                separator(time, 5, b':')?;
+                let (hour, minute, second) = (
+                    number(&time[..2])?,
+                    number(&time[3..5])?,
+                    number(&time[6..8])?,
+                );
+                if hour > 23 || minute > 59 || second > 60 {
+                    return Err(err());
+                }
+
+                let mut offset = &time[8..];
+                if let Some(fraction) = offset.strip_prefix('.') {
+                    offset = fraction.trim_start_matches(|c: char| c.is_ascii_digit());
+                    if offset.len() == fraction.len() {
+                        return Err(err());
+                    }
+                }
+
+                let offset = match offset {
+                    "Z" | "z" => 0,
+                    _ if offset.len() == 6 => {
+                        separator(offset, 3, b':')?;
+                        let sign = match &offset[..1] {
+                            "+" => 1,
+                            "-" => -1,
+                            _ => return Err(err()),
+                        };
+                        let (hours, minutes) =
+                            (number(&offset[1..3])?, number(&offset[4..])?);
+                        if hours > 23 || minutes > 59 {
+                            return Err(err());
+                        }
+                        sign * (hours * 3600 + minutes * 60)
+                    }
+                    _ => return Err(err()),
+                };
+
+                hour * 3600 + minute * 60 + second - offset
+            }
+            None => 0,
+        };
+
+        Ok(Timestamp(days * Self::SECONDS_PER_DAY + seconds))
+    }
+}
+
 /// Scalar for boolean.
 pub type Boolean = bool;
 
@@ -65,3 +374,94 @@ impl AsRef<[u8]> for Json {
         self.0.as_bytes()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<i64, String> {
+        s.parse::<Timestamp>().map(|t| t.to_unix())
+    }
+
+    #[test]
+    fn test_timestamps_are_parsed_with_their_offsets() {
+        assert_eq!(parse("2023-11-14T22:13:20Z"), Ok(1_700_000_000));
+        assert_eq!(parse("2023-11-14t22:13:20z"), Ok(1_700_000_000));
+        assert_eq!(parse("2023-11-14 22:13:20Z"), Ok(1_700_000_000));
+        assert_eq!(parse("2023-11-14T23:13:20+01:00"), Ok(1_700_000_000));
+        assert_eq!(parse("2023-11-14T21:43:20-00:30"), Ok(1_700_000_000));
+        assert_eq!(parse("2023-11-15T03:58:20+05:45"), Ok(1_700_000_000));
+        assert_eq!(parse("2023-11-14"), Ok(1_699_920_000));
+    }
+
+    #[test]
+    fn test_fractions_of_a_second_are_truncated() {
+        assert_eq!(parse("2023-11-14T22:13:20.999Z"), Ok(1_700_000_000));
+        assert_eq!(parse("2023-11-14T23:13:20.5+01:00"), Ok(1_700_000_000));
+        assert_eq!(parse("1969-12-31T23:59:59.5Z"), Ok(-1));
+        assert!(parse("2023-11-14T22:13:20.Z").is_err());
+        assert!(parse("2023-11-14T22:13:20,5Z").is_err());
+    }
+
+    #[test]
+    fn test_leap_days() {
+        assert_eq!(parse("2024-02-29"), Ok(1_709_164_800));
+        assert_eq!(parse("2000-02-29T00:00:00Z"), Ok(951_782_400));
+        assert!(parse("2023-02-29").is_err());
+        assert!(parse("1900-02-29").is_err());
+        assert_eq!(
+            Timestamp::from_unix(1_709_164_800).to_string(),
+            "2024-02-29T00:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_timestamps_before_the_unix_epoch() {
+        assert_eq!(parse("1969-12-31T23:59:59Z"), Ok(-1));
+        assert_eq!(parse("1900-01-01T00:00:00Z"), Ok(-2_208_988_800));
+        assert_eq!(parse("1600-03-01T12:00:00Z"), Ok(-11_670_868_800));
+        assert_eq!(Timestamp::from_unix(-1).to_string(), "1969-12-31T23:59:59Z");
+        assert_eq!(
+            Timestamp::from_unix(-11_670_868_800).to_string(),
+            "1600-03-01T12:00:00Z"
+        );
+    }
+
+    #[test]
+    fn test_invalid_timestamps_are_rejected() {
+        for s in [
+            "",
+            "2023-11-1",
+            "2023/11/14",
+            "2023-13-01",
+            "2023-00-10",
+            "2023-04-31",
+            "2023-11-00",
+            "2023-11-14T",
+            "2023-11-14T22:13:20",
+            "2023-11-14T24:00:00Z",
+            "2023-11-14T22:60:00Z",
+            "2023-11-14T22:13:20+0100",
+            "2023-11-14T22:13:20+01:60",
+            "2023-11-14T22:13:20*01:00",
+            "2023-11-14T22:13:2aZ",
+            "+023-11-14T22:13:20Z",
+            "2023-11-14T22:13:20Z ",
+            "２023-11-14",
+        ] {
+            assert!(parse(s).is_err(), "{s:?} should be rejected");
+        }
+    }
+
+    #[test]
+    fn test_timestamps_are_displayed_as_they_are_parsed() {
+        for seconds in [0, 1_700_000_000, -2_208_988_800, 253_402_300_799] {
+            let timestamp = Timestamp::from_unix(seconds);
+            assert_eq!(timestamp.to_string().parse::<Timestamp>(), Ok(timestamp));
+        }
+        assert_eq!(
+            Timestamp::from_unix(253_402_300_799).to_string(),
+            "9999-12-31T23:59:59Z"
+        );
+    }
+}
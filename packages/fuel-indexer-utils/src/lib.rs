@@ -25,3 +25,9 @@ pub mod prelude {
 pub mod plugin {
     pub use fuel_indexer_plugin::*;
 }
+
+/// In-memory store and block builders for unit-testing handlers.
+#[cfg(feature = "testing")]
+pub mod testing {
+    pub use fuel_indexer_plugin::testing::*;
+}
@@ -1,6 +1,6 @@
 /// Utility functions for Fuel indexers.
 use fuel_indexer_plugin::prelude::sha256_digest;
-use fuel_indexer_plugin::types::{Bytes32, UID};
+use fuel_indexer_plugin::types::{BlockData, Bytes32, Timestamp, UID};
 
 /// Returns the unique ID (UID) of an input.
 ///
@@ -17,3 +17,15 @@ pub fn bytes32(data: impl AsRef<[u8]>) -> Bytes32 {
     result[..len].copy_from_slice(&data[..len]); // copy only up to len bytes
     Bytes32::from(result)
 }
+
+/// Converts a block time, such as `Header.time`, into a `Timestamp`.
+///
+/// Block times are TAI64 labels that the indexer converts into seconds since the Unix epoch.
+pub fn timestamp(time: i64) -> Timestamp {
+    Timestamp::from_unix(time)
+}
+
+/// Returns the time at which the given block was produced as a `Timestamp`.
+pub fn block_timestamp(block: &BlockData) -> Timestamp {
+    timestamp(block.header.time)
+}
@@ -0,0 +1,399 @@
+use crate::{executor::retrieve_blocks_from_node, IndexerResult};
+use anyhow::Context;
+use async_std::sync::{Arc, Mutex};
+use fuel_core_client::client::FuelClient;
+use fuel_indexer_types::fuel::BlockData;
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+use tracing::debug;
+
+/// Number of bytes in a megabyte, used to convert `IndexerConfig::block_cache_size`.
+const BYTES_PER_MB: usize = 1024 * 1024;
+
+/// A block held by a `BlockCache`.
+struct CachedBlock {
+    /// The block.
+    block: BlockData,
+
+    /// Approximate size of the block in bytes.
+    size: usize,
+
+    /// Tick of the `CachedBlocks` clock at which the block was last used.
+    last_used: u64,
+}
+
+/// Blocks held by a `BlockCache`, keyed by height.
+#[derive(Default)]
+struct CachedBlocks {
+    /// Cached blocks.
+    blocks: BTreeMap<u32, CachedBlock>,
+
+    /// Heights of the cached blocks, keyed by the tick at which they were last used.
+    usage: BTreeMap<u64, u32>,
+
+    /// Clock that ticks whenever a block is used.
+    clock: u64,
+
+    /// Approximate size of all cached blocks in bytes.
+    size: usize,
+
+    /// Height of the highest block known to exist on the node.
+    highest: Option<u32>,
+}
+
+impl CachedBlocks {
+    /// Return the cached blocks of the page of `page_size` blocks starting at
+    /// `first`, up to the first block that isn't cached, and mark them as used.
+    fn page(
+        &mut self,
+        first: u32,
+        page_size: usize,
+        end_block: Option<u32>,
+    ) -> Vec<BlockData> {
+        let heights = (first..)
+            .take(page_size)
+            .take_while(|height| end_block.map_or(true, |end| *height <= end))
+            .take_while(|height| self.blocks.contains_key(height))
+            .collect::<Vec<_>>();
+
+        heights
+            .into_iter()
+            .map(|height| {
+                self.touch(height);
+                self.blocks[&height].block.clone()
+            })
+            .collect()
+    }
+
+    /// Add a block fetched from the node.
+    fn insert(&mut self, block: &BlockData) {
+        let size = bincode::serialized_size(block).unwrap_or_default() as usize;
+        let cached = CachedBlock {
+            block: block.clone(),
+            size,
+            last_used: 0,
+        };
+
+        if let Some(prev) = self.blocks.insert(block.height, cached) {
+            self.size -= prev.size;
+            self.usage.remove(&prev.last_used);
+        }
+        self.size += size;
+        self.touch(block.height);
+    }
+
+    /// Mark the block at the given height as used.
+    fn touch(&mut self, height: u32) {
+        if let Some(cached) = self.blocks.get_mut(&height) {
+            self.clock += 1;
+            self.usage.remove(&cached.last_used);
+            cached.last_used = self.clock;
+            self.usage.insert(self.clock, height);
+        }
+    }
+
+    /// Evict the least recently used blocks until the cached blocks fit in
+    /// `memory_limit` bytes.
+    fn evict(&mut self, memory_limit: usize) {
+        while self.size > memory_limit {
+            match self.usage.pop_first() {
+                Some((_, height)) => {
+                    if let Some(cached) = self.blocks.remove(&height) {
+                        self.size -= cached.size;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Record the height of the last block of a page fetched from the node, and
+    /// whether the node had more blocks.
+    fn record_highest(&mut self, height: u32, has_next_page: bool) {
+        let highest = if has_next_page { height + 1 } else { height };
+        self.highest = Some(self.highest.map_or(highest, |h| h.max(highest)));
+    }
+
+    /// Whether the node is known to have blocks above the given height.
+    fn has_next_page(&self, height: u32) -> bool {
+        self.highest.map_or(true, |highest| height < highest)
+    }
+}
+
+/// Block cache shared by all executors in an `IndexerService` that subscribe to the
+/// same Fuel node.
+///
+/// Executors request pages of blocks by height. The first executor to request a page
+/// fetches it from the node, and every other executor requesting the same heights is
+/// served from memory. When the cache exceeds its memory budget, the least recently
+/// used blocks are evicted first, so that executors indexing different heights can
+/// share it.
+pub struct BlockCache {
+    /// Client connected to the Fuel node from which blocks are fetched.
+    client: FuelClient,
+
+    /// Address of the Fuel node from which blocks are fetched.
+    fuel_node_addr: String,
+
+    /// Blocks fetched so far.
+    cache: Mutex<CachedBlocks>,
+
+    /// Locks of the pages being fetched from the node, keyed by their first height.
+    ///
+    /// Executors requesting the same page wait for the first request rather than
+    /// fetching it again, without holding up executors requesting other pages.
+    fetching: std::sync::Mutex<HashMap<u32, Arc<Mutex<()>>>>,
+
+    /// Maximum size of the cached blocks in bytes.
+    memory_limit: usize,
+}
+
+impl BlockCache {
+    /// Create a new `BlockCache` with a memory budget of `size_mb` megabytes.
+    pub fn new(fuel_node_addr: &str, size_mb: usize) -> IndexerResult<Self> {
+        let client = FuelClient::from_str(fuel_node_addr)
+            .with_context(|| "Client node connection failed".to_string())?;
+
+        Ok(Self {
+            client,
+            fuel_node_addr: fuel_node_addr.to_string(),
+            cache: Mutex::new(CachedBlocks::default()),
+            fetching: std::sync::Mutex::new(HashMap::new()),
+            memory_limit: size_mb * BYTES_PER_MB,
+        })
+    }
+
+    /// Address of the Fuel node from which blocks are fetched.
+    pub fn fuel_node_addr(&self) -> &str {
+        &self.fuel_node_addr
+    }
+
+    /// Retrieve the next page of blocks after `cursor`, from the cache if possible,
+    /// or else from the Fuel node.
+    ///
+    /// Follows the same contract as `retrieve_blocks_from_node`.
+    pub async fn retrieve_blocks(
+        &self,
+        block_page_size: usize,
+        cursor: &Option<String>,
+        end_block: Option<u32>,
+        indexer_uid: &str,
+    ) -> IndexerResult<(Vec<BlockData>, Option<String>, bool)> {
+        // The node returns blocks starting from genesis when no cursor is given.
+        let first = match cursor {
+            Some(cursor) => cursor.parse::<u32>().ok().map(|height| height + 1),
+            None => Some(0),
+        };
+
+        if let Some(first) = first {
+            if let Some(page) = self
+                .cached_page(first, block_page_size, end_block, indexer_uid)
+                .await
+            {
+                return Ok(page);
+            }
+        }
+
+        let page_lock = first.map(|first| self.page_lock(first));
+        let _fetching = match page_lock.as_ref() {
+            Some(lock) => Some(lock.lock().await),
+            None => None,
+        };
+
+        // The page may have been fetched while waiting for its lock.
+        let cached = match first {
+            Some(first) => {
+                self.cached_page(first, block_page_size, end_block, indexer_uid)
+                    .await
+            }
+            None => None,
+        };
+
+        let result = match cached {
+            Some(page) => Ok(page),
+            None => {
+                self.fetch_blocks(block_page_size, cursor, end_block, indexer_uid)
+                    .await
+            }
+        };
+
+        // Executors still waiting for the lock hold on to it, and then find the page
+        // in the cache.
+        if let Some(first) = first {
+            self.fetching
+                .lock()
+                .expect("Failed to lock block cache fetches.")
+                .remove(&first);
+        }
+
+        result
+    }
+
+    /// Fetch the next page of blocks after `cursor` from the Fuel node, and cache them.
+    async fn fetch_blocks(
+        &self,
+        block_page_size: usize,
+        cursor: &Option<String>,
+        end_block: Option<u32>,
+        indexer_uid: &str,
+    ) -> IndexerResult<(Vec<BlockData>, Option<String>, bool)> {
+        let (blocks, next_cursor, has_next_page) = retrieve_blocks_from_node(
+            &self.client,
+            block_page_size,
+            cursor,
+            end_block,
+            indexer_uid,
+        )
+        .await?;
+
+        let mut cache = self.cache.lock().await;
+        for block in blocks.iter() {
+            cache.insert(block);
+        }
+        if let Some(last) = blocks.last() {
+            cache.record_highest(last.height, has_next_page);
+        }
+        cache.evict(self.memory_limit);
+
+        Ok((blocks, next_cursor, has_next_page))
+    }
+
+    /// Return the page of blocks starting at `first` from the cache, if its first
+    /// block is cached.
+    async fn cached_page(
+        &self,
+        first: u32,
+        block_page_size: usize,
+        end_block: Option<u32>,
+        indexer_uid: &str,
+    ) -> Option<(Vec<BlockData>, Option<String>, bool)> {
+        let mut cache = self.cache.lock().await;
+        let blocks = cache.page(first, block_page_size, end_block);
+        let last = blocks.last()?.height;
+
+        debug!(
+            "Indexer({indexer_uid}) retrieved {} block(s) from the block cache.",
+            blocks.len()
+        );
+
+        Some((blocks, Some(last.to_string()), cache.has_next_page(last)))
+    }
+
+    /// Return the lock of the page of blocks starting at `first`.
+    fn page_lock(&self, first: u32) -> Arc<Mutex<()>> {
+        self.fetching
+            .lock()
+            .expect("Failed to lock block cache fetches.")
+            .entry(first)
+            .or_insert_with(|| Arc::new(Mutex::new(())))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_types::{
+        fuel::{Consensus, Header},
+        scalar::Bytes32,
+    };
+
+    fn block(height: u32) -> BlockData {
+        BlockData {
+            height,
+            id: Bytes32::zeroed(),
+            header: Header {
+                id: Bytes32::zeroed(),
+                da_height: 1,
+                transactions_count: 0,
+                message_receipt_count: 0,
+                transactions_root: Bytes32::zeroed(),
+                message_receipt_root: Bytes32::zeroed(),
+                height,
+                prev_root: Bytes32::zeroed(),
+                time: 0,
+                application_hash: Bytes32::zeroed(),
+            },
+            producer: None,
+            time: 0,
+            consensus: Consensus::Unknown,
+            transactions: vec![],
+        }
+    }
+
+    fn heights(blocks: &[BlockData]) -> Vec<u32> {
+        blocks.iter().map(|block| block.height).collect()
+    }
+
+    #[test]
+    fn test_pages_are_served_up_to_the_first_missing_block() {
+        let mut cache = CachedBlocks::default();
+        for height in [1, 2, 3, 5] {
+            cache.insert(&block(height));
+        }
+
+        assert_eq!(heights(&cache.page(1, 10, None)), vec![1, 2, 3]);
+        assert_eq!(heights(&cache.page(2, 1, None)), vec![2]);
+        assert_eq!(heights(&cache.page(1, 10, Some(2))), vec![1, 2]);
+        assert!(cache.page(4, 10, None).is_empty());
+    }
+
+    #[test]
+    fn test_least_recently_used_blocks_are_evicted() {
+        let mut cache = CachedBlocks::default();
+        for height in 1..=4 {
+            cache.insert(&block(height));
+        }
+        let block_size = cache.size / 4;
+
+        // An executor behind the others keeps using the lowest blocks.
+        cache.page(1, 2, None);
+        cache.evict(block_size * 2);
+
+        assert_eq!(cache.blocks.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(cache.size, block_size * 2);
+        assert_eq!(cache.usage.len(), 2);
+    }
+
+    #[test]
+    fn test_reinserted_blocks_are_not_counted_twice() {
+        let mut cache = CachedBlocks::default();
+        cache.insert(&block(1));
+        let size = cache.size;
+
+        cache.insert(&block(1));
+
+        assert_eq!(cache.size, size);
+        assert_eq!(cache.usage.len(), 1);
+    }
+
+    #[test]
+    fn test_next_page_is_known_from_the_node() {
+        let mut cache = CachedBlocks::default();
+
+        // Nothing has been fetched from the node yet.
+        assert!(cache.has_next_page(5));
+
+        cache.record_highest(5, true);
+        assert!(cache.has_next_page(5));
+        assert!(!cache.has_next_page(6));
+
+        cache.record_highest(8, false);
+        assert!(cache.has_next_page(7));
+        assert!(!cache.has_next_page(8));
+
+        // A lagging executor's page doesn't lower the highest known block.
+        cache.record_highest(3, false);
+        assert!(cache.has_next_page(7));
+    }
+
+    #[test]
+    fn test_requests_for_the_same_page_share_a_lock() {
+        let cache = BlockCache::new("127.0.0.1:4000", 1).unwrap();
+
+        assert!(Arc::ptr_eq(&cache.page_lock(5), &cache.page_lock(5)));
+        assert!(!Arc::ptr_eq(&cache.page_lock(5), &cache.page_lock(6)));
+    }
+}
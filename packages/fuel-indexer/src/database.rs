@@ -1,12 +1,18 @@
 use crate::{IndexerConfig, IndexerError, IndexerResult, Manifest};
 use fuel_indexer_database::{
-    queries, IndexerConnection, IndexerConnectionPool, IndexerDatabaseError,
+    queries, types::IndexerLog, IndexerConnection, IndexerConnectionPool,
+    IndexerDatabaseError,
 };
 use fuel_indexer_lib::{
-    fully_qualified_namespace, graphql::types::IdCol, utils::format_sql_query,
+    fully_qualified_namespace,
+    graphql::{
+        types::{HistoryCol, IdCol, ObjectCol, RetainCol},
+        GraphQLSchema, ParsedGraphQLSchema,
+    },
+    utils::{format_sql_query, EntityChange, EntityChangeKind},
 };
 use fuel_indexer_schema::FtColumn;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, error, info};
 
 /// Database for an executor instance, with schema info.
@@ -35,6 +41,29 @@ pub struct Database {
 
     /// Indexer configuration.
     config: IndexerConfig,
+
+    /// Changes made to entities in the current transaction, if they are recorded.
+    changes: Option<Vec<EntityChange>>,
+
+    /// Tables of the `TypeDefinition`s that keep history.
+    history: HashSet<String>,
+
+    /// Tables of the `TypeDefinition`s whose entities can only be inserted.
+    immutable: HashSet<String>,
+
+    /// Entity and operation of the last write rejected in the current transaction
+    /// because the entity is immutable.
+    rejected: Option<(String, String)>,
+
+    /// Tables of the `TypeDefinition`s with a retention policy, and the number of
+    /// blocks for which their rows are kept.
+    retained: HashMap<String, u32>,
+
+    /// Height of the block currently being indexed.
+    block_height: Option<u32>,
+
+    /// Number of log lines persisted since the logs of the indexer were last pruned.
+    unpruned_log_lines: usize,
 }
 
 /// Check if the upsert query is for an ID column only.
@@ -42,6 +71,21 @@ fn is_id_only_upsert(columns: &[String]) -> bool {
     columns.len() == 2 && columns[0] == IdCol::to_lowercase_string()
 }
 
+/// Postgres error code of a unique constraint violation.
+const UNIQUE_VIOLATION: &str = "23505";
+
+/// Check if the error is a violation of the primary key of the given table.
+fn is_primary_key_violation(e: &sqlx::Error, table: &str) -> bool {
+    match e {
+        sqlx::Error::Database(e) => {
+            let name = table.rsplit('.').next().unwrap_or(table);
+            e.code().as_deref() == Some(UNIQUE_VIOLATION)
+                && e.constraint() == Some(format!("{name}_pkey").as_str())
+        }
+        _ => false,
+    }
+}
+
 impl Database {
     /// Create a new `Database`.
     pub async fn new(
@@ -58,11 +102,34 @@ impl Database {
             schema: Default::default(),
             tables: Default::default(),
             config: config.clone(),
+            changes: None,
+            history: Default::default(),
+            immutable: Default::default(),
+            rejected: None,
+            retained: Default::default(),
+            block_height: None,
+            unpruned_log_lines: 0,
         }
     }
 
+    /// Set the height of the block currently being indexed.
+    ///
+    /// Versions of entities written to history tables are recorded at this height.
+    pub fn set_block_height(&mut self, height: u32) {
+        self.block_height = Some(height);
+    }
+
+    /// Return the height of the block currently being indexed, which is required
+    /// to write to tables that keep history or have a retention policy.
+    fn current_block_height(&self, table: &str) -> IndexerResult<u32> {
+        self.block_height.ok_or_else(|| {
+            anyhow::anyhow!("Block height is not set for writing to {table}.").into()
+        })
+    }
+
     /// Open a database transaction.
     pub async fn start_transaction(&mut self) -> IndexerResult<usize> {
+        self.rejected = None;
         let conn = self.pool.acquire().await?;
         self.stashed = Some(conn);
         debug!("Connection stashed as: {:?}", self.stashed);
@@ -100,7 +167,109 @@ impl Database {
         Ok(res)
     }
 
+    /// Open a database transaction in which changes to entities are recorded.
+    ///
+    /// The check that blocks are indexed consecutively is lifted within this
+    /// transaction, so that any block can be run in it. Such a transaction is only
+    /// meant to be reverted.
+    pub async fn start_debug_transaction(&mut self) -> IndexerResult<usize> {
+        let result = self.start_transaction().await?;
+
+        let conn =
+            self.stashed
+                .as_mut()
+                .ok_or(crate::IndexerError::NoTransactionError(
+                    "start_debug_transaction".to_string(),
+                ))?;
+
+        queries::skip_ensure_block_height_consecutive_check(conn).await?;
+
+        self.changes = Some(Vec::new());
+
+        Ok(result)
+    }
+
+    /// Take the changes to entities recorded in the current transaction, and stop
+    /// recording them.
+    pub fn take_changes(&mut self) -> Vec<EntityChange> {
+        self.changes.take().unwrap_or_default()
+    }
+
+    /// Return the columns of the rows of `table` that satisfy the given
+    /// constraints, without their serialized objects.
+    async fn entity_rows(
+        &mut self,
+        table: &str,
+        constraints: &str,
+    ) -> IndexerResult<Vec<serde_json::Value>> {
+        let query = format!(
+            "SELECT to_jsonb(t) - '{}' FROM {table} t WHERE {constraints}",
+            ObjectCol::to_lowercase_str()
+        );
+
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError("entity_rows".to_string()))?;
+
+        match queries::run_query(conn, query).await? {
+            serde_json::Value::Array(rows) => Ok(rows),
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Record a change to an entity, if changes are being recorded.
+    fn record_change(
+        &mut self,
+        table: &str,
+        kind: EntityChangeKind,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) {
+        if let Some(changes) = self.changes.as_mut() {
+            let id = before
+                .iter()
+                .chain(after.iter())
+                .find_map(|row| row.get(IdCol::to_lowercase_str()))
+                .map(|id| {
+                    id.as_str()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| id.to_string())
+                })
+                .unwrap_or_default();
+
+            changes.push(EntityChange {
+                entity: table.rsplit('.').next().unwrap_or(table).to_string(),
+                id,
+                kind,
+                before,
+                after,
+            });
+        }
+    }
+
+    /// Reject a write to an immutable entity, and remember it for the executor to
+    /// report once the handlers have failed.
+    fn reject_immutable(&mut self, entity: String, operation: &str) -> IndexerError {
+        self.rejected = Some((entity.clone(), operation.to_string()));
+        IndexerError::ImmutableEntityError(entity, operation.to_string())
+    }
+
+    /// Take the error of the last write rejected in the current transaction because
+    /// the entity is immutable, if any.
+    ///
+    /// Handlers only see that a write failed, so this is how the executor learns
+    /// why they did.
+    pub fn take_immutable_entity_error(&mut self) -> Option<IndexerError> {
+        self.rejected.take().map(|(entity, operation)| {
+            IndexerError::ImmutableEntityError(entity, operation)
+        })
+    }
+
     /// Build an upsert query using a set of columns, insert values, update values, and a table name.
+    ///
+    /// Entities of immutable tables are only ever inserted, with a plain insert that
+    /// fails if the entity already exists.
     fn upsert_query(
         &self,
         table: &str,
@@ -108,7 +277,14 @@ impl Database {
         inserts: Vec<String>,
         updates: Vec<String>,
     ) -> String {
-        if is_id_only_upsert(columns) {
+        if self.immutable.contains(table) {
+            format!(
+                "INSERT INTO {} ({}) VALUES ({}, $1::bytea)",
+                table,
+                columns.join(", "),
+                inserts.join(", "),
+            )
+        } else if is_id_only_upsert(columns) {
             format!(
                 "INSERT INTO {} ({}) VALUES ({}, $1::bytea) ON CONFLICT(id) DO NOTHING",
                 table,
@@ -143,7 +319,7 @@ impl Database {
         bytes: Vec<u8>,
     ) -> IndexerResult<()> {
         let table = match self.tables.get(&type_id) {
-            Some(t) => t,
+            Some(t) => t.clone(),
             None => {
                 return Err(anyhow::anyhow!(
                     r#"TypeId({type_id}) not found in tables: {:?}. 
@@ -158,28 +334,110 @@ Do your WASM modules need to be rebuilt?
             }
         };
 
-        let inserts: Vec<_> = columns.iter().map(|col| col.query_fragment()).collect();
-        let updates: Vec<_> = self.schema[table]
+        let mut inserts: Vec<_> =
+            columns.iter().map(|col| col.query_fragment()).collect();
+        let mut updates: Vec<_> = self.schema[&table]
             .iter()
             .zip(columns.iter())
             .map(|(colname, value)| format!("{colname} = {}", value.query_fragment()))
             .collect();
 
-        let columns = self.schema[table].clone();
+        let id_constraint = format!("{} = {}", IdCol::to_lowercase_str(), inserts[0]);
+        let before = if self.changes.is_some() {
+            self.entity_rows(&table, &id_constraint).await?.pop()
+        } else {
+            None
+        };
 
-        let query_text =
-            format_sql_query(self.upsert_query(table, &columns, inserts, updates));
+        let columns = self.schema[&table].clone();
+
+        // Rows of tables with a retention policy record the height at which they
+        // were written, so that they can be pruned.
+        let mut upsert_columns = columns.clone();
+        if self.retained.contains_key(&table) {
+            let height = self.current_block_height(&table)?;
+            let column = RetainCol::block_height();
+
+            // The serialized object is always bound as the last column.
+            upsert_columns.insert(upsert_columns.len() - 1, column.to_string());
+            inserts.push(height.to_string());
+            updates.push(format!("{column} = {height}"));
+        }
+
+        let query_text = format_sql_query(self.upsert_query(
+            &table,
+            &upsert_columns,
+            inserts,
+            updates,
+        ));
+
+        let history = if self.history.contains(&table) {
+            let height = self.current_block_height(&table)?;
+            let history_table = HistoryCol::table_name(&table);
+            let start = HistoryCol::block_height_start();
+            let end = HistoryCol::block_height_end();
+            let cols = columns.join(", ");
+
+            // A version written earlier in the same block is replaced, and the
+            // current version (if any) is closed at this height.
+            let before = vec![
+                format!("DELETE FROM {history_table} WHERE {id_constraint} AND {start} = {height}"),
+                format!("UPDATE {history_table} SET {end} = {height} WHERE {id_constraint} AND {end} IS NULL"),
+            ];
+            let after = format!(
+                "INSERT INTO {history_table} ({cols}, {start}) SELECT {cols}, {height} FROM {table} WHERE {id_constraint}"
+            );
+
+            Some((before, after))
+        } else {
+            None
+        };
 
         let conn = self
             .stashed
             .as_mut()
             .ok_or(IndexerError::NoTransactionError("put_object".to_string()))?;
 
+        if let Some((before, _)) = &history {
+            for query in before {
+                if self.config.verbose {
+                    info!("{query}");
+                }
+                queries::execute_query(conn, query.clone()).await?;
+            }
+        }
+
         if self.config.verbose {
             info!("{query_text}");
         }
 
-        queries::put_object(conn, query_text, bytes).await?;
+        if let Err(e) = queries::put_object(conn, query_text, bytes).await {
+            if self.immutable.contains(&table) && is_primary_key_violation(&e, &table) {
+                return Err(self.reject_immutable(
+                    format!("{table} with {id_constraint}"),
+                    "updated",
+                ));
+            }
+
+            return Err(e.into());
+        }
+
+        if let Some((_, after)) = history {
+            if self.config.verbose {
+                info!("{after}");
+            }
+            queries::execute_query(conn, after).await?;
+        }
+
+        if self.changes.is_some() {
+            let after = self.entity_rows(&table, &id_constraint).await?.pop();
+            let kind = if before.is_some() {
+                EntityChangeKind::Update
+            } else {
+                EntityChangeKind::Insert
+            };
+            self.record_change(&table, kind, before, after);
+        }
 
         Ok(())
     }
@@ -248,10 +506,38 @@ Do your WASM modules need to be rebuilt?
         type_id: i64,
         constraints: String,
     ) -> IndexerResult<usize> {
-        let table = &self
+        let table = self
             .tables
             .get(&type_id)
-            .ok_or(IndexerDatabaseError::TableMappingDoesNotExist(type_id))?;
+            .ok_or(IndexerDatabaseError::TableMappingDoesNotExist(type_id))?
+            .clone();
+
+        if self.immutable.contains(&table) {
+            return Err(self.reject_immutable(table, "deleted"));
+        }
+
+        let deleted = if self.changes.is_some() {
+            self.entity_rows(&table, &constraints).await?
+        } else {
+            vec![]
+        };
+
+        let history = if self.history.contains(&table) {
+            let height = self.current_block_height(&table)?;
+            let history_table = HistoryCol::table_name(&table);
+            let start = HistoryCol::block_height_start();
+            let end = HistoryCol::block_height_end();
+            let id = IdCol::to_lowercase_str();
+
+            // Close the current versions of the deleted entities, and drop the
+            // versions that were both written and deleted in this block.
+            vec![
+                format!("UPDATE {history_table} SET {end} = {height} WHERE {end} IS NULL AND {id} IN (SELECT {id} FROM {table} WHERE {constraints})"),
+                format!("DELETE FROM {history_table} WHERE {start} = {height} AND {end} = {height}"),
+            ]
+        } else {
+            vec![]
+        };
 
         let query = format!("DELETE from {table} WHERE {constraints}");
 
@@ -262,8 +548,19 @@ Do your WASM modules need to be rebuilt?
             .as_mut()
             .ok_or(IndexerError::NoTransactionError("find_many".to_string()))?;
 
+        for query in history {
+            if self.config.verbose {
+                info!("{query}");
+            }
+            queries::execute_query(conn, query).await?;
+        }
+
         let count = queries::execute_query(conn, query).await?;
 
+        for row in deleted {
+            self.record_change(&table, EntityChangeKind::Delete, Some(row), None);
+        }
+
         Ok(count)
     }
 
@@ -301,9 +598,79 @@ Do your WASM modules need to be rebuilt?
             columns.push(column.column_name);
         }
 
+        let root =
+            queries::graph_root_latest(&mut conn, &self.namespace, &self.identifier)
+                .await?;
+        let parsed = ParsedGraphQLSchema::new(
+            &self.namespace,
+            &self.identifier,
+            Some(&GraphQLSchema::new(root.schema)),
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to parse GraphQL schema: {e}"))?;
+
+        for (name, _) in parsed.storage_backed_typedefs() {
+            let table = format!(
+                "{}.{}",
+                fully_qualified_namespace(&self.namespace, &self.identifier),
+                name.to_lowercase()
+            );
+
+            if parsed.is_history_typedef(name) {
+                self.history.insert(table.clone());
+            }
+
+            if parsed.is_immutable_typedef(name) {
+                self.immutable.insert(table.clone());
+            }
+
+            if let Some(blocks) = parsed.retained_blocks(name) {
+                self.retained.insert(table, blocks);
+            }
+        }
+
         Ok(())
     }
 
+    /// Delete the rows of tables with a retention policy that were last written
+    /// more than their number of retained blocks before the given height, and
+    /// return the number of deleted rows.
+    ///
+    /// Rows are deleted outside of any transaction, and each table is pruned on
+    /// its own, so that a table whose rows are still referenced by other tables
+    /// doesn't prevent the others from being pruned.
+    pub async fn prune(&self, height: u32) -> IndexerResult<u64> {
+        let mut conn = self.pool.acquire().await?;
+        let mut count = 0;
+
+        for (table, blocks) in self.retained.iter() {
+            let horizon = match height.checked_sub(*blocks) {
+                Some(horizon) if horizon > 0 => horizon,
+                _ => continue,
+            };
+
+            let query = format!(
+                "DELETE FROM {table} WHERE {} < {horizon}",
+                RetainCol::block_height()
+            );
+
+            if self.config.verbose {
+                info!("{query}");
+            }
+
+            match queries::execute_query(&mut conn, query).await {
+                Ok(deleted) => count += deleted as u64,
+                Err(e) => error!("Failed to prune {table}: {e:?}"),
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Whether any table of this indexer has a retention policy.
+    pub fn has_retention_policy(&self) -> bool {
+        !self.retained.is_empty()
+    }
+
     pub fn namespace(&self) -> &str {
         &self.namespace
     }
@@ -347,4 +714,73 @@ Do your WASM modules need to be rebuilt?
 
         Ok(())
     }
+
+    /// Persist a contract ID that the indexer has started watching at runtime.
+    ///
+    /// The contract ID is written within the current transaction, so it is only
+    /// watched once the block that added it has been committed.
+    pub async fn watch_contract(&mut self, contract_id: String) -> IndexerResult<()> {
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError(
+                "watch_contract".to_string(),
+            ))?;
+
+        queries::put_watched_contract(
+            conn,
+            &self.namespace,
+            &self.identifier,
+            &contract_id,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Return the contract IDs that the indexer has started watching at runtime.
+    pub async fn watched_contracts(&mut self) -> IndexerResult<Vec<String>> {
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError(
+                "watched_contracts".to_string(),
+            ))?;
+
+        let contract_ids =
+            queries::watched_contracts(conn, &self.namespace, &self.identifier).await?;
+
+        Ok(contract_ids)
+    }
+
+    /// Persist lines logged by the handlers of the indexer.
+    ///
+    /// The lines are persisted outside of the current transaction, so that the
+    /// logs of a failed batch of blocks are kept. Older lines are pruned once
+    /// `IndexerConfig::indexer_log_lines` lines have been persisted since the last
+    /// pruning, so at most twice that many lines are kept for the indexer.
+    pub async fn put_logs(&mut self, logs: Vec<IndexerLog>) -> IndexerResult<()> {
+        if logs.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.pool.acquire().await?;
+        queries::put_indexer_logs(&mut conn, &self.namespace, &self.identifier, &logs)
+            .await?;
+
+        self.unpruned_log_lines += logs.len();
+        let max_lines = self.config.indexer_log_lines;
+        if self.unpruned_log_lines >= max_lines {
+            queries::prune_indexer_logs(
+                &mut conn,
+                &self.namespace,
+                &self.identifier,
+                max_lines,
+            )
+            .await?;
+            self.unpruned_log_lines = 0;
+        }
+
+        Ok(())
+    }
 }
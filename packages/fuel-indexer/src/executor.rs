@@ -1,7 +1,12 @@
 /// Abstractions for indexer task execution.
 use crate::{
-    database::Database, ffi, queries::ClientExt, IndexerConfig, IndexerError,
-    IndexerResult,
+    cache::BlockCache,
+    database::Database,
+    ffi,
+    logs::IndexerLogs,
+    native::NativeEnv,
+    queries::{ClientExt, FullBlock},
+    IndexerConfig, IndexerError, IndexerResult,
 };
 use anyhow::Context;
 use async_std::sync::{Arc, Mutex};
@@ -14,24 +19,30 @@ use fuel_core_client::client::{
 };
 use fuel_indexer_database::{queries, types::IndexerStatus, IndexerConnectionPool};
 use fuel_indexer_lib::{
-    defaults::*, manifest::Manifest, utils::serialize, WasmIndexerError,
+    defaults::*,
+    manifest::Manifest,
+    utils::{serialize, DebugReport},
+    WasmIndexerError,
 };
 #[cfg(feature = "metrics")]
 use fuel_indexer_metrics::METRICS;
 use fuel_indexer_types::{
     fuel::{field::*, *},
-    scalar::{Bytes, Bytes32},
+    scalar::{Bytes, Bytes32, ContractId},
+    Bech32ContractId,
 };
-use fuel_tx::UniqueIdentifier;
+use fuel_tx::{ConsensusParameters, UniqueIdentifier};
 use fuel_vm::fuel_types::canonical::Deserialize;
 use fuel_vm::state::ProgramState as ClientProgramState;
 use futures::Future;
 use itertools::Itertools;
 use std::{
+    collections::{BTreeSet, HashSet},
     marker::{Send, Sync},
+    panic::{catch_unwind, AssertUnwindSafe},
     path::Path,
     str::FromStr,
-    sync::atomic::{AtomicBool, Ordering},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
 };
 use tokio::{
     task::spawn_blocking,
@@ -84,6 +95,7 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
     config: &IndexerConfig,
     pool: IndexerConnectionPool,
     mut executor: T,
+    block_cache: Option<Arc<BlockCache>>,
 ) -> anyhow::Result<impl Future<Output = IndexerResult<()>>> {
     // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
 
@@ -113,13 +125,37 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
     let client = FuelClient::from_str(&fuel_node_addr)
         .with_context(|| "Client node connection failed".to_string())?;
 
+    // The shared block cache can only be used if this indexer subscribes to the same
+    // Fuel node as the service.
+    let block_cache =
+        block_cache.filter(|cache| cache.fuel_node_addr() == fuel_node_addr);
+
     if let Some(end_block) = end_block {
         info!("Indexer({indexer_uid}) will stop at block #{end_block}.");
     } else {
         warn!("No end_block specified in the manifest. Indexer({indexer_uid}) will run forever.");
     }
 
-    let allow_non_sequential_blocks = config.allow_non_sequential_blocks;
+    // In sparse mode, only blocks that include transactions involving the manifest's
+    // contracts are fetched, so block heights are expected to have gaps.
+    let mut sparse_fetcher = if executor.manifest().sparse().unwrap_or(false) {
+        let contract_ids = executor.manifest().subscribed_contract_ids();
+        if contract_ids.is_empty() {
+            warn!("Indexer({indexer_uid}) is sparse, but no contract_id is specified. Indexer({indexer_uid}) will fetch every block.");
+            None
+        } else {
+            info!(
+                "Indexer({indexer_uid}) will only fetch blocks involving {} contract(s).",
+                contract_ids.len()
+            );
+            Some(SparseBlockFetcher::new(&contract_ids)?)
+        }
+    } else {
+        None
+    };
+
+    let allow_non_sequential_blocks =
+        config.allow_non_sequential_blocks || sparse_fetcher.is_some();
     let client_request_delay = config.client_request_delay;
 
     let task = async move {
@@ -150,6 +186,18 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
             })?;
         }
 
+        // Contracts watched before the indexer was restarted are used to discover
+        // blocks too.
+        if let Some(fetcher) = sparse_fetcher.as_mut() {
+            let contract_ids = queries::watched_contracts(
+                &mut conn,
+                executor.manifest().namespace(),
+                executor.manifest().identifier(),
+            )
+            .await?;
+            fetcher.watch(&contract_ids, 0)?;
+        }
+
         // If we reach an issue that continues to fail, we'll retry a few times before giving up, as
         // we don't want to quit on the first error. But also don't want to waste CPU.
         //
@@ -171,6 +219,9 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
         // Keep track of how many empty pages we've received from the client.
         let mut num_empty_block_reqs = 0;
 
+        // Height at which the tables with a retention policy were last pruned.
+        let mut last_pruned_height: Option<u32> = None;
+
         loop {
             // If something else has signaled that this indexer should stop, then stop.
             if executor.kill_switch().load(Ordering::SeqCst) {
@@ -181,33 +232,58 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
             let start = Instant::now();
 
             // Fetch the next page of blocks, and the starting cursor for the subsequent page
-            let (block_info, next_cursor, _has_next_page) =
-                match retrieve_blocks_from_node(
-                    &client,
-                    block_page_size,
-                    &cursor,
-                    end_block,
-                    &indexer_uid,
-                )
-                .await
-                {
-                    Ok((block_info, next_cursor, has_next_page)) => {
-                        (block_info, next_cursor, has_next_page)
+            let fetched = match sparse_fetcher.as_mut() {
+                Some(fetcher) => {
+                    fetcher
+                        .retrieve_blocks(
+                            &client,
+                            block_page_size,
+                            &cursor,
+                            end_block,
+                            &indexer_uid,
+                        )
+                        .await
+                }
+                None => match block_cache.as_ref() {
+                    Some(cache) => {
+                        cache
+                            .retrieve_blocks(
+                                block_page_size,
+                                &cursor,
+                                end_block,
+                                &indexer_uid,
+                            )
+                            .await
                     }
-                    Err(e) => {
-                        if let IndexerError::EndBlockMet = e {
-                            info!("Indexer({indexer_uid}) has met its end block; beginning indexer shutdown process.");
-                            executor.kill_switch().store(true, Ordering::SeqCst);
-                            continue;
-                        } else {
-                            error!(
-                                "Indexer({indexer_uid}) failed to fetch blocks: {e:?}",
-                            );
-                            sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERROR)).await;
-                            continue;
-                        }
+                    None => {
+                        retrieve_blocks_from_node(
+                            &client,
+                            block_page_size,
+                            &cursor,
+                            end_block,
+                            &indexer_uid,
+                        )
+                        .await
                     }
-                };
+                },
+            };
+
+            let (block_info, next_cursor, _has_next_page) = match fetched {
+                Ok((block_info, next_cursor, has_next_page)) => {
+                    (block_info, next_cursor, has_next_page)
+                }
+                Err(e) => {
+                    if let IndexerError::EndBlockMet = e {
+                        info!("Indexer({indexer_uid}) has met its end block; beginning indexer shutdown process.");
+                        executor.kill_switch().store(true, Ordering::SeqCst);
+                        continue;
+                    } else {
+                        error!("Indexer({indexer_uid}) failed to fetch blocks: {e:?}");
+                        sleep(Duration::from_secs(DELAY_FOR_SERVICE_ERROR)).await;
+                        continue;
+                    }
+                }
+            };
 
             #[cfg(feature = "metrics")]
             {
@@ -252,14 +328,22 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                         }
                         Some(&WasmIndexerError::Panic)
                         | Some(&WasmIndexerError::GeneralError) => {
-                            let message = executor.get_error_message().await.unwrap_or(
-                                "unable to extract the error message".to_string(),
-                            );
-                            return Err(anyhow::anyhow!("{message}").into());
+                            let failure = executor.get_handler_failure().await?;
+                            return Err(IndexerError::HandlerFailure(failure));
                         }
                         _ => (),
                     }
                 }
+                // Errors returned by native handlers, and their panics, are not retried,
+                // just like those of WASM handlers.
+                if let IndexerError::NativeExecutionRuntimeError = e {
+                    let failure = executor.get_handler_failure().await?;
+                    return Err(IndexerError::HandlerFailure(failure));
+                }
+                // Writes to immutable entities would be rejected on every retry.
+                if let IndexerError::ImmutableEntityError(..) = e {
+                    return Err(e);
+                }
                 // Run time metering is deterministic. There is no point in retrying.
                 if let IndexerError::RunTimeLimitExceededError = e {
                     return Err(anyhow::format_err!(
@@ -311,6 +395,62 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
             )
             .await?;
 
+            // Contracts watched by the handlers are only persisted once their block
+            // has been committed, after which their later blocks can be discovered.
+            if let Some(fetcher) = sparse_fetcher.as_mut() {
+                let contract_ids = queries::watched_contracts(
+                    &mut conn,
+                    executor.manifest().namespace(),
+                    executor.manifest().identifier(),
+                )
+                .await?;
+                let height = cursor
+                    .as_ref()
+                    .and_then(|c| c.parse::<u32>().ok())
+                    .unwrap_or(0);
+                fetcher.watch(&contract_ids, height)?;
+            }
+
+            // Periodically prune the tables with a retention policy.
+            if let Some(height) = cursor.as_ref().and_then(|c| c.parse::<u32>().ok()) {
+                if last_pruned_height.map_or(true, |h| {
+                    height >= h.saturating_add(RETENTION_PRUNE_INTERVAL)
+                }) {
+                    last_pruned_height = Some(height);
+
+                    #[cfg(feature = "metrics")]
+                    let start = Instant::now();
+
+                    match executor.prune(height).await {
+                        Ok(Some(rows)) => {
+                            #[cfg(feature = "metrics")]
+                            {
+                                METRICS.exec.prune.record(
+                                    &indexer_uid,
+                                    start.elapsed().as_millis() as f64,
+                                );
+                                METRICS.exec.pruned_rows.record(&indexer_uid, rows);
+                            }
+
+                            info!("Indexer({indexer_uid}) pruned {rows} rows at block #{height}.");
+
+                            queries::set_indexer_pruned(
+                                &mut conn,
+                                executor.manifest().namespace(),
+                                executor.manifest().identifier(),
+                                height,
+                                rows,
+                            )
+                            .await?;
+                        }
+                        Ok(None) => {}
+                        Err(e) => {
+                            warn!("Indexer({indexer_uid}) failed to prune its tables: {e:?}")
+                        }
+                    }
+                }
+            }
+
             // Again, check if something else has signaled that this indexer should stop, then stop.
             if executor.kill_switch().load(Ordering::SeqCst) {
                 return Err(IndexerError::KillSwitch);
@@ -328,6 +468,25 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
     Ok(task)
 }
 
+/// Retrieve the block at the given height from the Fuel node at the given address.
+pub async fn retrieve_block_from_node(
+    fuel_node_addr: &str,
+    height: u32,
+    indexer_uid: &str,
+) -> IndexerResult<BlockData> {
+    let client = FuelClient::from_str(fuel_node_addr)
+        .with_context(|| "Client node connection failed".to_string())?;
+
+    let cursor = height.checked_sub(1).map(|h| h.to_string());
+    let (blocks, _, _) =
+        retrieve_blocks_from_node(&client, 1, &cursor, None, indexer_uid).await?;
+
+    blocks
+        .into_iter()
+        .find(|block| block.height == height)
+        .ok_or_else(|| anyhow::anyhow!("Block #{height} could not be retrieved.").into())
+}
+
 /// Retrieve blocks from a client node.
 ///
 /// This was abstracted out of `run_executor` in order to allow for use in the benchmarking suite
@@ -382,206 +541,578 @@ pub async fn retrieve_blocks_from_node(
             }
         });
 
-    let chain_id = client.chain_info().await?.consensus_parameters.chain_id;
-
-    let mut block_info = Vec::new();
-    for block in results.into_iter() {
-        let producer = block.block_producer().map(|pk| pk.hash());
-
-        let mut transactions = Vec::new();
-
-        for trans in block.transactions {
-            let receipts = trans
-                .receipts
-                .unwrap_or_default()
-                .into_iter()
-                .map(TryInto::try_into)
-                .try_collect()
-                .expect("Bad receipts.");
-
-            let status = trans.status.expect("Bad transaction status.");
-            // NOTE: https://github.com/FuelLabs/fuel-indexer/issues/286
-            let status = match status.try_into().unwrap() {
-                ClientTransactionStatus::Success {
-                    block_id,
-                    time,
-                    program_state,
-                    ..
-                } => {
-                    let program_state = program_state.map(|p| match p {
-                        ClientProgramState::Return(w) => ProgramState {
-                            return_type: ReturnType::Return,
-                            data: Bytes::from(w.to_le_bytes().to_vec()),
-                        },
-                        ClientProgramState::ReturnData(d) => ProgramState {
-                            return_type: ReturnType::ReturnData,
-                            data: Bytes::from(d.to_vec()),
-                        },
-                        ClientProgramState::Revert(w) => ProgramState {
-                            return_type: ReturnType::Revert,
-                            data: Bytes::from(w.to_le_bytes().to_vec()),
-                        },
-                        // Either `cargo watch` complains that this is unreachable, or `clippy` complains
-                        // that all patterns are not matched. These other program states are only used in
-                        // debug modes.
-                        #[allow(unreachable_patterns)]
-                        _ => unreachable!("Bad program state."),
-                    });
-                    TransactionStatus::Success {
-                        block: block_id.parse().expect("Bad block height."),
-                        time: time.to_unix() as u64,
-                        program_state,
-                    }
+    let params = client.chain_info().await?.consensus_parameters;
+
+    let block_info = results
+        .into_iter()
+        .map(|block| full_block_to_block_data(block, &params))
+        .collect();
+
+    Ok((block_info, cursor, has_next_page))
+}
+
+/// Number of transactions scanned per request by `SparseBlockFetcher`.
+const SPARSE_TRANSACTIONS_PAGE_SIZE: usize = 100;
+
+/// Fetches only the blocks that include transactions involving a set of contracts.
+///
+/// Relevant block heights are discovered by scanning the transactions of the node,
+/// without their payloads, for those with one of the contracts among their inputs.
+/// Transactions are scanned in the order of their blocks, so every relevant block up
+/// to the last scanned transaction is known, and blocks are processed in increasing
+/// order.
+///
+/// The scan starts after the last indexed block rather than from the first
+/// transaction, so restarting an indexer doesn't rescan the blocks it has already
+/// indexed. The node doesn't index transactions by their contract inputs, so every
+/// transaction after that block is still scanned.
+#[derive(Debug)]
+pub struct SparseBlockFetcher {
+    /// Contracts to which the indexer is subscribed.
+    contract_ids: HashSet<ContractId>,
+
+    /// Cursor of the last page of transactions scanned.
+    cursor: Option<String>,
+
+    /// Height of the block of the last transaction scanned.
+    scanned_height: u32,
+
+    /// Whether the node has more transactions than have been scanned.
+    has_next_page: bool,
+
+    /// Discovered block heights that have not yet been indexed.
+    pending: BTreeSet<u32>,
+
+    /// Height of the block after which the scan must resume, before the next
+    /// page of transactions is scanned.
+    resume_after: Option<u32>,
+}
+
+impl SparseBlockFetcher {
+    /// Create a new `SparseBlockFetcher` from a list of hex or `Bech32` contract IDs.
+    pub fn new(contract_ids: &[String]) -> IndexerResult<Self> {
+        let contract_ids = contract_ids
+            .iter()
+            .map(|id| parse_contract_id(id))
+            .collect::<IndexerResult<HashSet<_>>>()?;
+
+        Ok(Self {
+            contract_ids,
+            cursor: None,
+            scanned_height: 0,
+            has_next_page: true,
+            pending: BTreeSet::new(),
+            resume_after: Some(0),
+        })
+    }
+
+    /// Add the contracts that the indexer has started watching at runtime, as of the
+    /// block at `from_height`.
+    ///
+    /// The blocks after `from_height` may already have been scanned without the
+    /// contracts that weren't watched yet, so the scan resumes after that block
+    /// before the next page of blocks is retrieved.
+    pub fn watch(
+        &mut self,
+        contract_ids: &[String],
+        from_height: u32,
+    ) -> IndexerResult<()> {
+        let mut watched = false;
+        for id in contract_ids {
+            watched |= self.contract_ids.insert(parse_contract_id(id)?);
+        }
+
+        if watched {
+            self.resume_after = Some(
+                self.resume_after
+                    .map_or(from_height, |height| std::cmp::min(height, from_height)),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve the next page of relevant blocks after `cursor`.
+    ///
+    /// Follows the same contract as `retrieve_blocks_from_node`, where `cursor` is the
+    /// height of the last indexed block.
+    pub async fn retrieve_blocks(
+        &mut self,
+        client: &FuelClient,
+        block_page_size: usize,
+        cursor: &Option<String>,
+        end_block: Option<u32>,
+        indexer_uid: &str,
+    ) -> IndexerResult<(Vec<BlockData>, Option<String>, bool)> {
+        let last_height = cursor
+            .as_ref()
+            .and_then(|c| c.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        if let Some(end) = end_block {
+            if last_height >= end {
+                return Err(IndexerError::EndBlockMet);
+            }
+        }
+
+        // Everything at or below the cursor has already been indexed. Since
+        // transactions are scanned in the order of their blocks, no height at or
+        // below the cursor is discovered after the blocks above it were handed out.
+        self.pending = self.pending.split_off(&(last_height + 1));
+
+        if let Some(height) = self.resume_after {
+            let height = std::cmp::max(height, last_height);
+            if !self.resume_scan_after(client, height, indexer_uid).await {
+                return Ok((vec![], cursor.clone(), true));
+            }
+            self.resume_after = None;
+        }
+
+        // Keep scanning until a page of blocks is found, or the scan has caught up
+        // with the node or gone past the end block, so that stretches of blocks
+        // without relevant transactions aren't mistaken for an idle node.
+        while self.ready_heights(end_block).len() < block_page_size
+            && self.has_next_page
+            && end_block.map_or(true, |end| self.scanned_height < end)
+        {
+            if !self
+                .discover_heights(client, last_height, indexer_uid)
+                .await
+            {
+                break;
+            }
+        }
+
+        let heights = self
+            .ready_heights(end_block)
+            .into_iter()
+            .take(block_page_size)
+            .collect::<Vec<_>>();
+
+        if heights.is_empty() {
+            return Ok((vec![], cursor.clone(), self.has_next_page()));
+        }
+
+        let params = client.chain_info().await?.consensus_parameters;
+
+        let mut block_info = Vec::with_capacity(heights.len());
+        for height in heights {
+            debug!("Indexer({indexer_uid}) fetching sparse block #{height}");
+            match client.full_block_by_height(height).await {
+                Ok(Some(block)) => {
+                    block_info.push(full_block_to_block_data(block, &params))
                 }
-                ClientTransactionStatus::Failure {
-                    block_id,
-                    time,
-                    reason,
-                    program_state,
-                    ..
-                } => {
-                    let program_state = program_state.map(|p| match p {
-                        ClientProgramState::Return(w) => ProgramState {
-                            return_type: ReturnType::Return,
-                            data: Bytes::from(w.to_le_bytes().to_vec()),
-                        },
-                        ClientProgramState::ReturnData(d) => ProgramState {
-                            return_type: ReturnType::ReturnData,
-                            data: Bytes::from(d.to_vec()),
-                        },
-                        ClientProgramState::Revert(w) => ProgramState {
-                            return_type: ReturnType::Revert,
-                            data: Bytes::from(w.to_le_bytes().to_vec()),
-                        },
-                        // Either `cargo watch` complains that this is unreachable, or `clippy` complains
-                        // that all patterns are not matched. These other program states are only used in
-                        // debug modes.
-                        #[allow(unreachable_patterns)]
-                        _ => unreachable!("Bad program state."),
-                    });
-                    TransactionStatus::Failure {
-                        block: block_id.parse().expect("Bad block ID."),
-                        time: time.to_unix() as u64,
-                        program_state,
-                        reason,
-                    }
+                Ok(None) => {
+                    warn!("Indexer({indexer_uid}) could not find block #{height}.");
+                    break;
                 }
-                ClientTransactionStatus::Submitted { submitted_at } => {
-                    TransactionStatus::Submitted {
-                        submitted_at: submitted_at.to_unix() as u64,
-                    }
+                Err(e) => {
+                    error!("Indexer({indexer_uid}) failed to retrieve block #{height}: {e:?}");
+                    break;
                 }
-                ClientTransactionStatus::SqueezedOut { reason } => {
-                    TransactionStatus::SqueezedOut { reason }
+            }
+        }
+
+        let next_cursor = block_info
+            .last()
+            .map(|block| block.height.to_string())
+            .or(cursor.clone());
+
+        Ok((block_info, next_cursor, self.has_next_page()))
+    }
+
+    /// Return the pending heights that are safe to index, up to the end block.
+    ///
+    /// Every relevant block up to the last scanned transaction is known, so any
+    /// discovered height is safe to index.
+    fn ready_heights(&self, end_block: Option<u32>) -> Vec<u32> {
+        let bound = end_block.unwrap_or(u32::MAX);
+        self.pending.range(..=bound).copied().collect()
+    }
+
+    /// Move the scan to the first transaction after the block at `height`. Returns
+    /// whether the block could be retrieved.
+    async fn resume_scan_after(
+        &mut self,
+        client: &FuelClient,
+        height: u32,
+        indexer_uid: &str,
+    ) -> bool {
+        let cursor = if height == 0 {
+            None
+        } else {
+            match client.block_by_height(height).await {
+                // The transactions are paginated by block height and transaction ID,
+                // so the block's last transaction marks the end of the block.
+                Ok(Some(block)) => block
+                    .transactions
+                    .last()
+                    .map(|tx_id| transactions_cursor(height, tx_id)),
+                Ok(None) => {
+                    warn!("Indexer({indexer_uid}) could not find block #{height}.");
+                    return false;
                 }
-            };
+                Err(e) => {
+                    error!("Indexer({indexer_uid}) failed to retrieve block #{height}: {e:?}");
+                    return false;
+                }
+            }
+        };
 
-            let transaction: fuel_tx::Transaction =
-                fuel_tx::Transaction::from_bytes(trans.raw_payload.0 .0.as_slice())
-                    .expect("Bad transaction.");
-
-            let id = transaction.id(&chain_id);
-
-            let transaction = match transaction {
-                ClientTransaction::Create(tx) => Transaction::Create(Create {
-                    gas_price: tx.gas_price(),
-                    maturity: tx.maturity(),
-                    bytecode_length: *tx.bytecode_length(),
-                    bytecode_witness_index: *tx.bytecode_witness_index(),
-                    storage_slots: tx
-                        .storage_slots()
-                        .iter()
-                        .map(|x| StorageSlot {
-                            key: <[u8; 32]>::from(*x.key()).into(),
-                            value: <[u8; 32]>::from(*x.value()).into(),
-                        })
-                        .collect(),
-                    inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
-                    outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
-                    witnesses: tx.witnesses().to_vec(),
-                    salt: <[u8; 32]>::from(*tx.salt()).into(),
-                    metadata: None,
-                }),
-                ClientTransaction::Script(tx) => Transaction::Script(Script {
-                    gas_price: tx.gas_price(),
-                    maturity: (*tx.maturity()).into(),
-                    script: (*tx.script().clone()).to_vec(),
-                    script_data: (*tx.script_data().clone()).to_vec(),
-                    inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
-                    outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
-                    witnesses: tx.witnesses().to_vec(),
-                    receipts_root: <[u8; 32]>::from(*tx.receipts_root()).into(),
-                    metadata: None,
-                }),
-                ClientTransaction::Mint(tx) => Transaction::Mint(Mint {
-                    tx_pointer: tx.tx_pointer().to_owned().into(),
-                    metadata: None,
-                }),
-            };
+        // A block without transactions can't be used as a cursor, in which case the
+        // scan starts from the first transaction, and skips the indexed blocks.
+        debug!("Indexer({indexer_uid}) scanning transactions after block #{height}");
+        self.cursor = cursor;
+        self.scanned_height = height;
+        self.has_next_page = true;
 
-            let tx_data = TransactionData {
-                receipts,
-                status,
-                transaction,
-                id,
-            };
+        true
+    }
+
+    /// Scan the next page of transactions, and record the heights of the blocks
+    /// that include the ones involving the contracts. Returns whether the page
+    /// could be retrieved.
+    async fn discover_heights(
+        &mut self,
+        client: &FuelClient,
+        last_height: u32,
+        indexer_uid: &str,
+    ) -> bool {
+        let request = PaginationRequest {
+            cursor: self.cursor.clone(),
+            results: SPARSE_TRANSACTIONS_PAGE_SIZE as i32,
+            direction: PageDirection::Forward,
+        };
+
+        match client.transaction_contracts(request).await {
+            Ok(PaginatedResult {
+                cursor,
+                results,
+                has_next_page,
+                ..
+            }) => {
+                let transactions = results.iter().filter_map(|tx| {
+                    tx.block_height().map(|height| (height, tx.contract_ids()))
+                });
+                self.record_transactions(transactions, last_height);
+
+                // An empty page has no end cursor, in which case we keep our place.
+                if cursor.is_some() {
+                    self.cursor = cursor;
+                }
+                self.has_next_page = has_next_page;
 
-            transactions.push(tx_data);
+                true
+            }
+            Err(e) => {
+                error!("Indexer({indexer_uid}) failed to retrieve transactions: {e:?}");
+                false
+            }
         }
+    }
 
-        // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
-        let consensus = match &block.consensus {
-            ClientConsensus::Unknown => Consensus::Unknown,
-            ClientConsensus::Genesis(g) => {
-                let ClientGenesis {
-                    chain_config_hash,
-                    coins_root,
-                    contracts_root,
-                    messages_root,
-                } = g.to_owned();
+    /// Record the heights of the blocks of the given scanned transactions that
+    /// involve the contracts, along with the height of the last one.
+    fn record_transactions(
+        &mut self,
+        transactions: impl IntoIterator<Item = (u32, Vec<ContractId>)>,
+        last_height: u32,
+    ) {
+        for (height, contract_ids) in transactions {
+            self.scanned_height = std::cmp::max(self.scanned_height, height);
+
+            // Blocks at or below the cursor have already been indexed, in case the
+            // scan started from the first transaction.
+            if height > last_height
+                && contract_ids.iter().any(|id| self.contract_ids.contains(id))
+            {
+                self.pending.insert(height);
+            }
+        }
+    }
 
-                Consensus::Genesis(Genesis {
-                    chain_config_hash: <[u8; 32]>::from(
-                        chain_config_hash.to_owned().0 .0,
-                    )
-                    .into(),
-                    coins_root: <[u8; 32]>::from(coins_root.0 .0.to_owned()).into(),
-                    contracts_root: <[u8; 32]>::from(contracts_root.0 .0.to_owned())
-                        .into(),
-                    messages_root: <[u8; 32]>::from(messages_root.0 .0.to_owned()).into(),
-                })
-            }
-            ClientConsensus::PoAConsensus(poa) => Consensus::PoA(PoA {
-                signature: <[u8; 64]>::from(poa.signature.0 .0.to_owned()).into(),
+    /// Whether there are more relevant blocks known to the node than have been fetched.
+    fn has_next_page(&self) -> bool {
+        !self.pending.is_empty() || self.has_next_page
+    }
+}
+
+/// Parse a hex or `Bech32` contract ID.
+fn parse_contract_id(id: &str) -> IndexerResult<ContractId> {
+    Bech32ContractId::from_str(id)
+        .map(|id| ContractId::from(&id))
+        .or_else(|_| ContractId::from_str(id))
+        .map_err(|e| anyhow::anyhow!("Invalid contract ID '{id}': {e}").into())
+}
+
+/// Build the cursor of the transactions query that points at the given transaction
+/// of the block at `height`.
+fn transactions_cursor(height: u32, tx_id: &Bytes32) -> String {
+    format!("{height}#{tx_id:#x}")
+}
+
+/// Build the offsets of a script transaction's fields, which allow pointers into
+/// the transaction (e.g., contract call arguments) to be resolved.
+fn script_metadata(
+    tx: &fuel_tx::Script,
+    id: Bytes32,
+    params: &ConsensusParameters,
+) -> ScriptMetadata {
+    let inputs = 0..tx.inputs().len();
+    let outputs = 0..tx.outputs().len();
+    let witnesses = 0..tx.witnesses().len();
+
+    ScriptMetadata {
+        common: CommonMetadata {
+            id,
+            inputs_offset: tx.inputs_offset(),
+            inputs_offset_at: inputs
+                .clone()
+                .filter_map(|i| tx.inputs_offset_at(i))
+                .collect(),
+            inputs_predicate_offset_at: inputs
+                .map(|i| tx.inputs_predicate_offset_at(i))
+                .collect(),
+            outputs_offset: tx.outputs_offset(),
+            outputs_offset_at: outputs.filter_map(|i| tx.outputs_offset_at(i)).collect(),
+            witnesses_offset: tx.witnesses_offset(),
+            witnesses_offset_at: witnesses
+                .filter_map(|i| tx.witnesses_offset_at(i))
+                .collect(),
+        },
+        script_data_offset: tx.script_data_offset(),
+        tx_offset: params.tx_params.tx_offset(),
+    }
+}
+
+/// Convert a `FullBlock` returned by the client into the `BlockData` passed to indexers.
+fn full_block_to_block_data(block: FullBlock, params: &ConsensusParameters) -> BlockData {
+    let producer = block.block_producer().map(|pk| pk.hash());
+
+    let mut transactions = Vec::new();
+
+    for trans in block.transactions {
+        let receipts = trans
+            .receipts
+            .unwrap_or_default()
+            .into_iter()
+            .map(TryInto::try_into)
+            .try_collect()
+            .expect("Bad receipts.");
+
+        let status = trans.status.expect("Bad transaction status.");
+        // NOTE: https://github.com/FuelLabs/fuel-indexer/issues/286
+        let status = match status.try_into().unwrap() {
+            ClientTransactionStatus::Success {
+                block_id,
+                time,
+                program_state,
+                ..
+            } => {
+                let program_state = program_state.map(|p| match p {
+                    ClientProgramState::Return(w) => ProgramState {
+                        return_type: ReturnType::Return,
+                        data: Bytes::from(w.to_le_bytes().to_vec()),
+                    },
+                    ClientProgramState::ReturnData(d) => ProgramState {
+                        return_type: ReturnType::ReturnData,
+                        data: Bytes::from(d.to_vec()),
+                    },
+                    ClientProgramState::Revert(w) => ProgramState {
+                        return_type: ReturnType::Revert,
+                        data: Bytes::from(w.to_le_bytes().to_vec()),
+                    },
+                    // Either `cargo watch` complains that this is unreachable, or `clippy` complains
+                    // that all patterns are not matched. These other program states are only used in
+                    // debug modes.
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!("Bad program state."),
+                });
+                TransactionStatus::Success {
+                    block: block_id.parse().expect("Bad block height."),
+                    time: time.to_unix() as u64,
+                    program_state,
+                }
+            }
+            ClientTransactionStatus::Failure {
+                block_id,
+                time,
+                reason,
+                program_state,
+                ..
+            } => {
+                let program_state = program_state.map(|p| match p {
+                    ClientProgramState::Return(w) => ProgramState {
+                        return_type: ReturnType::Return,
+                        data: Bytes::from(w.to_le_bytes().to_vec()),
+                    },
+                    ClientProgramState::ReturnData(d) => ProgramState {
+                        return_type: ReturnType::ReturnData,
+                        data: Bytes::from(d.to_vec()),
+                    },
+                    ClientProgramState::Revert(w) => ProgramState {
+                        return_type: ReturnType::Revert,
+                        data: Bytes::from(w.to_le_bytes().to_vec()),
+                    },
+                    // Either `cargo watch` complains that this is unreachable, or `clippy` complains
+                    // that all patterns are not matched. These other program states are only used in
+                    // debug modes.
+                    #[allow(unreachable_patterns)]
+                    _ => unreachable!("Bad program state."),
+                });
+                TransactionStatus::Failure {
+                    block: block_id.parse().expect("Bad block ID."),
+                    time: time.to_unix() as u64,
+                    program_state,
+                    reason,
+                }
+            }
+            ClientTransactionStatus::Submitted { submitted_at } => {
+                TransactionStatus::Submitted {
+                    submitted_at: submitted_at.to_unix() as u64,
+                }
+            }
+            ClientTransactionStatus::SqueezedOut { reason } => {
+                TransactionStatus::SqueezedOut { reason }
+            }
+        };
+
+        let transaction: fuel_tx::Transaction =
+            fuel_tx::Transaction::from_bytes(trans.raw_payload.0 .0.as_slice())
+                .expect("Bad transaction.");
+
+        let id = transaction.id(&params.chain_id);
+
+        let transaction = match transaction {
+            ClientTransaction::Create(tx) => Transaction::Create(Create {
+                gas_price: tx.gas_price(),
+                maturity: tx.maturity(),
+                bytecode_length: *tx.bytecode_length(),
+                bytecode_witness_index: *tx.bytecode_witness_index(),
+                storage_slots: tx
+                    .storage_slots()
+                    .iter()
+                    .map(|x| StorageSlot {
+                        key: <[u8; 32]>::from(*x.key()).into(),
+                        value: <[u8; 32]>::from(*x.value()).into(),
+                    })
+                    .collect(),
+                inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
+                outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
+                witnesses: tx.witnesses().to_vec(),
+                salt: <[u8; 32]>::from(*tx.salt()).into(),
+                metadata: None,
+            }),
+            ClientTransaction::Script(tx) => Transaction::Script(Script {
+                gas_price: tx.gas_price(),
+                maturity: (*tx.maturity()).into(),
+                script: (*tx.script().clone()).to_vec(),
+                script_data: (*tx.script_data().clone()).to_vec(),
+                inputs: tx.inputs().iter().map(|i| i.to_owned().into()).collect(),
+                outputs: tx.outputs().iter().map(|o| o.to_owned().into()).collect(),
+                witnesses: tx.witnesses().to_vec(),
+                receipts_root: <[u8; 32]>::from(*tx.receipts_root()).into(),
+                metadata: Some(script_metadata(&tx, id, params)),
+            }),
+            ClientTransaction::Mint(tx) => Transaction::Mint(Mint {
+                tx_pointer: tx.tx_pointer().to_owned().into(),
+                metadata: None,
             }),
         };
 
-        // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
-        let block = BlockData {
-            height: block.header.height.clone().into(),
-            id: Bytes32::from(<[u8; 32]>::from(block.id.0 .0)),
-            producer,
-            time: block.header.time.0.to_unix(),
-            consensus,
-            header: Header {
-                id: block.header.id.into(),
-                da_height: block.header.da_height.0,
-                transactions_count: block.header.transactions_count.into(),
-                message_receipt_count: block.header.message_receipt_count.into(),
-                transactions_root: block.header.transactions_root.into(),
-                message_receipt_root: block.header.message_receipt_root.into(),
-                height: block.header.height.into(),
-                prev_root: block.header.prev_root.into(),
-                time: block.header.time.0.to_unix(),
-                application_hash: block.header.application_hash.into(),
-            },
-            transactions,
+        let tx_data = TransactionData {
+            receipts,
+            status,
+            transaction,
+            id,
         };
 
-        block_info.push(block);
+        transactions.push(tx_data);
     }
 
-    Ok((block_info, cursor, has_next_page))
+    // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
+    let consensus = match &block.consensus {
+        ClientConsensus::Unknown => Consensus::Unknown,
+        ClientConsensus::Genesis(g) => {
+            let ClientGenesis {
+                chain_config_hash,
+                coins_root,
+                contracts_root,
+                messages_root,
+            } = g.to_owned();
+
+            Consensus::Genesis(Genesis {
+                chain_config_hash: <[u8; 32]>::from(chain_config_hash.to_owned().0 .0)
+                    .into(),
+                coins_root: <[u8; 32]>::from(coins_root.0 .0.to_owned()).into(),
+                contracts_root: <[u8; 32]>::from(contracts_root.0 .0.to_owned()).into(),
+                messages_root: <[u8; 32]>::from(messages_root.0 .0.to_owned()).into(),
+            })
+        }
+        ClientConsensus::PoAConsensus(poa) => Consensus::PoA(PoA {
+            signature: <[u8; 64]>::from(poa.signature.0 .0.to_owned()).into(),
+        }),
+    };
+
+    // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
+    BlockData {
+        height: block.header.height.clone().into(),
+        id: Bytes32::from(<[u8; 32]>::from(block.id.0 .0)),
+        producer,
+        time: block.header.time.0.to_unix(),
+        consensus,
+        header: Header {
+            id: block.header.id.into(),
+            da_height: block.header.da_height.0,
+            transactions_count: block.header.transactions_count.into(),
+            message_receipt_count: block.header.message_receipt_count.into(),
+            transactions_root: block.header.transactions_root.into(),
+            message_receipt_root: block.header.message_receipt_root.into(),
+            height: block.header.height.into(),
+            prev_root: block.header.prev_root.into(),
+            time: block.header.time.0.to_unix(),
+            application_hash: block.header.application_hash.into(),
+        },
+        transactions,
+    }
+}
+
+/// Failure of the handlers of an indexer, as reported in its status.
+#[derive(Debug, Clone, Default)]
+pub struct HandlerFailure {
+    /// Message of the error returned by the handler, or of its panic, along with
+    /// the location of the panic in the source code.
+    pub message: String,
+
+    /// Height of the block being processed when the handler failed.
+    pub block_height: Option<u32>,
+
+    /// Name of the handler that failed.
+    pub handler: Option<String>,
+
+    /// Symbolized backtrace of the WASM execution.
+    pub backtrace: Option<String>,
+}
+
+impl std::fmt::Display for HandlerFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.handler, self.block_height) {
+            (Some(handler), Some(height)) => {
+                write!(f, "Handler {handler}() failed at block {height}: ")?
+            }
+            (Some(handler), None) => write!(f, "Handler {handler}() failed: ")?,
+            (None, Some(height)) => write!(f, "Handler failed at block {height}: ")?,
+            (None, None) => (),
+        }
+
+        write!(f, "{}", self.message)?;
+
+        if let Some(backtrace) = &self.backtrace {
+            write!(f, "\n\nBacktrace:\n{backtrace}")?;
+        }
+
+        Ok(())
+    }
 }
 
 /// Executors are responsible for the actual indexing of data.
@@ -596,7 +1127,12 @@ where
 
     fn kill_switch(&self) -> &Arc<AtomicBool>;
 
-    async fn get_error_message(&self) -> IndexerResult<String>;
+    /// Get the failure of the last execution of the handlers.
+    async fn get_handler_failure(&self) -> IndexerResult<HandlerFailure>;
+
+    /// Prune the tables with a retention policy as of the given block height, and
+    /// return the number of deleted rows, or `None` if no table has a retention policy.
+    async fn prune(&self, height: u32) -> IndexerResult<Option<u64>>;
 }
 
 /// WASM indexer runtime environment responsible for fetching/saving data to and from the database.
@@ -617,6 +1153,9 @@ pub struct IndexEnv {
     /// Kill switch for this indexer. When true, the indexer service indicated
     /// that the indexer is being terminated.
     pub kill_switch: Arc<AtomicBool>,
+
+    /// Logs of the indexer's handlers.
+    pub logs: Arc<std::sync::Mutex<IndexerLogs>>,
 }
 
 impl IndexEnv {
@@ -628,12 +1167,14 @@ impl IndexEnv {
         kill_switch: Arc<AtomicBool>,
     ) -> IndexerResult<IndexEnv> {
         let db = Database::new(pool, manifest, config).await;
+        let logs = IndexerLogs::new(manifest.uid(), config.indexer_log_lines);
         Ok(IndexEnv {
             memory: None,
             alloc: None,
             dealloc: None,
             db: Arc::new(Mutex::new(db)),
             kill_switch,
+            logs: Arc::new(std::sync::Mutex::new(logs)),
         })
     }
 }
@@ -657,9 +1198,15 @@ pub struct WasmIndexExecutor {
     /// Reference to the connected database.
     db: Arc<Mutex<Database>>,
 
+    /// Logs of the indexer's handlers.
+    logs: Arc<std::sync::Mutex<IndexerLogs>>,
+
     /// Number of metering points to use for this executor.
     metering_points: Option<u64>,
 
+    /// Symbolized backtrace of the last failed execution.
+    backtrace: Option<String>,
+
     /// Manifest of the indexer.
     manifest: Manifest,
 
@@ -692,6 +1239,7 @@ impl WasmIndexExecutor {
         let idx_env = IndexEnv::new(pool, manifest, config, kill_switch.clone()).await?;
 
         let db: Arc<Mutex<Database>> = idx_env.db.clone();
+        let logs = idx_env.logs.clone();
 
         let mut store = Store::new(compiler_config);
 
@@ -749,7 +1297,9 @@ impl WasmIndexExecutor {
             _module: module,
             store: Arc::new(Mutex::new(store)),
             db: db.clone(),
+            logs,
             metering_points: config.metering_points,
+            backtrace: None,
             manifest: manifest.clone(),
             kill_switch,
         })
@@ -860,6 +1410,122 @@ impl WasmIndexExecutor {
             .into())
         }
     }
+
+    /// Get the entrypoint of the WASM module.
+    async fn entrypoint(&self) -> IndexerResult<TypedFunction<(u32, u32), ()>> {
+        let store_guard = self.store.lock().await;
+        let fun = self
+            .instance
+            .exports
+            .get_typed_function::<(u32, u32), ()>(&store_guard, ffi::MODULE_ENTRYPOINT)?;
+        Ok(fun)
+    }
+
+    /// Call the entrypoint of the WASM module with the given serialized blocks.
+    async fn call_entrypoint(
+        &self,
+        fun: TypedFunction<(u32, u32), ()>,
+        bytes: Vec<u8>,
+    ) -> IndexerResult<Result<(), wasmer::RuntimeError>> {
+        let res = spawn_blocking({
+            let store = self.store.clone();
+            let instance = self.instance.clone();
+            let metering_enabled = self.metering_enabled();
+            move || {
+                let store_guard =
+                    tokio::runtime::Handle::current().block_on(store.lock());
+                let mut arg =
+                    ffi::WasmArg::new(store_guard, instance, bytes, metering_enabled)
+                        .unwrap();
+
+                let ptr = arg.get_ptr();
+                let len = arg.get_len();
+
+                fun.call(&mut arg.store(), ptr, len)
+            }
+        })
+        .await?;
+
+        Ok(res)
+    }
+
+    /// Run the handlers against a single block, in a transaction that is always
+    /// reverted, and report the changes that they made to entities, along with
+    /// the lines that they logged and the metering points that they used.
+    pub async fn debug_block(&mut self, block: BlockData) -> IndexerResult<DebugReport> {
+        let block_height = block.height;
+        self.backtrace = None;
+
+        if let Some(metering_points) = self.metering_points {
+            self.set_metering_points(metering_points).await?
+        }
+        let bytes = serialize(&vec![block]);
+
+        let fun = self.entrypoint().await?;
+
+        let _ = self.db.lock().await.start_debug_transaction().await?;
+
+        let res = self.call_entrypoint(fun, bytes).await;
+
+        let (changes, rejected) = {
+            let mut db = self.db.lock().await;
+            let changes = db.take_changes();
+            db.revert_transaction().await?;
+            (changes, db.take_immutable_entity_error())
+        };
+
+        let logs = self
+            .logs
+            .lock()
+            .expect("Failed to lock indexer logs.")
+            .take()
+            .iter()
+            .map(|line| line.to_string())
+            .collect();
+
+        let metering_points_used = match (
+            self.metering_points,
+            self.get_remaining_metering_points().await,
+        ) {
+            (Some(limit), Some(MeteringPoints::Remaining(remaining))) => {
+                Some(limit - remaining)
+            }
+            (Some(limit), Some(MeteringPoints::Exhausted)) => Some(limit),
+            _ => None,
+        };
+
+        let error = match res? {
+            Ok(()) => None,
+            Err(e) => match e.downcast_ref::<WasmIndexerError>() {
+                Some(&WasmIndexerError::Panic)
+                | Some(&WasmIndexerError::GeneralError) => {
+                    self.backtrace = wasm_backtrace(&e);
+                    Some(self.get_handler_failure().await?.to_string())
+                }
+                _ => Some(rejected.map_or_else(|| e.to_string(), |e| e.to_string())),
+            },
+        };
+
+        Ok(DebugReport {
+            block_height,
+            changes,
+            logs,
+            metering_points_used,
+            error,
+        })
+    }
+
+    /// Report the logs that the indexer dropped because they could not be decoded
+    /// using its ABI, and it has no `UndecodedLog` handler.
+    async fn record_dropped_logs(&self) {
+        let dropped_logs = {
+            let mut store_guard = self.store.lock().await;
+            // Modules built with an older version of the macro don't count dropped logs.
+            ffi::take_dropped_logs(&mut store_guard, &self.instance).unwrap_or(0)
+        };
+
+        report_dropped_logs(&self.manifest, dropped_logs);
+    }
 }
 
 #[async_trait]
@@ -870,43 +1536,25 @@ impl Executor for WasmIndexExecutor {
             return Ok(());
         }
 
+        // Only the backtrace of the last failed execution is reported.
+        self.backtrace = None;
+
         if let Some(metering_points) = self.metering_points {
             self.set_metering_points(metering_points).await?
         }
         let bytes = serialize(&blocks);
         let uid = self.manifest.uid();
 
-        let fun = {
-            let store_guard = self.store.lock().await;
-            self.instance.exports.get_typed_function::<(u32, u32), ()>(
-                &store_guard,
-                ffi::MODULE_ENTRYPOINT,
-            )?
-        };
+        let fun = self.entrypoint().await?;
 
         let _ = self.db.lock().await.start_transaction().await?;
 
         #[cfg(feature = "metrics")]
         let start = Instant::now();
 
-        let res = spawn_blocking({
-            let store = self.store.clone();
-            let instance = self.instance.clone();
-            let metering_enabled = self.metering_enabled();
-            move || {
-                let store_guard =
-                    tokio::runtime::Handle::current().block_on(store.lock());
-                let mut arg =
-                    ffi::WasmArg::new(store_guard, instance, bytes, metering_enabled)
-                        .unwrap();
-
-                let ptr = arg.get_ptr();
-                let len = arg.get_len();
+        let res = self.call_entrypoint(fun, bytes).await?;
 
-                fun.call(&mut arg.store(), ptr, len)
-            }
-        })
-        .await?;
+        persist_logs(&self.db, &self.logs, &self.manifest).await;
 
         #[cfg(feature = "metrics")]
         {
@@ -934,10 +1582,19 @@ impl Executor for WasmIndexExecutor {
                 } else {
                     error!("Indexer({uid}) WASM execution failed: {e:?}.");
                 };
-                self.db.lock().await.revert_transaction().await?;
+                self.backtrace = wasm_backtrace(&e);
+                let mut db = self.db.lock().await;
+                db.revert_transaction().await?;
+                // Handlers only learn that a write failed, so the reason for a
+                // rejected write to an immutable entity is taken from the database.
+                if let Some(e) = db.take_immutable_entity_error() {
+                    return Err(e);
+                }
                 return Err(IndexerError::from(e));
             }
         } else {
+            self.record_dropped_logs().await;
+
             // Do not commit if kill switch has been triggered.
             if self.kill_switch.load(Ordering::SeqCst) {
                 self.db.lock().await.revert_transaction().await?;
@@ -957,9 +1614,584 @@ impl Executor for WasmIndexExecutor {
         &self.manifest
     }
 
-    async fn get_error_message(&self) -> IndexerResult<String> {
+    async fn get_handler_failure(&self) -> IndexerResult<HandlerFailure> {
         let mut store = self.store.lock().await;
-        let result = ffi::get_error_message(&mut store, &self.instance)?;
-        Ok(result)
+        let message = ffi::get_error_message(&mut store, &self.instance)
+            .unwrap_or("unable to extract the error message".to_string());
+
+        // Modules built before handler names were reported don't export them.
+        let handler = ffi::get_handler_name(&mut store, &self.instance)
+            .ok()
+            .filter(|handler| !handler.is_empty());
+
+        Ok(HandlerFailure {
+            message,
+            block_height: last_block_height(&self.logs),
+            handler,
+            backtrace: self.backtrace.clone(),
+        })
+    }
+
+    async fn prune(&self, height: u32) -> IndexerResult<Option<u64>> {
+        let db = self.db.lock().await;
+        if !db.has_retention_policy() {
+            return Ok(None);
+        }
+
+        db.prune(height).await.map(Some)
+    }
+}
+
+/// Symbolize the backtrace of a failed WASM execution, using the function names
+/// of the name section of the module.
+fn wasm_backtrace(e: &wasmer::RuntimeError) -> Option<String> {
+    symbolize_frames(e.trace().iter().filter_map(|frame| frame.function_name()))
+}
+
+/// Format the frames of a backtrace from their mangled function names.
+///
+/// Frames of the standard library, and of the panic hook, are left out.
+fn symbolize_frames<'a>(names: impl Iterator<Item = &'a str>) -> Option<String> {
+    const INTERNAL_PREFIXES: [&str; 8] = [
+        "std::", "core::", "alloc::", "<std::", "<core::", "<alloc::", "__rust", "rust_",
+    ];
+
+    let frames = names
+        .filter_map(|name| {
+            let name = format!("{:#}", rustc_demangle::demangle(name));
+            let internal = INTERNAL_PREFIXES
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+                || name.contains("register_panic_hook")
+                || name.contains("early_exit");
+            (!internal).then_some(name)
+        })
+        .enumerate()
+        .map(|(i, name)| format!("{i:>4}: {name}"))
+        .collect::<Vec<_>>();
+
+    if frames.is_empty() {
+        None
+    } else {
+        Some(frames.join("\n"))
+    }
+}
+
+/// Height of the block that the handlers of an indexer processed last, if they
+/// reported it.
+fn last_block_height(logs: &std::sync::Mutex<IndexerLogs>) -> Option<u32> {
+    let block_height = logs
+        .lock()
+        .expect("Failed to lock indexer logs.")
+        .block_height();
+    (block_height > 0).then_some(block_height)
+}
+
+/// Persist the lines logged by the handlers of an indexer.
+///
+/// Failing to persist logs does not fail the indexer.
+async fn persist_logs(
+    db: &Arc<Mutex<Database>>,
+    logs: &std::sync::Mutex<IndexerLogs>,
+    manifest: &Manifest,
+) {
+    let lines = logs.lock().expect("Failed to lock indexer logs.").take();
+    if let Err(e) = db.lock().await.put_logs(lines).await {
+        let uid = manifest.uid();
+        warn!("Indexer({uid}) failed to persist logs: {e:?}.");
+    }
+}
+
+/// Report the logs that an indexer dropped because they could not be decoded using
+/// its ABI, and it has no `UndecodedLog` handler.
+fn report_dropped_logs(manifest: &Manifest, dropped_logs: u64) {
+    if dropped_logs > 0 {
+        let uid = manifest.uid();
+        warn!("Indexer({uid}) dropped {dropped_logs} log(s) that could not be decoded.");
+
+        #[cfg(feature = "metrics")]
+        METRICS.exec.dropped_logs.record(&uid, dropped_logs);
+    }
+}
+
+/// Handler function of a native indexer.
+///
+/// This is the `handle_events` function that the `indexer` macro generates for
+/// manifests with a `native` module.
+pub type NativeHandlerFn = fn(Vec<BlockData>) -> anyhow::Result<()>;
+
+/// Native executors run indexers that are compiled as regular Rust.
+///
+/// The indexer is linked into a custom indexer service binary, which registers its
+/// handler function into a native executor. Handlers use the same entity API as WASM
+/// handlers, but run without being sandboxed or metered, so native executors should
+/// only be used for trusted indexers.
+pub struct NativeIndexExecutor {
+    /// Handler function of the indexer.
+    handle_events_fn: NativeHandlerFn,
+
+    /// Reference to the connected database.
+    db: Arc<Mutex<Database>>,
+
+    /// Manifest of the indexer.
+    manifest: Manifest,
+
+    /// Kill switch. When set to true, the indexer must stop execution.
+    kill_switch: Arc<AtomicBool>,
+
+    /// Logs of the indexer's handlers.
+    logs: Arc<std::sync::Mutex<IndexerLogs>>,
+
+    /// Failure of the last execution of the handler, if it returned an error or panicked.
+    failure: Option<HandlerFailure>,
+}
+
+impl NativeIndexExecutor {
+    /// Create a new `NativeIndexExecutor`.
+    pub async fn new(
+        config: &IndexerConfig,
+        manifest: &Manifest,
+        pool: IndexerConnectionPool,
+        schema_version: String,
+        handle_events_fn: NativeHandlerFn,
+    ) -> IndexerResult<Self> {
+        let mut db = Database::new(pool, manifest, config).await;
+        db.load_schema(schema_version).await?;
+
+        Ok(NativeIndexExecutor {
+            handle_events_fn,
+            db: Arc::new(Mutex::new(db)),
+            manifest: manifest.clone(),
+            kill_switch: Arc::new(AtomicBool::new(false)),
+            logs: Arc::new(std::sync::Mutex::new(IndexerLogs::new(
+                manifest.uid(),
+                config.indexer_log_lines,
+            ))),
+            failure: None,
+        })
+    }
+
+    /// Create a new `NativeIndexExecutor`.
+    pub async fn create(
+        config: &IndexerConfig,
+        manifest: &Manifest,
+        pool: IndexerConnectionPool,
+        schema_version: String,
+        handle_events_fn: NativeHandlerFn,
+    ) -> IndexerResult<Self> {
+        let uid = manifest.uid();
+
+        let mut conn = pool.acquire().await?;
+        queries::set_indexer_status(
+            &mut conn,
+            manifest.namespace(),
+            manifest.identifier(),
+            IndexerStatus::instantiating(),
+        )
+        .await?;
+
+        match NativeIndexExecutor::new(
+            config,
+            manifest,
+            pool.clone(),
+            schema_version,
+            handle_events_fn,
+        )
+        .await
+        {
+            Ok(executor) => Ok(executor),
+            Err(e) => {
+                error!("Could not instantiate NativeIndexExecutor({uid}): {e:?}.");
+                let mut conn = pool.acquire().await?;
+                queries::set_indexer_status(
+                    &mut conn,
+                    manifest.namespace(),
+                    manifest.identifier(),
+                    IndexerStatus::error(format!("{e}")),
+                )
+                .await?;
+                Err(IndexerError::ExecutorInitError)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Executor for NativeIndexExecutor {
+    /// Call the native event handler, passing in the blocks.
+    async fn handle_events(&mut self, blocks: Vec<BlockData>) -> IndexerResult<()> {
+        if blocks.is_empty() {
+            return Ok(());
+        }
+
+        let uid = self.manifest.uid();
+
+        let _ = self.db.lock().await.start_transaction().await?;
+
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+
+        let handler_name = Arc::new(std::sync::Mutex::new(None));
+        let dropped_logs = Arc::new(AtomicU64::new(0));
+
+        let res = spawn_blocking({
+            let env = NativeEnv {
+                db: self.db.clone(),
+                kill_switch: self.kill_switch.clone(),
+                logs: self.logs.clone(),
+                handler_name: handler_name.clone(),
+                dropped_logs: dropped_logs.clone(),
+            };
+            let handle_events_fn = self.handle_events_fn;
+            move || {
+                env.enter(|| catch_unwind(AssertUnwindSafe(|| handle_events_fn(blocks))))
+            }
+        })
+        .await?;
+
+        persist_logs(&self.db, &self.logs, &self.manifest).await;
+
+        #[cfg(feature = "metrics")]
+        {
+            METRICS
+                .exec
+                .handler
+                .record(&self.manifest.uid(), start.elapsed().as_millis() as f64);
+        }
+
+        let message = match res {
+            Ok(Ok(())) => {
+                report_dropped_logs(&self.manifest, dropped_logs.load(Ordering::SeqCst));
+
+                // Do not commit if kill switch has been triggered.
+                if self.kill_switch.load(Ordering::SeqCst) {
+                    self.db.lock().await.revert_transaction().await?;
+                } else {
+                    self.db.lock().await.commit_transaction().await?;
+                }
+
+                return Ok(());
+            }
+            Ok(Err(e)) => format!("{e:?}"),
+            Err(payload) => match payload.downcast::<WasmIndexerError>() {
+                // The handler exited early through the entity API.
+                Ok(e) => match *e {
+                    // Termination due to kill switch is an expected behavior.
+                    WasmIndexerError::KillSwitch => {
+                        info!("Indexer({uid}) native execution terminated: {e}.");
+                        self.db.lock().await.revert_transaction().await?;
+                        return Err(IndexerError::KillSwitch);
+                    }
+                    WasmIndexerError::MissingBlocksError => e.to_string(),
+                    _ => {
+                        error!("Indexer({uid}) native execution failed: {e}.");
+                        let mut db = self.db.lock().await;
+                        db.revert_transaction().await?;
+                        return Err(db
+                            .take_immutable_entity_error()
+                            .unwrap_or_else(|| anyhow::anyhow!("{e}").into()));
+                    }
+                },
+                Err(payload) => {
+                    if let Some(message) = payload.downcast_ref::<&str>() {
+                        message.to_string()
+                    } else if let Some(message) = payload.downcast_ref::<String>() {
+                        message.clone()
+                    } else {
+                        "handler panicked".to_string()
+                    }
+                }
+            },
+        };
+
+        error!("Indexer({uid}) native execution failed: {message}.");
+        let handler = *handler_name.lock().expect("Failed to lock handler name.");
+        self.failure = Some(HandlerFailure {
+            message,
+            block_height: last_block_height(&self.logs),
+            handler: handler.map(str::to_string),
+            backtrace: None,
+        });
+        self.db.lock().await.revert_transaction().await?;
+
+        Err(IndexerError::NativeExecutionRuntimeError)
+    }
+
+    fn kill_switch(&self) -> &Arc<AtomicBool> {
+        &self.kill_switch
+    }
+
+    fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    async fn get_handler_failure(&self) -> IndexerResult<HandlerFailure> {
+        self.failure
+            .clone()
+            .ok_or(IndexerError::NativeExecutionRuntimeError)
+    }
+
+    async fn prune(&self, height: u32) -> IndexerResult<Option<u64>> {
+        let db = self.db.lock().await;
+        if !db.has_retention_policy() {
+            return Ok(None);
+        }
+
+        db.prune(height).await.map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract_id(byte: u8) -> ContractId {
+        ContractId::from([byte; 32])
+    }
+
+    fn sparse_fetcher() -> SparseBlockFetcher {
+        SparseBlockFetcher::new(&[
+            format!("{:x}", contract_id(1)),
+            format!("{:x}", contract_id(2)),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sparse_fetcher_discovers_blocks_involving_its_contracts() {
+        let mut fetcher = sparse_fetcher();
+
+        fetcher.record_transactions(
+            vec![
+                (3, vec![contract_id(1)]),
+                (4, vec![contract_id(3)]),
+                (5, vec![]),
+                (7, vec![contract_id(3), contract_id(2)]),
+                (7, vec![contract_id(1)]),
+            ],
+            0,
+        );
+
+        assert_eq!(fetcher.ready_heights(None), vec![3, 7]);
+        assert_eq!(fetcher.scanned_height, 7);
+    }
+
+    #[test]
+    fn test_sparse_fetcher_skips_indexed_blocks() {
+        let mut fetcher = sparse_fetcher();
+
+        fetcher.record_transactions(
+            vec![(3, vec![contract_id(1)]), (8, vec![contract_id(2)])],
+            5,
+        );
+
+        assert_eq!(fetcher.ready_heights(None), vec![8]);
+        assert_eq!(fetcher.scanned_height, 8);
+    }
+
+    #[test]
+    fn test_sparse_fetcher_stops_at_end_block() {
+        let mut fetcher = sparse_fetcher();
+
+        fetcher.record_transactions(
+            vec![
+                (3, vec![contract_id(1)]),
+                (6, vec![contract_id(1)]),
+                (9, vec![contract_id(2)]),
+            ],
+            0,
+        );
+
+        assert_eq!(fetcher.ready_heights(Some(6)), vec![3, 6]);
+        assert_eq!(fetcher.ready_heights(Some(2)), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_sparse_fetcher_has_next_page_until_scanned_and_indexed() {
+        let mut fetcher = sparse_fetcher();
+        assert!(fetcher.has_next_page());
+
+        fetcher.record_transactions(vec![(3, vec![contract_id(1)])], 0);
+        fetcher.has_next_page = false;
+        assert!(fetcher.has_next_page());
+
+        fetcher.pending.clear();
+        assert!(!fetcher.has_next_page());
+    }
+
+    #[test]
+    fn test_sparse_fetcher_resumes_after_the_last_transaction_of_a_block() {
+        let tx_id = Bytes32::from([0xab; 32]);
+
+        assert_eq!(
+            transactions_cursor(42, &tx_id),
+            format!("42#0x{}", "ab".repeat(32))
+        );
+    }
+
+    #[test]
+    fn test_sparse_fetcher_discovers_blocks_of_watched_contracts() {
+        let mut fetcher = sparse_fetcher();
+        fetcher.resume_after = None;
+
+        // Transactions up to block 9 were scanned before a handler watched
+        // contract 3 in block 5.
+        fetcher.record_transactions(
+            vec![
+                (4, vec![contract_id(3)]),
+                (6, vec![contract_id(3)]),
+                (9, vec![contract_id(1), contract_id(3)]),
+            ],
+            0,
+        );
+        assert_eq!(fetcher.ready_heights(None), vec![9]);
+
+        fetcher
+            .watch(&[format!("{:x}", contract_id(3))], 5)
+            .unwrap();
+        assert_eq!(fetcher.resume_after, Some(5));
+
+        // The scan resumes after block 5, once it has been indexed.
+        fetcher.record_transactions(
+            vec![
+                (6, vec![contract_id(3)]),
+                (9, vec![contract_id(1), contract_id(3)]),
+                (11, vec![contract_id(3)]),
+            ],
+            5,
+        );
+        assert_eq!(fetcher.ready_heights(None), vec![6, 9, 11]);
+    }
+
+    #[test]
+    fn test_sparse_fetcher_only_rescans_for_new_contracts() {
+        let mut fetcher = sparse_fetcher();
+        fetcher.resume_after = None;
+
+        fetcher
+            .watch(&[format!("{:x}", contract_id(1))], 5)
+            .unwrap();
+        assert_eq!(fetcher.resume_after, None);
+
+        fetcher
+            .watch(&[format!("{:x}", contract_id(3))], 8)
+            .unwrap();
+        fetcher
+            .watch(
+                &[
+                    format!("{:x}", contract_id(3)),
+                    format!("{:x}", contract_id(4)),
+                ],
+                10,
+            )
+            .unwrap();
+        assert_eq!(fetcher.resume_after, Some(8));
+
+        assert!(fetcher.watch(&["not a contract".to_string()], 12).is_err());
+    }
+
+    #[test]
+    fn test_sparse_fetcher_accepts_bech32_contract_ids() {
+        let bech32 = Bech32ContractId::from(contract_id(1)).to_string();
+        let fetcher = SparseBlockFetcher::new(&[bech32]).unwrap();
+        assert!(fetcher.contract_ids.contains(&contract_id(1)));
+
+        assert!(SparseBlockFetcher::new(&["not a contract".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_script_data_is_decoded_at_the_chain_specific_address() {
+        // A contract call's arguments, as written to the script data by the script.
+        let script_data = [[0u8; 8], 42u64.to_be_bytes()].concat();
+        let tx = fuel_tx::Transaction::script(
+            1_000_000,
+            vec![],
+            script_data.clone(),
+            fuel_tx::policies::Policies::default(),
+            vec![],
+            vec![],
+            vec![],
+        );
+
+        let mut params = ConsensusParameters::standard();
+        params.tx_params.max_inputs = 8;
+        assert_ne!(
+            params.tx_params.tx_offset(),
+            fuel_tx::TxParameters::DEFAULT.tx_offset()
+        );
+
+        let script = Script {
+            script_data,
+            metadata: Some(script_metadata(&tx, Bytes32::zeroed(), &params)),
+            ..Script::default()
+        };
+
+        // The pointer to the argument, as found in a `Call` receipt, is relative to
+        // where the chain places the transaction in VM memory.
+        let ptr = params.tx_params.tx_offset() + tx.script_data_offset() + 8;
+        let offset = ptr - script.script_data_address().unwrap();
+        let arg = &script.script_data[offset..offset + 8];
+        assert_eq!(u64::from_be_bytes(arg.try_into().unwrap()), 42);
+    }
+
+    #[test]
+    fn test_backtrace_leaves_out_internal_frames() {
+        let frames = [
+            "rust_begin_unwind",
+            "_ZN4core9panicking5panic17h0123456789abcdefE",
+            "fuel_indexer_plugin::wasm::register_panic_hook::{{closure}}",
+            "_ZN13hello_indexer15handle_transfer17h0123456789abcdefE",
+            "__rust_start_panic",
+            "handle_events",
+        ];
+
+        assert_eq!(
+            symbolize_frames(frames.into_iter()).unwrap(),
+            "   0: hello_indexer::handle_transfer\n   1: handle_events"
+        );
+
+        let internal = ["fuel_indexer_plugin::wasm::early_exit", "rust_panic"];
+        assert_eq!(symbolize_frames(internal.into_iter()), None);
+    }
+
+    #[test]
+    fn test_handler_failure_display() {
+        let failure = HandlerFailure {
+            message: "Transfer has no recipient at src/lib.rs:10:5".to_string(),
+            block_height: Some(7),
+            handler: Some("handle_transfer".to_string()),
+            backtrace: None,
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Handler handle_transfer() failed at block 7: Transfer has no recipient at src/lib.rs:10:5"
+        );
+
+        let failure = HandlerFailure {
+            block_height: None,
+            ..failure
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Handler handle_transfer() failed: Transfer has no recipient at src/lib.rs:10:5"
+        );
+
+        let failure = HandlerFailure {
+            message: "Failed to decode block".to_string(),
+            block_height: Some(7),
+            handler: None,
+            backtrace: Some("   0: handle_events".to_string()),
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Handler failed at block 7: Failed to decode block\n\nBacktrace:\n   0: handle_events"
+        );
+
+        let failure = HandlerFailure {
+            block_height: None,
+            backtrace: None,
+            ..failure
+        };
+        assert_eq!(failure.to_string(), "Failed to decode block");
     }
 }
@@ -1,11 +1,9 @@
 use async_std::sync::MutexGuard;
 use fuel_indexer_lib::{defaults, WasmIndexerError};
 use fuel_indexer_schema::{join::RawQuery, FtColumn};
-use fuel_indexer_types::ffi::{
-    LOG_LEVEL_DEBUG, LOG_LEVEL_ERROR, LOG_LEVEL_INFO, LOG_LEVEL_TRACE, LOG_LEVEL_WARN,
-};
+use fuel_indexer_types::{ffi::LogRecord, scalar::ContractId};
 use thiserror::Error;
-use tracing::{debug, error, info, trace, warn};
+use tracing::error;
 use wasmer::{
     AsStoreMut, ExportError, Exports, Function, FunctionEnvMut, Instance, MemoryView,
     RuntimeError, Store, WasmPtr,
@@ -73,6 +71,28 @@ pub fn get_error_message(store: &mut Store, instance: &Instance) -> FFIResult<St
     )
 }
 
+/// Get the name of the handler that the WASM instance ran last.
+pub fn get_handler_name(store: &mut Store, instance: &Instance) -> FFIResult<String> {
+    get_string_from_instance(
+        store,
+        instance,
+        "get_handler_name_ptr",
+        "get_handler_name_len",
+    )
+}
+
+/// Take the number of logs that the WASM instance dropped since this was last called.
+pub fn take_dropped_logs(store: &mut Store, instance: &Instance) -> FFIResult<u64> {
+    let dropped_logs = instance
+        .exports
+        .get_function("take_dropped_logs")?
+        .call(&mut store.as_store_mut(), &[])?[0]
+        .i64()
+        .ok_or_else(|| FFIError::None("take_dropped_logs".to_string()))?;
+
+    Ok(dropped_logs as u64)
+}
+
 /// Get the version of the indexer schema stored in the WASM instance.
 pub fn get_version(store: &mut Store, instance: &Instance) -> FFIResult<String> {
     get_string_from_instance(store, instance, "get_version_ptr", "get_version_len")
@@ -121,18 +141,88 @@ fn log_data(
     let log_string =
         get_string(&mem, ptr, len).expect("Log string could not be fetched.");
 
-    match log_level {
-        LOG_LEVEL_ERROR => error!("{log_string}",),
-        LOG_LEVEL_WARN => warn!("{log_string}",),
-        LOG_LEVEL_INFO => info!("{log_string}",),
-        LOG_LEVEL_DEBUG => debug!("{log_string}",),
-        LOG_LEVEL_TRACE => trace!("{log_string}",),
-        l => {
-            error!("Invalid log level: {l}");
-            return Err(WasmIndexerError::InvalidLogLevel);
+    idx_env
+        .logs
+        .lock()
+        .expect("Failed to lock indexer logs.")
+        .record(LogRecord {
+            level: log_level,
+            target: String::new(),
+            message: log_string,
+            fields: Vec::new(),
+        })
+}
+
+/// Log the serialized `LogRecord` at the given pointer.
+fn log_record(
+    mut env: FunctionEnvMut<IndexEnv>,
+    ptr: u32,
+    len: u32,
+) -> Result<(), WasmIndexerError> {
+    let (idx_env, store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .ok_or(WasmIndexerError::UninitializedMemory)?
+        .view(&store);
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let record: LogRecord = match bincode::deserialize(&bytes) {
+        Ok(record) => record,
+        Err(e) => {
+            error!("Failed to deserialize LogRecord for log_record: {e:?}");
+            return Err(WasmIndexerError::DeserializationError);
         }
+    };
+
+    idx_env
+        .logs
+        .lock()
+        .expect("Failed to lock indexer logs.")
+        .record(record)
+}
+
+/// Set the height of the block being processed, which is attached to logs.
+fn set_block_height(
+    env: FunctionEnvMut<IndexEnv>,
+    height: u32,
+) -> Result<(), WasmIndexerError> {
+    let idx_env = env.data();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
     }
 
+    idx_env
+        .logs
+        .lock()
+        .expect("Failed to lock indexer logs.")
+        .set_block_height(height);
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { idx_env.db.lock().await.set_block_height(height) });
+
     Ok(())
 }
 
@@ -324,7 +414,10 @@ fn delete_many(
                 .delete_many(type_id, constraints)
                 .await
         })
-        .unwrap();
+        .map_err(|e| {
+            error!("Failed to delete_many: {e}");
+            database_operation_failure(e)
+        })?;
 
     Ok(count as u64)
 }
@@ -442,11 +535,123 @@ fn put_many_to_many_record(
     Ok(())
 }
 
+/// Persist the contract ID at the given pointer as watched by the indexer.
+fn watch_contract(
+    mut env: FunctionEnvMut<IndexEnv>,
+    ptr: u32,
+    len: u32,
+) -> Result<(), WasmIndexerError> {
+    let (idx_env, store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .ok_or(WasmIndexerError::UninitializedMemory)?
+        .view(&store);
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    let range = ptr as usize..ptr as usize + len as usize;
+
+    unsafe {
+        bytes.extend_from_slice(&mem.data_unchecked()[range]);
+    }
+
+    let contract_id = match ContractId::try_from(bytes.as_slice()) {
+        Ok(contract_id) => format!("{contract_id:x}"),
+        Err(e) => {
+            error!("Failed to deserialize ContractId for watch_contract: {e:?}");
+            return Err(WasmIndexerError::DeserializationError);
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    let result =
+        rt.block_on(async { idx_env.db.lock().await.watch_contract(contract_id).await });
+
+    if let Err(e) = result {
+        error!("Failed to watch_contract: {e}");
+        return Err(WasmIndexerError::DatabaseError);
+    }
+
+    Ok(())
+}
+
+/// Get the contract IDs watched by the indexer.
+fn get_watched_contracts(
+    mut env: FunctionEnvMut<IndexEnv>,
+    len_ptr: u32,
+) -> Result<u32, WasmIndexerError> {
+    let (idx_env, mut store) = env.data_and_store_mut();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let rt = tokio::runtime::Handle::current();
+    let contract_ids = rt
+        .block_on(async { idx_env.db.lock().await.watched_contracts().await })
+        .map_err(|e| {
+            error!("Failed to get_watched_contracts: {e}");
+            WasmIndexerError::DatabaseError
+        })?;
+
+    if contract_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let bytes = fuel_indexer_lib::utils::serialize(&contract_ids);
+
+    let alloc_fn = idx_env
+        .alloc
+        .as_mut()
+        .ok_or(WasmIndexerError::AllocMissing)?;
+
+    let size = bytes.len() as u32;
+    let result = alloc_fn
+        .call(&mut store, size)
+        .map_err(|_| WasmIndexerError::AllocFailed)?;
+    let range = result as usize..result as usize + size as usize;
+
+    let mem = idx_env
+        .memory
+        .as_mut()
+        .ok_or(WasmIndexerError::UninitializedMemory)?
+        .view(&store);
+    WasmPtr::<u32>::new(len_ptr)
+        .deref(&mem)
+        .write(size)
+        .expect("Failed to write length to memory.");
+
+    unsafe {
+        mem.data_unchecked_mut()[range].copy_from_slice(&bytes);
+    }
+
+    Ok(result)
+}
+
 // Returns a specialized error code when the database trigger, which ensures
-// indexers can't miss blocks, raises an exception. Otherwise, returns an error
-// code indicating a generic database operation failure.
-fn database_operation_failure(e: crate::IndexerError) -> WasmIndexerError {
+// indexers can't miss blocks, raises an exception, or when an immutable entity
+// is written to. Otherwise, returns an error code indicating a generic database
+// operation failure.
+pub(crate) fn database_operation_failure(e: crate::IndexerError) -> WasmIndexerError {
     match e {
+        crate::IndexerError::ImmutableEntityError(..) => {
+            WasmIndexerError::ImmutableEntityError
+        }
         crate::IndexerError::SqlxError(e) => {
             if let Some(e) = e.as_database_error() {
                 if let Some(e) = e.try_downcast_ref::<sqlx::postgres::PgDatabaseError>() {
@@ -478,8 +683,13 @@ pub fn get_exports(store: &mut Store, env: &wasmer::FunctionEnv<IndexEnv>) -> Ex
     let f_delete_many = Function::new_typed_with_env(store, env, delete_many);
     let f_put_obj = Function::new_typed_with_env(store, env, put_object);
     let f_log_data = Function::new_typed_with_env(store, env, log_data);
+    let f_log_record = Function::new_typed_with_env(store, env, log_record);
+    let f_set_block_height = Function::new_typed_with_env(store, env, set_block_height);
     let f_put_many_to_many_record =
         Function::new_typed_with_env(store, env, put_many_to_many_record);
+    let f_watch_contract = Function::new_typed_with_env(store, env, watch_contract);
+    let f_get_watched_contracts =
+        Function::new_typed_with_env(store, env, get_watched_contracts);
     let f_early_exit = Function::new_typed(store, early_exit);
 
     exports.insert("ff_early_exit".to_string(), f_early_exit);
@@ -492,6 +702,13 @@ pub fn get_exports(store: &mut Store, env: &wasmer::FunctionEnv<IndexEnv>) -> Ex
         f_put_many_to_many_record,
     );
     exports.insert("ff_log_data".to_string(), f_log_data);
+    exports.insert("ff_log_record".to_string(), f_log_record);
+    exports.insert("ff_set_block_height".to_string(), f_set_block_height);
+    exports.insert("ff_watch_contract".to_string(), f_watch_contract);
+    exports.insert(
+        "ff_get_watched_contracts".to_string(),
+        f_get_watched_contracts,
+    );
 
     exports
 }
@@ -1,14 +1,21 @@
 #![deny(unused_crate_dependencies)]
+pub mod cache;
 pub mod cli;
 pub(crate) mod commands;
 mod database;
 pub mod executor;
 pub mod ffi;
+pub mod logs;
+pub mod native;
 pub(crate) mod queries;
 mod service;
 
+pub use cache::BlockCache;
 pub use database::Database;
-pub use executor::{Executor, IndexEnv, WasmIndexExecutor};
+pub use executor::{
+    Executor, HandlerFailure, IndexEnv, NativeHandlerFn, NativeIndexExecutor,
+    WasmIndexExecutor,
+};
 pub use fuel_indexer_database::IndexerDatabaseError;
 pub use fuel_indexer_lib::{
     config::IndexerConfig,
@@ -26,7 +33,8 @@ use openssl as _;
 pub mod prelude {
     pub use super::{
         Database, Executor, FtColumn, IndexEnv, IndexerConfig, IndexerError,
-        IndexerResult, IndexerService, Manifest, Module, WasmIndexExecutor,
+        IndexerResult, IndexerService, Manifest, Module, NativeHandlerFn,
+        NativeIndexExecutor, WasmIndexExecutor,
     };
     pub use async_std::sync::{Arc, Mutex};
     pub use fuel_indexer_lib::config::{DatabaseConfig, FuelClientConfig, WebApiConfig};
@@ -77,10 +85,14 @@ pub enum IndexerError {
     WasmExecutionInstantiationError,
     #[error("Native execution runtime error.")]
     NativeExecutionRuntimeError,
+    #[error("{0}")]
+    HandlerFailure(executor::HandlerFailure),
     #[error("Tokio time error: {0:?}")]
     Elapsed(#[from] tokio::time::error::Elapsed),
     #[error("Invalid schema: {0:?}")]
     SchemaVersionMismatch(String),
+    #[error("Entity {0} is immutable and cannot be {1}.")]
+    ImmutableEntityError(String, String),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
     // The following errors actually signify a successful completion.
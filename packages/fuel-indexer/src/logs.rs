@@ -0,0 +1,212 @@
+//! Logs of indexer handlers.
+//!
+//! Messages logged by handlers are emitted by the indexer service along with the
+//! UID of the indexer and the height of the block being processed, and buffered so
+//! that the executor can persist the last lines of each indexer after each batch of
+//! blocks.
+use chrono::Utc;
+use fuel_indexer_database::types::IndexerLog;
+use fuel_indexer_lib::WasmIndexerError;
+use fuel_indexer_types::ffi::{
+    LogRecord, LOG_LEVEL_DEBUG, LOG_LEVEL_ERROR, LOG_LEVEL_INFO, LOG_LEVEL_TRACE,
+    LOG_LEVEL_WARN,
+};
+use std::collections::VecDeque;
+use tracing::{debug, error, info, trace, warn};
+
+/// Lines logged by the handlers of an indexer since they were last taken.
+#[derive(Debug)]
+pub struct IndexerLogs {
+    /// UID of the indexer.
+    uid: String,
+
+    /// Height of the block being processed by the handlers.
+    block_height: u32,
+
+    /// Maximum number of lines to buffer. When 0, lines are not buffered.
+    max_lines: usize,
+
+    /// Lines logged since they were last taken.
+    lines: VecDeque<IndexerLog>,
+}
+
+impl IndexerLogs {
+    /// Create a new `IndexerLogs` buffering at most `max_lines` lines.
+    pub fn new(uid: String, max_lines: usize) -> Self {
+        Self {
+            uid,
+            block_height: 0,
+            max_lines,
+            lines: VecDeque::new(),
+        }
+    }
+
+    /// Set the height of the block being processed by the handlers.
+    pub fn set_block_height(&mut self, block_height: u32) {
+        self.block_height = block_height;
+    }
+
+    /// Height of the block being processed by the handlers.
+    pub fn block_height(&self) -> u32 {
+        self.block_height
+    }
+
+    /// Emit the given record, and buffer it.
+    pub fn record(&mut self, record: LogRecord) -> Result<(), WasmIndexerError> {
+        let LogRecord {
+            level,
+            target,
+            message,
+            fields,
+        } = record;
+
+        let uid = &self.uid;
+        let block_height = self.block_height;
+        let line = fields.iter().fold(message.clone(), |line, (key, value)| {
+            format!("{line} {key}={value}")
+        });
+
+        let level = match level {
+            LOG_LEVEL_ERROR => {
+                error!(indexer = %uid, block_height, module = %target, "{line}");
+                "ERROR"
+            }
+            LOG_LEVEL_WARN => {
+                warn!(indexer = %uid, block_height, module = %target, "{line}");
+                "WARN"
+            }
+            LOG_LEVEL_INFO => {
+                info!(indexer = %uid, block_height, module = %target, "{line}");
+                "INFO"
+            }
+            LOG_LEVEL_DEBUG => {
+                debug!(indexer = %uid, block_height, module = %target, "{line}");
+                "DEBUG"
+            }
+            LOG_LEVEL_TRACE => {
+                trace!(indexer = %uid, block_height, module = %target, "{line}");
+                "TRACE"
+            }
+            l => {
+                error!("Invalid log level: {l}");
+                return Err(WasmIndexerError::InvalidLogLevel);
+            }
+        };
+
+        if self.max_lines > 0 {
+            // Only the last `max_lines` lines are kept anyway.
+            if self.lines.len() == self.max_lines {
+                self.lines.pop_front();
+            }
+
+            self.lines.push_back(IndexerLog {
+                id: 0,
+                block_height,
+                level: level.to_string(),
+                target,
+                message,
+                fields,
+                created_at: Utc::now(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Take the lines logged since they were last taken.
+    pub fn take(&mut self) -> Vec<IndexerLog> {
+        self.lines.drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(level: u32, message: &str) -> LogRecord {
+        LogRecord {
+            level,
+            target: "handlers".to_string(),
+            message: message.to_string(),
+            fields: vec![],
+        }
+    }
+
+    fn messages(lines: &[IndexerLog]) -> Vec<&str> {
+        lines.iter().map(|line| line.message.as_str()).collect()
+    }
+
+    #[test]
+    fn test_only_the_last_lines_are_buffered() {
+        let mut logs = IndexerLogs::new("test.indexer".to_string(), 2);
+        for message in ["one", "two", "three"] {
+            logs.record(record(LOG_LEVEL_INFO, message)).unwrap();
+        }
+
+        assert_eq!(messages(&logs.take()), vec!["two", "three"]);
+    }
+
+    #[test]
+    fn test_lines_are_not_buffered_when_disabled() {
+        let mut logs = IndexerLogs::new("test.indexer".to_string(), 0);
+        logs.record(record(LOG_LEVEL_INFO, "one")).unwrap();
+
+        assert!(logs.take().is_empty());
+    }
+
+    #[test]
+    fn test_take_drains_the_buffer() {
+        let mut logs = IndexerLogs::new("test.indexer".to_string(), 10);
+        logs.set_block_height(7);
+        logs.record(LogRecord {
+            fields: vec![("owner".to_string(), "0x01".to_string())],
+            ..record(LOG_LEVEL_INFO, "one")
+        })
+        .unwrap();
+
+        let lines = logs.take();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].block_height, 7);
+        assert_eq!(lines[0].target, "handlers");
+        assert_eq!(
+            lines[0].fields,
+            vec![("owner".to_string(), "0x01".to_string())]
+        );
+        assert!(logs.take().is_empty());
+
+        logs.record(record(LOG_LEVEL_INFO, "two")).unwrap();
+        assert_eq!(messages(&logs.take()), vec!["two"]);
+    }
+
+    #[test]
+    fn test_levels_are_mapped_to_their_names() {
+        let mut logs = IndexerLogs::new("test.indexer".to_string(), 10);
+        for level in [
+            LOG_LEVEL_ERROR,
+            LOG_LEVEL_WARN,
+            LOG_LEVEL_INFO,
+            LOG_LEVEL_DEBUG,
+            LOG_LEVEL_TRACE,
+        ] {
+            logs.record(record(level, "line")).unwrap();
+        }
+
+        let levels = logs
+            .take()
+            .into_iter()
+            .map(|line| line.level)
+            .collect::<Vec<_>>();
+        assert_eq!(levels, vec!["ERROR", "WARN", "INFO", "DEBUG", "TRACE"]);
+    }
+
+    #[test]
+    fn test_invalid_levels_are_rejected() {
+        let mut logs = IndexerLogs::new("test.indexer".to_string(), 10);
+
+        assert_eq!(
+            logs.record(record(5, "line")),
+            Err(WasmIndexerError::InvalidLogLevel)
+        );
+        assert!(logs.take().is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+//! Host functions for native indexers.
+//!
+//! These are the native counterparts of the functions that `ffi` exports to WASM
+//! modules. Native indexer handlers run on a blocking thread of the indexer service,
+//! and the `NativeIndexExecutor` installs the environment of the indexer on that
+//! thread for the duration of the call.
+use crate::{database::Database, ffi::database_operation_failure, logs::IndexerLogs};
+use async_std::sync::{Arc, Mutex};
+use fuel_indexer_lib::WasmIndexerError;
+use fuel_indexer_schema::{join::RawQuery, FtColumn};
+use fuel_indexer_types::{ffi::LogRecord, scalar::ContractId};
+use std::{
+    cell::RefCell,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+use tracing::error;
+
+thread_local! {
+    /// Environment of the native indexer whose handlers run on this thread.
+    static NATIVE_ENV: RefCell<Option<NativeEnv>> = RefCell::new(None);
+}
+
+/// Native indexer runtime environment responsible for fetching/saving data to and from the database.
+#[derive(Clone)]
+pub(crate) struct NativeEnv {
+    /// Reference to the connected database.
+    pub db: Arc<Mutex<Database>>,
+
+    /// Kill switch for this indexer. When true, the indexer service indicated
+    /// that the indexer is being terminated.
+    pub kill_switch: Arc<AtomicBool>,
+
+    /// Logs of the indexer's handlers.
+    pub logs: Arc<std::sync::Mutex<IndexerLogs>>,
+
+    /// Name of the handler being run.
+    pub handler_name: Arc<std::sync::Mutex<Option<&'static str>>>,
+
+    /// Number of logs that the handlers dropped because they could not be decoded.
+    pub dropped_logs: Arc<AtomicU64>,
+}
+
+impl NativeEnv {
+    /// Run `f` with this environment installed on the current thread.
+    pub fn enter<T>(self, f: impl FnOnce() -> T) -> T {
+        /// Uninstalls the environment, even if `f` panics.
+        struct Guard;
+
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                NATIVE_ENV.with(|env| env.borrow_mut().take());
+            }
+        }
+
+        NATIVE_ENV.with(|env| *env.borrow_mut() = Some(self));
+        let _guard = Guard;
+
+        f()
+    }
+}
+
+/// Get the environment of the native indexer running on this thread.
+fn native_env() -> Result<NativeEnv, WasmIndexerError> {
+    let env = NATIVE_ENV
+        .with(|env| env.borrow().clone())
+        .ok_or(WasmIndexerError::UninitializedMemory)?;
+
+    if env.kill_switch.load(Ordering::SeqCst) {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of handler execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    Ok(env)
+}
+
+/// Log the given string.
+pub fn log_data(log_string: &str, log_level: u32) -> Result<(), WasmIndexerError> {
+    log_record(LogRecord {
+        level: log_level,
+        target: String::new(),
+        message: log_string.to_string(),
+        fields: Vec::new(),
+    })
+}
+
+/// Log the given record.
+pub fn log_record(record: LogRecord) -> Result<(), WasmIndexerError> {
+    let env = native_env()?;
+
+    let mut logs = env.logs.lock().expect("Failed to lock indexer logs.");
+    logs.record(record)
+}
+
+/// Set the height of the block being processed, which is attached to logs.
+pub fn set_block_height(height: u32) -> Result<(), WasmIndexerError> {
+    let env = native_env()?;
+
+    let mut logs = env.logs.lock().expect("Failed to lock indexer logs.");
+    logs.set_block_height(height);
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { env.db.lock().await.set_block_height(height) });
+
+    Ok(())
+}
+
+/// Set the name of the handler being run, which is reported when it fails. An
+/// empty name means that no handler is being run.
+pub fn set_handler_name(name: &'static str) {
+    NATIVE_ENV.with(|env| {
+        if let Some(env) = env.borrow().as_ref() {
+            *env.handler_name
+                .lock()
+                .expect("Failed to lock handler name.") =
+                Some(name).filter(|name| !name.is_empty());
+        }
+    })
+}
+
+/// Count logs that the indexer dropped because they could not be decoded.
+pub fn record_dropped_logs(count: u64) {
+    NATIVE_ENV.with(|env| {
+        if let Some(env) = env.borrow().as_ref() {
+            env.dropped_logs.fetch_add(count, Ordering::SeqCst);
+        }
+    })
+}
+
+/// Fetch the object of the given type with the given ID.
+pub fn get_object(type_id: i64, id: String) -> Result<Option<Vec<u8>>, WasmIndexerError> {
+    let env = native_env()?;
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { env.db.lock().await.get_object(type_id, id).await })
+        .map_err(|e| {
+            error!("Failed to get_object: {e}");
+            WasmIndexerError::DatabaseError
+        })
+}
+
+/// Get multiple objects from the database that satisfy the given constraints.
+pub fn find_many(
+    type_id: i64,
+    constraints: String,
+) -> Result<Vec<Vec<u8>>, WasmIndexerError> {
+    let env = native_env()?;
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { env.db.lock().await.find_many(type_id, constraints).await })
+        .map_err(|e| {
+            error!("Failed to find_many: {e}");
+            WasmIndexerError::DatabaseError
+        })
+}
+
+/// Delete multiple objects from the database that satisfy the given constraints.
+pub fn delete_many(type_id: i64, constraints: String) -> Result<usize, WasmIndexerError> {
+    let env = native_env()?;
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { env.db.lock().await.delete_many(type_id, constraints).await })
+        .map_err(|e| {
+            error!("Failed to delete_many: {e}");
+            database_operation_failure(e)
+        })
+}
+
+/// Put the object with the given serialized columns into the database.
+pub fn put_object(type_id: i64, bytes: Vec<u8>) -> Result<(), WasmIndexerError> {
+    let env = native_env()?;
+
+    let columns: Vec<FtColumn> = match bincode::deserialize(&bytes) {
+        Ok(columns) => columns,
+        Err(e) => {
+            error!("Failed to deserialize Vec<FtColumn> for put_object: {e:?}",);
+            return Err(WasmIndexerError::DeserializationError);
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    let result = rt.block_on(async {
+        env.db
+            .lock()
+            .await
+            .put_object(type_id, columns, bytes)
+            .await
+    });
+
+    if let Err(e) = result {
+        error!("Failed to put_object: {e}");
+        return Err(database_operation_failure(e));
+    };
+
+    Ok(())
+}
+
+/// Execute the given serialized queries.
+pub fn put_many_to_many_record(bytes: Vec<u8>) -> Result<(), WasmIndexerError> {
+    let env = native_env()?;
+
+    let queries: Vec<String> = match bincode::deserialize::<Vec<RawQuery>>(&bytes) {
+        Ok(queries) => queries.iter().map(|q| q.to_string()).collect(),
+        Err(e) => {
+            error!("Failed to deserialize queries: {e:?}");
+            return Err(WasmIndexerError::DeserializationError);
+        }
+    };
+
+    let rt = tokio::runtime::Handle::current();
+    let result =
+        rt.block_on(async { env.db.lock().await.put_many_to_many_record(queries).await });
+
+    if let Err(e) = result {
+        error!("Failed to put_many_to_many_record: {e:?}");
+        return Err(database_operation_failure(e));
+    }
+
+    Ok(())
+}
+
+/// Persist the given contract ID as watched by the indexer.
+pub fn watch_contract(contract_id: &ContractId) -> Result<(), WasmIndexerError> {
+    let env = native_env()?;
+
+    let contract_id = format!("{contract_id:x}");
+
+    let rt = tokio::runtime::Handle::current();
+    let result =
+        rt.block_on(async { env.db.lock().await.watch_contract(contract_id).await });
+
+    if let Err(e) = result {
+        error!("Failed to watch_contract: {e}");
+        return Err(WasmIndexerError::DatabaseError);
+    }
+
+    Ok(())
+}
+
+/// Get the contract IDs watched by the indexer.
+pub fn get_watched_contracts() -> Result<Vec<String>, WasmIndexerError> {
+    let env = native_env()?;
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { env.db.lock().await.watched_contracts().await })
+        .map_err(|e| {
+            error!("Failed to get_watched_contracts: {e}");
+            WasmIndexerError::DatabaseError
+        })
+}
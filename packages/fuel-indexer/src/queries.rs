@@ -5,7 +5,7 @@ use fuel_core_client::client::{
         block::{BlockByHeightArgs, Consensus, Header},
         schema,
         tx::OpaqueTransaction,
-        BlockId, ConnectionArgs, PageInfo,
+        BlockId, ConnectionArgs, ContractId, PageInfo, U32,
     },
     FuelClient,
 };
@@ -55,6 +55,122 @@ pub struct FullBlock {
     pub transactions: Vec<OpaqueTransaction>,
 }
 
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "Query",
+    variables = "ConnectionArgs"
+)]
+pub struct TransactionContractsQuery {
+    #[arguments(after: $after, before: $before, first: $first, last: $last)]
+    pub transactions: TransactionContractsConnection,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "TransactionConnection"
+)]
+pub struct TransactionContractsConnection {
+    pub edges: Vec<TransactionContractsEdge>,
+    pub page_info: PageInfo,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "TransactionEdge")]
+pub struct TransactionContractsEdge {
+    pub cursor: String,
+    pub node: TransactionContracts,
+}
+
+/// A transaction, with only the contracts among its inputs, and the height of the
+/// block in which it was included.
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Transaction")]
+pub struct TransactionContracts {
+    pub input_contracts: Option<Vec<ContractIdFragment>>,
+    pub status: Option<TransactionHeightStatus>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Contract")]
+pub struct ContractIdFragment {
+    pub id: ContractId,
+}
+
+#[derive(cynic::InlineFragments, Debug)]
+#[cynic(
+    schema_path = "./assets/schema.sdl",
+    graphql_type = "TransactionStatus"
+)]
+pub enum TransactionHeightStatus {
+    SuccessStatus(SuccessStatusHeight),
+    FailureStatus(FailureStatusHeight),
+    #[cynic(fallback)]
+    Unknown,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "SuccessStatus")]
+pub struct SuccessStatusHeight {
+    pub block: BlockHeight,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "FailureStatus")]
+pub struct FailureStatusHeight {
+    pub block: BlockHeight,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Block")]
+pub struct BlockHeight {
+    pub header: HeaderHeight,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "./assets/schema.sdl", graphql_type = "Header")]
+pub struct HeaderHeight {
+    pub height: U32,
+}
+
+impl TransactionContracts {
+    /// Returns the height of the block in which this transaction was included, if any.
+    pub fn block_height(&self) -> Option<u32> {
+        match &self.status {
+            Some(TransactionHeightStatus::SuccessStatus(s)) => {
+                Some(s.block.header.height.clone().into())
+            }
+            Some(TransactionHeightStatus::FailureStatus(s)) => {
+                Some(s.block.header.height.clone().into())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the IDs of the contracts among the inputs of this transaction.
+    pub fn contract_ids(&self) -> Vec<fuel_vm::fuel_types::ContractId> {
+        self.input_contracts
+            .iter()
+            .flatten()
+            .map(|contract| contract.id.clone().into())
+            .collect()
+    }
+}
+
+impl From<TransactionContractsConnection>
+    for PaginatedResult<TransactionContracts, String>
+{
+    fn from(conn: TransactionContractsConnection) -> Self {
+        PaginatedResult {
+            cursor: conn.page_info.end_cursor,
+            has_next_page: conn.page_info.has_next_page,
+            has_previous_page: conn.page_info.has_previous_page,
+            results: conn.edges.into_iter().map(|e| e.node).collect(),
+        }
+    }
+}
+
 impl FullBlock {
     /// Returns the block producer public key, if any.
     pub fn block_producer(&self) -> Option<fuel_crypto::PublicKey> {
@@ -88,6 +204,16 @@ pub trait ClientExt {
         &self,
         request: PaginationRequest<String>,
     ) -> std::io::Result<PaginatedResult<FullBlock, String>>;
+
+    async fn full_block_by_height(
+        &self,
+        height: u32,
+    ) -> std::io::Result<Option<FullBlock>>;
+
+    async fn transaction_contracts(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> std::io::Result<PaginatedResult<TransactionContracts, String>>;
 }
 
 #[async_trait::async_trait]
@@ -100,4 +226,23 @@ impl ClientExt for FuelClient {
         let blocks = self.query(query).await?.blocks.into();
         Ok(blocks)
     }
+    async fn full_block_by_height(
+        &self,
+        height: u32,
+    ) -> std::io::Result<Option<FullBlock>> {
+        let query = FullBlockByHeightQuery::build(BlockByHeightArgs {
+            height: Some(height.into()),
+        });
+        let block = self.query(query).await?.block;
+        Ok(block)
+    }
+
+    async fn transaction_contracts(
+        &self,
+        request: PaginationRequest<String>,
+    ) -> std::io::Result<PaginatedResult<TransactionContracts, String>> {
+        let query = TransactionContractsQuery::build(request.into());
+        let transactions = self.query(query).await?.transactions.into();
+        Ok(transactions)
+    }
 }
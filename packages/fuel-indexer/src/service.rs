@@ -1,6 +1,9 @@
 use crate::{
-    executor::WasmIndexExecutor, Executor, IndexerConfig, IndexerError, IndexerResult,
-    Manifest,
+    cache::BlockCache,
+    executor::{
+        retrieve_block_from_node, NativeHandlerFn, NativeIndexExecutor, WasmIndexExecutor,
+    },
+    Executor, IndexerConfig, IndexerError, IndexerResult, Manifest, ManifestError,
 };
 use anyhow::Context;
 use async_std::sync::Arc;
@@ -10,7 +13,7 @@ use fuel_indexer_database::{
     types::{IndexerAssetType, IndexerStatus},
     IndexerConnection, IndexerConnectionPool,
 };
-use fuel_indexer_lib::utils::ServiceRequest;
+use fuel_indexer_lib::utils::{DebugReport, ServiceRequest};
 use fuel_indexer_schema::db::manager::SchemaManager;
 use std::collections::HashMap;
 use std::marker::Send;
@@ -37,6 +40,9 @@ pub struct IndexerService {
 
     /// Killers used to stop the spawned indexers.
     killers: HashMap<String, Arc<AtomicBool>>,
+
+    /// Block cache shared by the spawned indexers, if enabled.
+    block_cache: Option<Arc<BlockCache>>,
 }
 
 impl IndexerService {
@@ -48,6 +54,17 @@ impl IndexerService {
     ) -> IndexerResult<IndexerService> {
         let manager = SchemaManager::new(pool.clone());
 
+        let block_cache = match config.block_cache_size {
+            Some(size) => {
+                info!("Indexers will share a {size} MB block cache.");
+                Some(Arc::new(BlockCache::new(
+                    &config.fuel_node.to_string(),
+                    size,
+                )?))
+            }
+            None => None,
+        };
+
         Ok(IndexerService {
             config,
             pool,
@@ -55,6 +72,7 @@ impl IndexerService {
             killers: HashMap::default(),
             tasks: tokio::task::JoinSet::new(),
             rx,
+            block_cache,
         })
     }
 
@@ -64,6 +82,98 @@ impl IndexerService {
         mut manifest: Manifest,
         remove_data: bool,
     ) -> IndexerResult<()> {
+        let wasm_bytes = match manifest.module() {
+            crate::Module::Wasm(ref module) => {
+                let mut bytes = Vec::<u8>::new();
+                let mut file = File::open(module).await?;
+                file.read_to_end(&mut bytes).await?;
+                bytes
+            }
+            crate::Module::Native => return Err(ManifestError::NativeModuleError.into()),
+        };
+
+        let (schema_version, schema_bytes) =
+            self.register_indexer(&mut manifest, remove_data).await?;
+
+        let executor = WasmIndexExecutor::create(
+            &self.config,
+            &manifest,
+            self.pool.clone(),
+            schema_version,
+            wasm_bytes.clone(),
+        )
+        .await?;
+
+        self.register_indexer_assets(
+            &manifest,
+            vec![
+                (IndexerAssetType::Wasm, wasm_bytes),
+                (IndexerAssetType::Manifest, manifest.clone().into()),
+                (IndexerAssetType::Schema, schema_bytes),
+            ],
+        )
+        .await?;
+
+        self.start_executor(executor).await?;
+
+        Ok(())
+    }
+
+    /// Register a new native indexer to the `IndexerService`, from a `Manifest` and
+    /// the handler function generated for it by the `indexer` macro.
+    ///
+    /// Native indexers are linked into the indexer service binary, so only their
+    /// manifest and schema are registered as assets, and they are not restarted
+    /// from the registry.
+    pub async fn register_native_indexer(
+        &mut self,
+        mut manifest: Manifest,
+        handle_events_fn: NativeHandlerFn,
+        remove_data: bool,
+    ) -> IndexerResult<()> {
+        if !matches!(manifest.module(), crate::Module::Native) {
+            return Err(anyhow::anyhow!(
+                "Indexer({}) does not use a native module.",
+                manifest.uid()
+            )
+            .into());
+        }
+
+        let (schema_version, schema_bytes) =
+            self.register_indexer(&mut manifest, remove_data).await?;
+
+        let executor = NativeIndexExecutor::create(
+            &self.config,
+            &manifest,
+            self.pool.clone(),
+            schema_version,
+            handle_events_fn,
+        )
+        .await?;
+
+        self.register_indexer_assets(
+            &manifest,
+            vec![
+                (IndexerAssetType::Manifest, manifest.clone().into()),
+                (IndexerAssetType::Schema, schema_bytes),
+            ],
+        )
+        .await?;
+
+        self.start_executor(executor).await?;
+
+        Ok(())
+    }
+
+    /// Register the indexer and its schema in the database, replacing any existing
+    /// version of the indexer, and set the start block of its manifest.
+    ///
+    /// Returns the version and the bytes of the schema.
+    async fn register_indexer(
+        &mut self,
+        manifest: &mut Manifest,
+        remove_data: bool,
+    ) -> IndexerResult<(String, Vec<u8>)> {
         if let Some(killer) = self.killers.get(&manifest.uid()) {
             killer.store(true, std::sync::atomic::Ordering::SeqCst);
         }
@@ -124,32 +234,19 @@ impl IndexerService {
             )
             .await?;
 
-        let start_block = get_start_block(&mut conn, &manifest).await?;
+        let start_block = get_start_block(&mut conn, manifest).await?;
         manifest.set_start_block(start_block);
 
-        let wasm_bytes = match manifest.module() {
-            crate::Module::Wasm(ref module) => {
-                let mut bytes = Vec::<u8>::new();
-                let mut file = File::open(module).await?;
-                file.read_to_end(&mut bytes).await?;
-                bytes
-            }
-        };
-
-        let executor = WasmIndexExecutor::create(
-            &self.config,
-            &manifest,
-            self.pool.clone(),
-            schema_version,
-            wasm_bytes.clone(),
-        )
-        .await?;
+        Ok((schema_version, schema_bytes))
+    }
 
-        let mut items = vec![
-            (IndexerAssetType::Wasm, wasm_bytes),
-            (IndexerAssetType::Manifest, manifest.clone().into()),
-            (IndexerAssetType::Schema, schema_bytes),
-        ];
+    /// Register the given assets of the indexer in the database.
+    async fn register_indexer_assets(
+        &mut self,
+        manifest: &Manifest,
+        mut items: Vec<(IndexerAssetType, Vec<u8>)>,
+    ) -> IndexerResult<()> {
+        let mut conn = self.pool.acquire().await?;
 
         while let Some((asset_type, bytes)) = items.pop() {
             info!(
@@ -175,8 +272,6 @@ impl IndexerService {
             manifest.identifier()
         );
 
-        self.start_executor(executor).await?;
-
         Ok(())
     }
 
@@ -185,8 +280,18 @@ impl IndexerService {
         let mut conn = self.pool.acquire().await?;
         let indices = queries::all_registered_indexers(&mut conn).await?;
         for index in indices {
+            let manifest =
+                queries::indexer_asset(&mut conn, &index.id, IndexerAssetType::Manifest)
+                    .await?;
+            let mut manifest = Manifest::try_from(&manifest.bytes)?;
+
+            // Native indexers are registered by the service binary they are linked into.
+            if let crate::Module::Native = manifest.module() {
+                info!("Skipping native Indexer({}) from registry.", manifest.uid());
+                continue;
+            }
+
             let assets = queries::indexer_assets(&mut conn, &index.id).await?;
-            let mut manifest = Manifest::try_from(&assets.manifest.bytes)?;
 
             let start_block = get_start_block(&mut conn, &manifest).await.unwrap_or(1);
             manifest.set_start_block(start_block);
@@ -294,6 +399,30 @@ impl IndexerService {
                                 );
                             }
                         }
+                        ServiceRequest::Debug(request) => {
+                            let config = self.config.clone();
+                            let pool = self.pool.clone();
+
+                            // Debug runs don't hold up the service loop.
+                            tokio::spawn(async move {
+                                let report = debug_indexer(
+                                    &config,
+                                    pool,
+                                    &request.namespace,
+                                    &request.identifier,
+                                    request.block_height,
+                                )
+                                .await
+                                .map_err(|e| e.to_string());
+
+                                if request.response.send(report).is_err() {
+                                    warn!(
+                                        "Debug Indexer({}.{}): The report could not be sent back.",
+                                        request.namespace, request.identifier
+                                    );
+                                }
+                            });
+                        }
                     }
                 }
             }
@@ -316,8 +445,12 @@ impl IndexerService {
         self.killers
             .insert(uid.clone(), executor.kill_switch().clone());
 
-        let task =
-            crate::executor::run_executor(&self.config, self.pool.clone(), executor)?;
+        let task = crate::executor::run_executor(
+            &self.config,
+            self.pool.clone(),
+            executor,
+            self.block_cache.clone(),
+        )?;
         self.tasks.spawn(async move {
             queries::set_indexer_status(
                 &mut conn,
@@ -340,6 +473,14 @@ impl IndexerService {
                         info!("Indexer({namespace}.{identifier}) terminated: {e}");
                         status = IndexerStatus::stopped(e.to_string())
                     }
+                    IndexerError::HandlerFailure(failure) => {
+                        error!("Indexer({namespace}.{identifier}) terminated with an error: {e}");
+                        status = IndexerStatus::handler_error(
+                            e.to_string(),
+                            failure.block_height,
+                            failure.handler.clone(),
+                        )
+                    }
                     _ => {
                         error!("Indexer({namespace}.{identifier}) terminated with an error: {e}");
                         status = IndexerStatus::error(e.to_string())
@@ -363,6 +504,58 @@ impl IndexerService {
     }
 }
 
+/// Run the registered indexer against the block at the given height, in a
+/// transaction that is always reverted, and report what its handlers did.
+async fn debug_indexer(
+    config: &IndexerConfig,
+    pool: IndexerConnectionPool,
+    namespace: &str,
+    identifier: &str,
+    block_height: u32,
+) -> IndexerResult<DebugReport> {
+    let mut conn = pool.acquire().await?;
+    let id = queries::get_indexer_id(&mut conn, namespace, identifier).await?;
+    let assets = queries::indexer_assets(&mut conn, &id).await?;
+    let manifest = Manifest::try_from(&assets.manifest.bytes)?;
+
+    if let crate::Module::Native = manifest.module() {
+        return Err(anyhow::anyhow!(
+            "Indexer({}) is a native indexer, which can't be debugged.",
+            manifest.uid()
+        )
+        .into());
+    }
+
+    info!(
+        "Debugging Indexer({}) at block #{block_height}.",
+        manifest.uid()
+    );
+
+    let fuel_node_addr = manifest
+        .fuel_client()
+        .map(|x| x.to_string())
+        .unwrap_or(config.fuel_node.to_string());
+    let block =
+        retrieve_block_from_node(&fuel_node_addr, block_height, &manifest.uid()).await?;
+
+    // Every line logged during the run is part of the report.
+    let config = IndexerConfig {
+        indexer_log_lines: usize::MAX,
+        ..config.clone()
+    };
+
+    let mut executor = WasmIndexExecutor::new(
+        &config,
+        &manifest,
+        assets.wasm.bytes,
+        pool,
+        assets.schema.digest,
+    )
+    .await?;
+
+    executor.debug_block(block).await
+}
+
 /// Determine the starting block for this indexer.
 pub async fn get_start_block(
     conn: &mut IndexerConnection,
@@ -1,8 +1,9 @@
 #[allow(unused)]
 pub(crate) use crate::commands::{
     auth::Command as AuthCommand, build::Command as BuildCommand,
-    check::Command as CheckCommand, deploy::Command as DeployCommand,
-    kill::Command as KillCommand, new::Command as NewCommand,
+    check::Command as CheckCommand, debug::Command as DebugCommand,
+    deploy::Command as DeployCommand, kill::Command as KillCommand,
+    logs::Command as LogsCommand, new::Command as NewCommand,
     remove::Command as RemoveCommand, start::Command as StartCommand,
     status::Command as StatusCommand,
 };
@@ -26,8 +27,10 @@ pub enum ForcIndex {
     Auth(AuthCommand),
     Build(BuildCommand),
     Check(CheckCommand),
+    Debug(DebugCommand),
     Deploy(DeployCommand),
     Kill(KillCommand),
+    Logs(LogsCommand),
     New(NewCommand),
     Postgres(ForcPostgresOpt),
     Remove(RemoveCommand),
@@ -47,6 +50,7 @@ pub async fn run_cli() -> Result<(), anyhow::Error> {
         ForcIndex::Deploy(command) => crate::commands::deploy::exec(command).await,
         ForcIndex::Start(command) => crate::commands::start::exec(command).await,
         ForcIndex::Check(_command) => crate::commands::check::exec().await,
+        ForcIndex::Debug(command) => crate::commands::debug::exec(command).await,
         ForcIndex::Remove(command) => crate::commands::remove::exec(command).await,
         ForcIndex::Build(command) => crate::commands::build::exec(command),
         ForcIndex::Auth(command) => crate::commands::auth::exec(command).await,
@@ -57,6 +61,7 @@ pub async fn run_cli() -> Result<(), anyhow::Error> {
             ForcPostgres::Start(command) => pg_commands::start::exec(command).await,
         },
         ForcIndex::Kill(command) => crate::commands::kill::exec(command),
+        ForcIndex::Logs(command) => crate::commands::logs::exec(command).await,
         ForcIndex::Status(command) => crate::commands::status::exec(command).await,
     }
 }
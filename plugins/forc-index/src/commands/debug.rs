@@ -0,0 +1,37 @@
+use crate::{defaults, ops::forc_index_debug};
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Run a registered indexer against a single block, without persisting anything.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Height of the block to run the indexer against.
+    #[clap(long, help = "Height of the block to run the indexer against.")]
+    pub block: u32,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    forc_index_debug::init(command).await?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::{defaults, ops::forc_index_logs};
+use anyhow::Result;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Print the logs of a registered indexer.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// URL at which indexer is deployed.
+    #[clap(long, default_value = defaults::INDEXER_SERVICE_HOST, help = "URL at which indexer is deployed.")]
+    pub url: String,
+
+    /// Path to the manifest of the indexer project.
+    #[clap(short, long, help = "Path to the manifest of the indexer project.")]
+    pub manifest: Option<String>,
+
+    /// Path of indexer project.
+    #[clap(short, long, help = "Path to the indexer project.")]
+    pub path: Option<PathBuf>,
+
+    /// Authentication header value.
+    #[clap(long, help = "Authentication header value.")]
+    pub auth: Option<String>,
+
+    /// Number of log lines to print.
+    #[clap(short = 'n', long, default_value = defaults::INDEXER_LOG_LINES, help = "Number of log lines to print.")]
+    pub lines: i64,
+
+    /// Keep printing new log lines as they are logged.
+    #[clap(short, long, help = "Keep printing new log lines as they are logged.")]
+    pub follow: bool,
+
+    /// Enable verbose output.
+    #[clap(short, long, help = "Enable verbose output.")]
+    pub verbose: bool,
+}
+
+pub async fn exec(command: Command) -> Result<()> {
+    forc_index_logs::init(command).await?;
+    Ok(())
+}
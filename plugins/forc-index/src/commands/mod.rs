@@ -1,8 +1,10 @@
 pub mod auth;
 pub mod build;
 pub mod check;
+pub mod debug;
 pub mod deploy;
 pub mod kill;
+pub mod logs;
 pub mod new;
 pub mod remove;
 pub mod start;
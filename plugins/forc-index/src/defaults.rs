@@ -7,6 +7,8 @@ pub const INDEXER_LIB_FILENAME: &str = "lib.rs";
 pub const CARGO_CONFIG_DIR_NAME: &str = ".cargo";
 pub const CARGO_CONFIG_FILENAME: &str = "config";
 pub const INDEXER_SERVICE_HOST: &str = "http://127.0.0.1:29987";
+pub const INDEXER_LOG_LINES: &str = "100";
+pub const LOGS_POLL_INTERVAL_SECS: u64 = 1;
 pub const WEB_API_PORT: &str = defaults::WEB_API_PORT;
 pub const WASM_TARGET: &str = "wasm32-unknown-unknown";
 pub const MESSAGE_PADDING: usize = 55;
@@ -56,6 +56,13 @@ pub fn init(command: BuildCommand) -> anyhow::Result<()> {
     let indexer_manifest_path = root_dir.join(manifest);
     let mut manifest = Manifest::from_file(&indexer_manifest_path)?;
 
+    if let Module::Native = manifest.module() {
+        anyhow::bail!(
+            "❌ Indexer({}) uses a native module. Native indexers are compiled into the indexer service binary that runs them.",
+            manifest.uid()
+        );
+    }
+
     let manifest_schema_file = {
         let workspace_root: std::path::PathBuf =
             crate::utils::cargo_workspace_root_dir(path.as_path()).unwrap();
@@ -0,0 +1,153 @@
+use crate::{cli::DebugCommand, utils::project_dir_info};
+use colorful::{Color, Colorful};
+use fuel_indexer_lib::{
+    manifest::Manifest,
+    utils::{DebugReport, EntityChange, EntityChangeKind},
+};
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION},
+    Client, StatusCode,
+};
+use serde_json::{to_string_pretty, value::Value, Map};
+use tracing::{error, info};
+
+pub async fn init(command: DebugCommand) -> anyhow::Result<()> {
+    let DebugCommand {
+        path,
+        manifest,
+        url,
+        auth,
+        block,
+        verbose,
+    } = command;
+
+    let (_root_dir, manifest_path, _indexer_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/index/{}/{}/debug",
+        manifest.namespace(),
+        manifest.identifier()
+    );
+
+    let mut headers = HeaderMap::new();
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    if verbose {
+        info!(
+            "\n🔎 Debugging indexer '{}.{}' at block #{block} via {target}\n",
+            manifest.namespace(),
+            manifest.identifier()
+        );
+    }
+
+    let res = Client::new()
+        .post(&target)
+        .headers(headers)
+        .query(&[("block", block.to_string())])
+        .send()
+        .await
+        .expect("Failed to debug indexer.");
+
+    let status = res.status();
+    if status != StatusCode::OK {
+        if verbose {
+            error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+        } else {
+            error!("\n❌ Failed to debug indexer.");
+        }
+
+        let res_json = res
+            .json::<Map<String, Value>>()
+            .await
+            .expect("Failed to read JSON response.");
+        info!("\n{}", to_string_pretty(&res_json)?);
+        return Ok(());
+    }
+
+    let report: DebugReport = res.json().await.expect("Failed to read JSON response.");
+
+    print_report(&report);
+
+    Ok(())
+}
+
+fn print_report(report: &DebugReport) {
+    println!(
+        "{}",
+        format!("Block #{}", report.block_height)
+            .color(Color::Blue)
+            .bold()
+    );
+
+    println!("\nEntity changes ({}):", report.changes.len());
+    for change in report.changes.iter() {
+        print_change(change);
+    }
+
+    println!("\nLogs ({}):", report.logs.len());
+    for line in report.logs.iter() {
+        println!("  {line}");
+    }
+
+    match report.metering_points_used {
+        Some(points) => println!("\nMetering points used: {points}"),
+        None => println!("\nMetering points used: metering is disabled"),
+    }
+
+    match &report.error {
+        Some(e) => println!("\n{}\n{e}", "Handler failed:".color(Color::Red).bold()),
+        None => println!("\n{}", "Handlers succeeded.".color(Color::Green)),
+    }
+
+    println!("\nNothing was persisted: the transaction was rolled back.");
+}
+
+fn print_change(change: &EntityChange) {
+    let EntityChange {
+        entity,
+        id,
+        kind,
+        before,
+        after,
+    } = change;
+
+    match kind {
+        EntityChangeKind::Insert => {
+            println!("  {} {entity} {id}", "+ insert".color(Color::Green));
+            print_columns(after, "+");
+        }
+        EntityChangeKind::Update => {
+            println!("  {} {entity} {id}", "~ update".color(Color::Yellow));
+            let before = before.as_ref().and_then(Value::as_object);
+            let after = after.as_ref().and_then(Value::as_object);
+            if let (Some(before), Some(after)) = (before, after) {
+                for (column, value) in after.iter() {
+                    match before.get(column) {
+                        Some(previous) if previous == value => (),
+                        Some(previous) => {
+                            println!("      {column}: {previous} -> {value}")
+                        }
+                        None => println!("      {column}: {value}"),
+                    }
+                }
+            }
+        }
+        EntityChangeKind::Delete => {
+            println!("  {} {entity} {id}", "- delete".color(Color::Red));
+            print_columns(before, "-");
+        }
+    }
+}
+
+fn print_columns(columns: &Option<Value>, sign: &str) {
+    if let Some(columns) = columns.as_ref().and_then(Value::as_object) {
+        for (column, value) in columns.iter() {
+            println!("    {sign} {column}: {value}");
+        }
+    }
+}
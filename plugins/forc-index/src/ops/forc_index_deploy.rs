@@ -3,7 +3,7 @@ use crate::{
     commands::{build, remove},
     utils::{file_part, project_dir_info},
 };
-use fuel_indexer_lib::manifest::Manifest;
+use fuel_indexer_lib::manifest::{Manifest, Module};
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::{
     header::{HeaderMap, AUTHORIZATION, CONNECTION},
@@ -59,6 +59,13 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
 
     let manifest = Manifest::from_file(&manifest_path)?;
 
+    if let Module::Native = manifest.module() {
+        anyhow::bail!(
+            "❌ Indexer({}) uses a native module, and can't be deployed to an indexer service.",
+            manifest.uid()
+        );
+    }
+
     let current_dir = std::env::current_dir()?;
 
     let path = path.unwrap_or(current_dir);
@@ -71,7 +78,13 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
         .unwrap()
         .to_string();
 
-    let manifest_module_file = workspace_root.join(manifest.module());
+    let manifest_module_file = match manifest.module().path() {
+        Some(module_path) => workspace_root.join(module_path),
+        None => anyhow::bail!(
+            "❌ Indexer({}) does not specify a WASM module to deploy.",
+            manifest.uid()
+        ),
+    };
 
     let form = Form::new()
         .text("replace_indexer", replace_indexer.to_string())
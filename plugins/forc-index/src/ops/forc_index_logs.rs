@@ -0,0 +1,102 @@
+use crate::{cli::LogsCommand, defaults, utils::project_dir_info};
+use fuel_indexer_database_types::IndexerLog;
+use fuel_indexer_lib::manifest::Manifest;
+use reqwest::{
+    header::{HeaderMap, AUTHORIZATION},
+    Client, StatusCode,
+};
+use serde_json::{to_string_pretty, value::Value, Map};
+use std::time::Duration;
+use tracing::{error, info};
+
+pub async fn init(command: LogsCommand) -> anyhow::Result<()> {
+    let LogsCommand {
+        path,
+        manifest,
+        url,
+        auth,
+        lines,
+        follow,
+        verbose,
+    } = command;
+
+    let (_root_dir, manifest_path, _indexer_name) =
+        project_dir_info(path.as_ref(), manifest.as_ref())?;
+
+    let manifest: Manifest = Manifest::from_file(manifest_path.as_path())?;
+
+    let target = format!(
+        "{url}/api/logs/{}/{}",
+        manifest.namespace(),
+        manifest.identifier()
+    );
+
+    let mut headers = HeaderMap::new();
+    if let Some(auth) = auth {
+        headers.insert(AUTHORIZATION, auth.parse()?);
+    }
+
+    if verbose {
+        info!(
+            "\n📜 Fetching logs of indexer '{}.{}' at {target}\n",
+            manifest.namespace(),
+            manifest.identifier()
+        );
+    }
+
+    let client = Client::new();
+    let mut since = None;
+
+    loop {
+        let mut query = vec![("limit", lines.to_string())];
+        if let Some(since) = since {
+            query.push(("since", since.to_string()));
+        }
+
+        let res = client
+            .get(&target)
+            .headers(headers.clone())
+            .query(&query)
+            .send()
+            .await
+            .expect("Failed to fetch indexer logs.");
+
+        let status = res.status();
+        if status != StatusCode::OK {
+            if verbose {
+                error!("\n❌ {target} returned a non-200 response code: {status:?}",);
+            } else {
+                error!("\n❌ Failed to fetch indexer logs.");
+            }
+
+            let res_json = res
+                .json::<Map<String, Value>>()
+                .await
+                .expect("Failed to read JSON response.");
+            info!("\n{}", to_string_pretty(&res_json)?);
+            break;
+        }
+
+        let logs: Vec<IndexerLog> =
+            res.json().await.expect("Failed to read JSON response.");
+
+        for log in logs.iter() {
+            println!("{log}");
+        }
+
+        if !follow {
+            break;
+        }
+
+        if let Some(last) = logs.last() {
+            since = Some(last.id);
+        } else if since.is_none() {
+            // Only print lines logged from now on.
+            since = Some(0);
+        }
+
+        tokio::time::sleep(Duration::from_secs(defaults::LOGS_POLL_INTERVAL_SECS)).await;
+    }
+
+    Ok(())
+}
@@ -42,7 +42,10 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         replace_indexer,
         remove_data,
         accept_sql_queries,
+        stringify_large_integers,
         block_page_size,
+        block_cache_size,
+        indexer_log_lines,
         allow_non_sequential_blocks,
         disable_toolchain_version_check,
         client_request_delay,
@@ -84,6 +87,8 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             .arg(OsStr::new(&metering_points.to_string()));
         cmd.arg("--block-page-size")
             .arg(OsStr::new(&block_page_size.to_string()));
+        cmd.arg("--indexer-log-lines")
+            .arg(OsStr::new(&indexer_log_lines.to_string()));
         cmd.arg("--max-db-connections")
             .arg(OsStr::new(&max_db_connections.to_string()));
 
@@ -96,6 +101,7 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             ("--replace-indexer", replace_indexer),
             ("--remove-data", remove_data),
             ("--accept-sql-queries", accept_sql_queries),
+            ("--stringify-large-integers", stringify_large_integers),
             ("--run-migrations", run_migrations),
             ("--metrics", metrics),
             ("--auth-enabled", auth_enabled),
@@ -124,6 +130,10 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
                 client_request_delay.map(|x| x.to_string()),
             ),
             ("--network", network),
+            (
+                "--block-cache-size",
+                block_cache_size.map(|x| x.to_string()),
+            ),
         ];
         for (opt, value) in options.iter() {
             if let Some(value) = value {
@@ -164,6 +164,9 @@ fn print_indexers(indexers: Vec<(RegisteredIndexer, IndexerStatus)>) {
                 .map(|x| format!("{ng2}  {ig2}      {x}"))
                 .collect::<Vec<String>>()
                 .join("\n");
+            let block_height = status.block_height;
+            let handler = status.handler.clone();
+            let pruned = status.pruned_block_height.zip(status.pruned_rows);
             let status = if status.status_kind == IndexerStatusKind::Error {
                 status.status_kind.to_string().color(Color::Red)
             } else {
@@ -196,6 +199,18 @@ fn print_indexers(indexers: Vec<(RegisteredIndexer, IndexerStatus)>) {
                 println!("{}  {}  • pubkey: {:?}", ng2, ig2, indexer.pubkey);
             }
             println!("{}  {}  • status: {}", ng2, ig2, status);
+            if let Some(handler) = handler {
+                println!("{}  {}  • failed handler: {}", ng2, ig2, handler);
+            }
+            if let Some(block_height) = block_height {
+                println!("{}  {}  • failed at block: {}", ng2, ig2, block_height);
+            }
+            if let Some((pruned_block_height, pruned_rows)) = pruned {
+                println!(
+                    "{}  {}  • last pruned at block: {} ({} rows)",
+                    ng2, ig2, pruned_block_height, pruned_rows
+                );
+            }
             println!("{}  {}  • status message:", ng2, ig2);
             if !message.is_empty() {
                 println!("{message}");
@@ -1,8 +1,10 @@
 pub mod forc_index_auth;
 pub mod forc_index_build;
 pub mod forc_index_check;
+pub mod forc_index_debug;
 pub mod forc_index_deploy;
 pub mod forc_index_kill;
+pub mod forc_index_logs;
 pub mod forc_index_new;
 pub mod forc_index_remove;
 pub mod forc_index_start;